@@ -0,0 +1,66 @@
+use biomod::client::BioModServerClient;
+use biomod::consensus::{ConsensusManager, ConsensusStatus, PerformanceMetrics};
+use biomod::model::{
+    unix_timestamp, NucleotideSequence, QualityMetrics, SequenceData, ValidatorInfo,
+};
+use solana_program::pubkey::Pubkey;
+
+#[tokio::main]
+async fn main() {
+    // Initialize the BioMod server client
+    let bio_mod_client = BioModServerClient::new("https://biomod.server.com");
+
+    // Sample validators
+    let validators = vec![
+        ValidatorInfo::new(Pubkey::new_from_array([1; 32])),
+        ValidatorInfo::new(Pubkey::new_from_array([2; 32])),
+    ];
+
+    // Initialize the Consensus Manager
+    let mut consensus_manager =
+        ConsensusManager::new(validators, 80, bio_mod_client).expect("80% is a valid threshold");
+
+    // Example sequence data
+    let sequence = NucleotideSequence::from_ascii(b"ACGTACGT").unwrap();
+    let quality_metrics = QualityMetrics {
+        coverage_depth: 35,
+        quality_score: 32.5,
+        error_rate: 0.0009,
+        confidence_intervals: vec![],
+        insert_size: None,
+    };
+    let validator_nodes = vec![
+        Pubkey::new_from_array([3; 32]),
+        Pubkey::new_from_array([4; 32]),
+    ];
+    let sequence_data = SequenceData::new(
+        &sequence,
+        validator_nodes,
+        quality_metrics,
+        unix_timestamp(),
+        86_400,
+    );
+
+    // Process consensus
+    match consensus_manager.process_consensus(sequence_data).await {
+        Ok(report) => {
+            match report.status {
+                ConsensusStatus::Confirmed => println!("Consensus confirmed."),
+                ConsensusStatus::Pending => println!("Consensus pending."),
+                ConsensusStatus::Rejected => println!("Sequence rejected by validators."),
+                ConsensusStatus::Disputed => println!("Validators disagree on the merkle root."),
+                ConsensusStatus::Expired => println!("Sequence expired before consensus."),
+            }
+            for (validator, error) in &report.failures {
+                println!("No proof from {}: {:?}", validator, error);
+            }
+        }
+        Err(e) => println!("Error processing consensus: {:?}", e),
+    }
+
+    // Metrics tracking
+    let mut performance_metrics = PerformanceMetrics::default();
+
+    performance_metrics.update_metrics(true, 100);
+    performance_metrics.print_metrics();
+}
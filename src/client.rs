@@ -0,0 +1,203 @@
+use crate::error::ValidationError;
+use crate::model::{unix_timestamp, SequenceData};
+use crate::proof::ValidationProof;
+use reqwest::{Client, Error};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::time::Duration;
+
+const STATUS_POLL_ATTEMPTS: usize = 5;
+const STATUS_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BioModServerResponse {
+    pub sequence_id: String,
+    pub status: String,
+    pub message: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct BioModServerRequest {
+    pub sequence_data: SequenceData,
+    pub validation_status: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidationRequest {
+    pub sequence_data: SequenceData,
+    pub validator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ValidationResponse {
+    pub status: String,
+    pub sequence_id: String,
+    pub validation_proof: Option<ValidationProof>,
+    pub error_message: Option<String>,
+}
+
+pub struct BioModServerClient {
+    client: Client,
+    base_url: String,
+}
+
+impl BioModServerClient {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.to_string(),
+        }
+    }
+
+    pub async fn send_sequence_data(
+        &self,
+        sequence_data: &SequenceData,
+    ) -> Result<BioModServerResponse, Error> {
+        let request_body = BioModServerRequest {
+            sequence_data: sequence_data.clone(),
+            validation_status: "Pending".to_string(),
+        };
+
+        let response = self
+            .client
+            .post(format!("{}/api/validate", self.base_url))
+            .json(&request_body)
+            .send()
+            .await?;
+
+        response.json::<BioModServerResponse>().await
+    }
+
+    pub async fn get_sequence_status(
+        &self,
+        sequence_id: &str,
+    ) -> Result<BioModServerResponse, Error> {
+        let response = self
+            .client
+            .get(format!("{}/api/status/{}", self.base_url, sequence_id))
+            .send()
+            .await?;
+
+        response.json::<BioModServerResponse>().await
+    }
+
+    pub async fn send_validation_request(
+        &self,
+        request: ValidationRequest,
+    ) -> Result<ValidationResponse, Error> {
+        let url = format!("{}/api/validate_sequence", self.base_url);
+
+        let response = self.client.post(url).json(&request).send().await?;
+
+        response.json::<ValidationResponse>().await
+    }
+
+    /// Same endpoint as `get_sequence_status`, read as a `ValidationResponse`
+    /// so any relayed validator proof comes back with it.
+    pub async fn get_validation_status(
+        &self,
+        sequence_id: &str,
+    ) -> Result<ValidationResponse, Error> {
+        let url = format!("{}/api/status/{}", self.base_url, sequence_id);
+
+        let response = self.client.get(url).send().await?;
+
+        response.json::<ValidationResponse>().await
+    }
+
+    /// Submits `sequence_data` on behalf of `validator` and fails unless the
+    /// server reports success.
+    pub async fn submit_sequence(
+        &self,
+        sequence_data: SequenceData,
+        validator: Pubkey,
+    ) -> Result<ValidationResponse, ValidationError> {
+        let request = ValidationRequest {
+            sequence_data,
+            validator,
+            timestamp: unix_timestamp(),
+        };
+
+        let response = self
+            .send_validation_request(request)
+            .await
+            .map_err(|e| ValidationError::ServerError(e.to_string()))?;
+
+        if response.status != "Success" {
+            let reason = response
+                .error_message
+                .unwrap_or_else(|| "Validation failed".to_string());
+            return Err(ValidationError::ServerError(reason));
+        }
+
+        Ok(response)
+    }
+}
+
+pub struct SequenceValidator {
+    server_client: BioModServerClient,
+}
+
+impl SequenceValidator {
+    pub fn new(server_client: BioModServerClient) -> Self {
+        Self { server_client }
+    }
+
+    pub async fn validate_sequence(
+        &self,
+        sequence_data: SequenceData,
+    ) -> Result<(), ValidationError> {
+        // Step 1: Send sequence data to BioMod server for initial validation
+        let response = self.server_client.send_sequence_data(&sequence_data).await;
+
+        match response {
+            Ok(resp) => {
+                if resp.status == "Accepted" {
+                    println!("Validation started for sequence {}", resp.sequence_id);
+                    self.wait_for_validation(&resp.sequence_id).await?;
+                } else {
+                    return Err(ValidationError::ServerError(
+                        "Failed to accept sequence data".to_string(),
+                    ));
+                }
+            }
+            Err(_) => {
+                return Err(ValidationError::ServerError(
+                    "Unable to connect to BioMod server".to_string(),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn wait_for_validation(&self, sequence_id: &str) -> Result<(), ValidationError> {
+        // Poll the BioMod server for sequence validation status
+        for _ in 0..STATUS_POLL_ATTEMPTS {
+            let status_response = self.server_client.get_sequence_status(sequence_id).await;
+
+            match status_response {
+                Ok(resp) => {
+                    if resp.status == "Validated" {
+                        println!("Validation complete for sequence {}", sequence_id);
+                        return Ok(());
+                    }
+                    println!("Waiting for validation...");
+                }
+                Err(_) => {
+                    return Err(ValidationError::ServerError(
+                        "Failed to retrieve validation status".to_string(),
+                    ));
+                }
+            }
+
+            // Wait before retrying
+            tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+        }
+
+        Err(ValidationError::Timeout(
+            "Validation took too long".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,425 @@
+use crate::consensus::{
+    latest_votes, weigh_consensus, ConsensusError, ConsensusOutcome, ConsensusStatus,
+};
+use crate::model::ValidatorInfo;
+use crate::proof::ValidationProof;
+use crate::security::FULL_WEIGHT;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// The validators whose latest acceptance names `merkle_root`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RootGroup {
+    pub merkle_root: [u8; 32],
+    pub weight: u128, // Sum of `ValidatorInfo::consensus_weight`
+    pub proofs: Vec<ValidationProof>,
+}
+
+impl RootGroup {
+    pub fn validators(&self) -> impl Iterator<Item = Pubkey> + '_ {
+        self.proofs.iter().map(|proof| proof.validator)
+    }
+}
+
+/// A validator's own signed acceptance of a root other than the one the
+/// sequence was confirmed with, together with the signed acceptances that
+/// confirmed it, so anyone can check the claim without trusting the
+/// reporter.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConflictEvidence {
+    pub validator: Pubkey,
+    pub sequence_hash: [u8; 32],
+    pub finalized_root: [u8; 32],
+    pub proof: ValidationProof,
+    pub finalizing_proofs: Vec<ValidationProof>, // Acceptances of `finalized_root` that confirmed the sequence
+}
+
+impl ConflictEvidence {
+    /// Checks that the proof is the validator's signed acceptance of this
+    /// sequence and names a different root, and that `finalizing_proofs`
+    /// are signed acceptances of `finalized_root`, one per member of
+    /// `validators` and none after `expiration`, reaching the supermajority
+    /// at full weight. The accused must not have superseded the proof with
+    /// an acceptance of `finalized_root`: only a validator's latest vote
+    /// counts, ordered as in `latest_votes`.
+    pub fn verify(
+        &self,
+        validators: &[ValidatorInfo],
+        quorum: usize,
+        supermajority_percentage: u8,
+        expiration: i64,
+    ) -> Result<(), ConsensusError> {
+        if self.proof.validator != self.validator {
+            return Err(ConsensusError::InvalidSignature);
+        }
+        if self.proof.sequence_hash != self.sequence_hash {
+            return Err(ConsensusError::SequenceMismatch);
+        }
+        if !self.proof.verdict.is_accept() || self.proof.merkle_root == self.finalized_root {
+            return Err(ConsensusError::InvalidProof);
+        }
+        self.proof
+            .verify_signature()
+            .map_err(|_| ConsensusError::InvalidSignature)?;
+
+        let accused = (self.proof.timestamp, self.proof.signing_digest());
+        let mut finalizers = HashSet::new();
+        for proof in &self.finalizing_proofs {
+            if proof.sequence_hash != self.sequence_hash {
+                return Err(ConsensusError::SequenceMismatch);
+            }
+            if !proof.verdict.is_accept() || proof.merkle_root != self.finalized_root {
+                return Err(ConsensusError::UnfinalizedRoot);
+            }
+            if !validators.iter().any(|info| info.pubkey == proof.validator) {
+                return Err(ConsensusError::UnknownValidator);
+            }
+            if !finalizers.insert(proof.validator) {
+                return Err(ConsensusError::ReplayedProof);
+            }
+            if proof.timestamp > expiration
+                || (proof.validator == self.validator
+                    && (proof.timestamp, proof.signing_digest()) >= accused)
+            {
+                return Err(ConsensusError::StaleProof);
+            }
+            proof
+                .verify_signature()
+                .map_err(|_| ConsensusError::InvalidSignature)?;
+        }
+        let approving = self
+            .finalizing_proofs
+            .iter()
+            .map(|proof| (proof.validator, FULL_WEIGHT))
+            .collect();
+        match weigh_consensus(validators, &approving, quorum, supermajority_percentage) {
+            ConsensusOutcome::Reached => Ok(()),
+            _ => Err(ConsensusError::UnfinalizedRoot),
+        }
+    }
+}
+
+/// Acceptances of one sequence grouped by the merkle root they claim,
+/// heaviest group first. Each validator counts once, by its latest vote as
+/// in `settle_consensus`, and only if that vote is an acceptance: a later
+/// rejection withdraws an earlier acceptance. Validators outside the set are
+/// left out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConflictReport {
+    pub sequence_hash: [u8; 32],
+    pub groups: Vec<RootGroup>,
+}
+
+impl ConflictReport {
+    pub fn new(
+        sequence_hash: [u8; 32],
+        validators: &[ValidatorInfo],
+        validations: &[ValidationProof],
+    ) -> Self {
+        let weights: HashMap<Pubkey, u128> = validators
+            .iter()
+            .map(|info| (info.pubkey, info.consensus_weight()))
+            .collect();
+        let votes = validations.iter().filter(|proof| {
+            proof.sequence_hash == sequence_hash && weights.contains_key(&proof.validator)
+        });
+        let latest: BTreeMap<Pubkey, &ValidationProof> = latest_votes(votes)
+            .into_iter()
+            .filter(|(_, proof)| proof.verdict.is_accept())
+            .collect();
+        let mut by_root: BTreeMap<[u8; 32], RootGroup> = BTreeMap::new();
+        for (validator, proof) in latest {
+            let group = by_root
+                .entry(proof.merkle_root)
+                .or_insert_with(|| RootGroup {
+                    merkle_root: proof.merkle_root,
+                    weight: 0,
+                    proofs: Vec::new(),
+                });
+            group.weight += weights[&validator];
+            group.proofs.push(proof.clone());
+        }
+        let mut groups: Vec<RootGroup> = by_root.into_values().collect();
+        groups.sort_by(|a, b| {
+            b.weight
+                .cmp(&a.weight)
+                .then(a.merkle_root.cmp(&b.merkle_root))
+        });
+
+        Self {
+            sequence_hash,
+            groups,
+        }
+    }
+
+    pub fn is_conflicting(&self) -> bool {
+        self.groups.len() > 1
+    }
+
+    /// The heaviest root, unless another is just as heavy.
+    pub fn majority(&self) -> Option<&RootGroup> {
+        match self.groups.as_slice() {
+            [first, second, ..] if first.weight == second.weight => None,
+            [first, ..] => Some(first),
+            [] => None,
+        }
+    }
+
+    /// Every root but the majority one.
+    pub fn minorities(&self) -> &[RootGroup] {
+        let skip = self.majority().map_or(0, |_| 1);
+        &self.groups[skip..]
+    }
+
+    /// Validators whose latest acceptance names a root other than
+    /// `finalized_root`.
+    pub fn dissenters(&self, finalized_root: &[u8; 32]) -> Vec<Pubkey> {
+        self.groups
+            .iter()
+            .filter(|group| group.merkle_root != *finalized_root)
+            .flat_map(RootGroup::validators)
+            .collect()
+    }
+
+    /// Evidence against every validator whose latest acceptance names a
+    /// root other than `finalized_root`, backed by the acceptances of
+    /// `finalized_root`.
+    pub fn evidence(&self, finalized_root: &[u8; 32]) -> Vec<ConflictEvidence> {
+        let finalizing_proofs = self
+            .groups
+            .iter()
+            .find(|group| group.merkle_root == *finalized_root)
+            .map_or_else(Vec::new, |group| group.proofs.clone());
+        self.groups
+            .iter()
+            .filter(|group| group.merkle_root != *finalized_root)
+            .flat_map(|group| &group.proofs)
+            .map(|proof| ConflictEvidence {
+                validator: proof.validator,
+                sequence_hash: self.sequence_hash,
+                finalized_root: *finalized_root,
+                proof: proof.clone(),
+                finalizing_proofs: finalizing_proofs.clone(),
+            })
+            .collect()
+    }
+
+    /// Evidence once the sequence is confirmed, against the majority root;
+    /// none before.
+    pub fn slashing_evidence(&self, status: ConsensusStatus) -> Vec<ConflictEvidence> {
+        match (status, self.majority()) {
+            (ConsensusStatus::Confirmed, Some(majority)) => self.evidence(&majority.merkle_root),
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proof::RejectReason;
+    use ed25519_dalek::SigningKey;
+
+    const HASH: [u8; 32] = [5; 32];
+    const EXPIRATION: i64 = 100;
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn validator(seed: u8, stake: u64) -> ValidatorInfo {
+        ValidatorInfo {
+            stake,
+            ..ValidatorInfo::new(Pubkey::new_from_array(
+                signing_key(seed).verifying_key().to_bytes(),
+            ))
+        }
+    }
+
+    fn accept(seed: u8, root: u8, timestamp: i64) -> ValidationProof {
+        ValidationProof::new_signed(&signing_key(seed), HASH, [root; 32], vec![], timestamp)
+    }
+
+    #[test]
+    fn test_conflict_report_groups_roots() {
+        let validators = vec![
+            validator(1, 100),
+            validator(2, 100),
+            validator(3, 300),
+            validator(4, 100),
+            validator(5, 100),
+        ];
+        let reject = |seed: u8, timestamp: i64| {
+            ValidationProof::new_signed_rejection(
+                &signing_key(seed),
+                HASH,
+                [0xdd; 32],
+                vec![],
+                timestamp,
+                RejectReason::Other,
+            )
+        };
+        let validations = vec![
+            accept(1, 0xaa, 1),
+            // Validator 2 changes its mind; only its latest vote is grouped,
+            // wherever it sits in the slice.
+            accept(2, 0xaa, 2),
+            accept(2, 0xbb, 1),
+            accept(3, 0xaa, 1),
+            // An earlier rejection leaves a later acceptance standing, while a
+            // later one withdraws the acceptance.
+            accept(4, 0xcc, 1),
+            reject(4, 0),
+            reject(5, 2),
+            accept(5, 0xdd, 1),
+            accept(9, 0xee, 1),
+            ValidationProof::new_signed(&signing_key(1), [6; 32], [0xff; 32], vec![], 1),
+        ];
+        let report = ConflictReport::new(HASH, &validators, &validations);
+        assert!(report.is_conflicting());
+        let roots: Vec<[u8; 32]> = report
+            .groups
+            .iter()
+            .map(|group| group.merkle_root)
+            .collect();
+        assert_eq!(roots, vec![[0xaa; 32], [0xcc; 32]]);
+        let majority = report.majority().unwrap();
+        assert_eq!(majority.validators().count(), 3);
+        assert_eq!(majority.weight, 5 * validators[3].consensus_weight());
+        assert_eq!(report.minorities().len(), 1);
+        assert_eq!(report.dissenters(&[0xaa; 32]), vec![validators[3].pubkey]);
+
+        // Two equally heavy roots: no majority, both are minorities.
+        let split = ConflictReport::new(
+            HASH,
+            &validators[..2],
+            &[accept(1, 0xaa, 1), accept(2, 0xbb, 1)],
+        );
+        assert_eq!(split.majority(), None);
+        assert_eq!(split.minorities().len(), 2);
+        assert!(!ConflictReport::new(HASH, &validators, &[accept(1, 0xaa, 1)]).is_conflicting());
+    }
+
+    #[test]
+    fn test_conflict_evidence() {
+        let validators = vec![validator(1, 100), validator(2, 100), validator(3, 50)];
+        let validations = vec![accept(1, 0xaa, 1), accept(2, 0xaa, 1), accept(3, 0xbb, 1)];
+        let report = ConflictReport::new(HASH, &validators, &validations);
+        assert!(report
+            .slashing_evidence(ConsensusStatus::Pending)
+            .is_empty());
+
+        let evidence = report.slashing_evidence(ConsensusStatus::Confirmed);
+        assert_eq!(evidence.len(), 1);
+        let item = &evidence[0];
+        assert_eq!(
+            (item.validator, item.finalized_root),
+            (validators[2].pubkey, [0xaa; 32])
+        );
+        assert_eq!(item.finalizing_proofs.len(), 2);
+        assert_eq!(item.verify(&validators, 2, 67, EXPIRATION), Ok(()));
+        let bytes = borsh::to_vec(item).unwrap();
+        assert_eq!(&ConflictEvidence::try_from_slice(&bytes).unwrap(), item);
+        let json = serde_json::to_string(item).unwrap();
+        assert_eq!(
+            &serde_json::from_str::<ConflictEvidence>(&json).unwrap(),
+            item
+        );
+
+        // Evidence that does not hold up is refused.
+        let agreeing = ConflictEvidence {
+            finalized_root: [0xbb; 32],
+            ..item.clone()
+        };
+        assert_eq!(
+            agreeing.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::InvalidProof)
+        );
+        let framed = ConflictEvidence {
+            validator: validators[1].pubkey,
+            ..item.clone()
+        };
+        assert_eq!(
+            framed.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::InvalidSignature)
+        );
+        let mut forged = item.clone();
+        forged.proof.merkle_root = [0xdd; 32];
+        assert_eq!(
+            forged.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::InvalidSignature)
+        );
+
+        // So is evidence against a root that was never finalized, or whose
+        // backing acceptances fall short or do not hold up.
+        assert_eq!(
+            item.verify(&validators, 3, 67, EXPIRATION),
+            Err(ConsensusError::UnfinalizedRoot)
+        );
+        assert_eq!(
+            item.verify(&validators, 2, 90, EXPIRATION),
+            Err(ConsensusError::UnfinalizedRoot)
+        );
+        let unbacked = &report.evidence(&[0xcc; 32])[0];
+        assert!(unbacked.finalizing_proofs.is_empty());
+        assert_eq!(
+            unbacked.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::UnfinalizedRoot)
+        );
+        let mut padded = item.clone();
+        padded.finalizing_proofs.push(accept(3, 0xbb, 1));
+        assert_eq!(
+            padded.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::UnfinalizedRoot)
+        );
+        let mut tampered = item.clone();
+        tampered.finalizing_proofs[0].timestamp = 2;
+        assert_eq!(
+            tampered.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::InvalidSignature)
+        );
+
+        // Finalizing votes must come from distinct members of the set, in time.
+        let mut outsider = item.clone();
+        outsider.finalizing_proofs.push(accept(9, 0xaa, 1));
+        assert_eq!(
+            outsider.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::UnknownValidator)
+        );
+        let mut doubled = item.clone();
+        doubled.finalizing_proofs.push(accept(1, 0xaa, 1));
+        assert_eq!(
+            doubled.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::ReplayedProof)
+        );
+        let mut late = item.clone();
+        late.finalizing_proofs[0] = accept(1, 0xaa, EXPIRATION + 1);
+        assert_eq!(
+            late.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::StaleProof)
+        );
+
+        // A vote the accused has since corrected is no evidence, even at the
+        // same timestamp if the correction wins the tie-break.
+        let mut corrected = item.clone();
+        corrected.finalizing_proofs.push(accept(3, 0xaa, 2));
+        assert_eq!(
+            corrected.verify(&validators, 2, 67, EXPIRATION),
+            Err(ConsensusError::StaleProof)
+        );
+        let tied = accept(3, 0xaa, 1);
+        let mut simultaneous = item.clone();
+        simultaneous.finalizing_proofs.push(tied.clone());
+        let expected = if tied.signing_digest() >= item.proof.signing_digest() {
+            Err(ConsensusError::StaleProof)
+        } else {
+            Ok(())
+        };
+        assert_eq!(
+            simultaneous.verify(&validators, 2, 67, EXPIRATION),
+            expected
+        );
+    }
+}
@@ -0,0 +1,1690 @@
+pub mod conflict;
+pub mod round;
+
+use crate::client::{BioModServerClient, ValidationRequest};
+use crate::error::ValidationError;
+use crate::model::{
+    unix_timestamp, QualityMetrics, SequenceData, ValidationThresholds, ValidatorInfo,
+};
+use crate::oracle::OracleNetworkManager;
+use crate::proof::{ProofValidator, SignatureError, ValidationProof};
+use crate::security::{SecurityError, FULL_WEIGHT};
+use borsh::{BorshDeserialize, BorshSerialize};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::future::Future;
+use std::time::Duration;
+
+pub use conflict::{ConflictEvidence, ConflictReport, RootGroup};
+pub use round::{
+    ConsensusEngine, ConsensusRound, RoundPhase, ValidatorState, DEFAULT_MAX_ROUNDS,
+    DEFAULT_ROUND_DURATION,
+};
+
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub enum ConsensusStatus {
+    Confirmed,
+    Pending,
+    /// Validators holding the supermajority voted against the sequence.
+    Rejected,
+    /// Enough validators accept the sequence but they disagree on its
+    /// merkle root. Later proofs can still settle it.
+    Disputed,
+    /// `SequenceData.expiration` passed before the sequence was settled.
+    Expired,
+}
+
+impl ConsensusStatus {
+    /// Confirmed, rejected and expired sequences take no further proofs.
+    pub fn is_settled(self) -> bool {
+        matches!(
+            self,
+            ConsensusStatus::Confirmed | ConsensusStatus::Rejected | ConsensusStatus::Expired
+        )
+    }
+}
+
+/// Why a set of validations did or did not settle a sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConsensusOutcome {
+    Reached,
+    /// Fewer distinct validators than the quorum have validated.
+    InsufficientQuorum,
+    /// The validations hold exactly half the total weight, short of the
+    /// supermajority.
+    Tie,
+    BelowSupermajority,
+}
+
+/// Weighs the validators in `approving` against all of `validators` by
+/// `ValidatorInfo::consensus_weight`, each approval scaled by its percentage
+/// in `approving` (`FULL_WEIGHT` for a full vote). Consensus needs at least
+/// `quorum` distinct approving validators holding `supermajority_percentage`
+/// of the total weight; approvals from outside the set or at zero weight are
+/// ignored.
+pub fn weigh_consensus(
+    validators: &[ValidatorInfo],
+    approving: &HashMap<Pubkey, u8>,
+    quorum: usize,
+    supermajority_percentage: u8,
+) -> ConsensusOutcome {
+    let (mut approving_count, mut approving_weight, mut total_weight) = (0, 0u128, 0u128);
+    for validator in validators {
+        let weight = validator.consensus_weight();
+        total_weight += weight * FULL_WEIGHT as u128;
+        match approving.get(&validator.pubkey) {
+            Some(&share) if share > 0 => {
+                approving_count += 1;
+                approving_weight += weight * share.min(FULL_WEIGHT) as u128;
+            }
+            _ => {}
+        }
+    }
+
+    if approving_count < quorum.max(1) {
+        ConsensusOutcome::InsufficientQuorum
+    } else if approving_weight * 100 >= total_weight * supermajority_percentage as u128 {
+        ConsensusOutcome::Reached
+    } else if approving_weight * 2 == total_weight {
+        ConsensusOutcome::Tie
+    } else {
+        ConsensusOutcome::BelowSupermajority
+    }
+}
+
+/// Each validator's latest vote in `validations`: the one with the highest
+/// `timestamp`, with ties going to the higher signing digest so the choice
+/// does not depend on the order of the slice.
+pub(crate) fn latest_votes<'a>(
+    validations: impl IntoIterator<Item = &'a ValidationProof>,
+) -> HashMap<Pubkey, &'a ValidationProof> {
+    let mut latest: HashMap<Pubkey, &'a ValidationProof> = HashMap::new();
+    for proof in validations {
+        latest
+            .entry(proof.validator)
+            .and_modify(|current| {
+                if (proof.timestamp, proof.signing_digest())
+                    > (current.timestamp, current.signing_digest())
+                {
+                    *current = proof;
+                }
+            })
+            .or_insert(proof);
+    }
+    latest
+}
+
+/// Status implied by `validations`, counting each validator's latest vote
+/// as chosen by `latest_votes`.
+/// Rejection is checked first, then acceptance of a single merkle root; if
+/// the acceptances only reach the supermajority across several roots the
+/// sequence is disputed. Each outcome needs `quorum` distinct validators,
+/// as in `weigh_consensus`, with each vote scaled by `vote_weight`, such as
+/// `CalibrationPolicy::vote_weight`. Expiry is left to the caller.
+pub fn settle_consensus(
+    validators: &[ValidatorInfo],
+    validations: &[ValidationProof],
+    quorum: usize,
+    supermajority_percentage: u8,
+    vote_weight: impl Fn(&ValidatorInfo, &ValidationProof) -> u8,
+) -> ConsensusStatus {
+    let votes = latest_votes(validations);
+    let weights: HashMap<Pubkey, u8> = validators
+        .iter()
+        .filter_map(|info| {
+            votes
+                .get(&info.pubkey)
+                .map(|proof| (info.pubkey, vote_weight(info, proof)))
+        })
+        .collect();
+    let reached = |voters: &HashSet<Pubkey>| {
+        let approving = voters
+            .iter()
+            .filter_map(|voter| weights.get(voter).map(|&weight| (*voter, weight)))
+            .collect();
+        weigh_consensus(validators, &approving, quorum, supermajority_percentage)
+            == ConsensusOutcome::Reached
+    };
+
+    let rejecting: HashSet<Pubkey> = votes
+        .values()
+        .filter(|proof| !proof.verdict.is_accept())
+        .map(|proof| proof.validator)
+        .collect();
+    if reached(&rejecting) {
+        return ConsensusStatus::Rejected;
+    }
+    let mut by_root: BTreeMap<[u8; 32], HashSet<Pubkey>> = BTreeMap::new();
+    for proof in votes.values().filter(|proof| proof.verdict.is_accept()) {
+        by_root
+            .entry(proof.merkle_root)
+            .or_default()
+            .insert(proof.validator);
+    }
+    if by_root.values().any(reached) {
+        ConsensusStatus::Confirmed
+    } else if by_root.len() > 1 && reached(&by_root.values().flatten().copied().collect()) {
+        ConsensusStatus::Disputed
+    } else {
+        ConsensusStatus::Pending
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConsensusError {
+    ProofError(String),
+    ServerError(String),
+    OracleError(String),
+    UnknownValidator,
+    InvalidSignature,
+    MalformedSignature,
+    ReplayedProof,
+    SequenceMismatch,
+    StaleProof,
+    InvalidProof,
+    QualityBelowThreshold,
+    SequenceSettled,
+    RoundInProgress,
+    NoActiveRound,
+    Timeout,
+    TooManyFailures(ConsensusReport), // More validators failed than the round tolerates; still `Pending`, with the proofs that passed
+    CalibrationFailed(SecurityError),
+    UnfinalizedRoot, // Conflict evidence whose finalized root lacks a supermajority
+}
+
+pub const DEFAULT_COLLECTION_CONCURRENCY: usize = 8;
+pub const DEFAULT_VALIDATOR_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The decision of one collection round together with what it was based on.
+#[derive(Debug, PartialEq)]
+pub struct ConsensusReport {
+    pub status: ConsensusStatus,
+    pub proofs: Vec<ValidationProof>,
+    pub failures: Vec<(Pubkey, ConsensusError)>, // Validators that returned no usable proof, and why
+}
+
+/// Runs `collect` for every validator, at most `concurrency` at once and
+/// each cut off after `timeout`. Results keep the validators' order.
+async fn fan_out<'a, F, Fut>(
+    validators: &'a [ValidatorInfo],
+    concurrency: usize,
+    timeout: Duration,
+    collect: F,
+) -> Vec<(Pubkey, Result<ValidationProof, ConsensusError>)>
+where
+    F: Fn(&'a ValidatorInfo) -> Fut,
+    Fut: Future<Output = Result<ValidationProof, ConsensusError>> + 'a,
+{
+    stream::iter(validators)
+        .map(|validator| {
+            let proof = tokio::time::timeout(timeout, collect(validator));
+            async move {
+                (
+                    validator.pubkey,
+                    proof.await.unwrap_or(Err(ConsensusError::Timeout)),
+                )
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await
+}
+
+pub struct ConsensusManager {
+    validators: Vec<ValidatorInfo>,
+    thresholds: ValidationThresholds,
+    quorum: usize,
+    current_round: u64,
+    seen_proofs: HashSet<[u8; 32]>,
+    proof_validator: ProofValidator,
+    server_client: BioModServerClient,
+    oracle_network: Option<OracleNetworkManager>, // Reaches each validator's own node; `None` asks through the server
+    collection_concurrency: usize,
+    validator_timeout: Duration,
+    max_faulty: Option<usize>, // `None` tolerates the BFT bound of (n - 1) / 3
+}
+
+impl ConsensusManager {
+    /// `validation_threshold` is the percentage of the validators' combined
+    /// weight that must agree, from 1 to 100; the remaining thresholds start
+    /// at their defaults and any single validator is a quorum.
+    pub fn new(
+        validators: Vec<ValidatorInfo>,
+        validation_threshold: u8,
+        server_client: BioModServerClient,
+    ) -> Result<Self, ValidationError> {
+        let thresholds = ValidationThresholds {
+            consensus_percentage: validation_threshold,
+            ..ValidationThresholds::default()
+        };
+        thresholds.validate()?;
+        Ok(Self {
+            validators,
+            thresholds,
+            quorum: 1,
+            current_round: 0,
+            seen_proofs: HashSet::new(),
+            proof_validator: ProofValidator::default(),
+            server_client,
+            oracle_network: None,
+            collection_concurrency: DEFAULT_COLLECTION_CONCURRENCY,
+            validator_timeout: DEFAULT_VALIDATOR_TIMEOUT,
+            max_faulty: None,
+        })
+    }
+
+    pub fn with_thresholds(
+        mut self,
+        thresholds: ValidationThresholds,
+    ) -> Result<Self, ValidationError> {
+        thresholds.validate()?;
+        self.thresholds = thresholds;
+        Ok(self)
+    }
+
+    /// Distinct validators required before consensus can be reached,
+    /// whatever their weight.
+    pub fn with_quorum(mut self, quorum: usize) -> Self {
+        self.quorum = quorum;
+        self
+    }
+
+    pub fn with_proof_validator(mut self, proof_validator: ProofValidator) -> Self {
+        self.proof_validator = proof_validator;
+        self
+    }
+
+    /// Collects each validator's proof from its own node through
+    /// `oracle_network` rather than through the BioMod server.
+    pub fn with_oracle_network(mut self, oracle_network: OracleNetworkManager) -> Self {
+        self.oracle_network = Some(oracle_network);
+        self
+    }
+
+    /// Validators queried at once by `process_consensus`.
+    pub fn with_collection_concurrency(mut self, concurrency: usize) -> Self {
+        self.collection_concurrency = concurrency;
+        self
+    }
+
+    pub fn with_validator_timeout(mut self, timeout: Duration) -> Self {
+        self.validator_timeout = timeout;
+        self
+    }
+
+    /// Validators that may fail to return a proof before a round is
+    /// abandoned.
+    pub fn with_max_faulty(mut self, max_faulty: usize) -> Self {
+        self.max_faulty = Some(max_faulty);
+        self
+    }
+
+    pub fn current_round(&self) -> u64 {
+        self.current_round
+    }
+
+    fn max_faulty(&self) -> usize {
+        self.max_faulty
+            .unwrap_or(self.validators.len().saturating_sub(1) / 3)
+    }
+
+    pub async fn validate_with_consensus(
+        &mut self,
+        sequence_data: SequenceData,
+    ) -> Result<ConsensusReport, ConsensusError> {
+        // Step 1: Send sequence data to server for initial validation
+        let validation_response = self.server_client.send_sequence_data(&sequence_data).await;
+
+        match validation_response {
+            Ok(resp) => {
+                if resp.status == "Accepted" {
+                    println!("Sequence {} validation initiated.", resp.sequence_id);
+                } else {
+                    return Err(ConsensusError::ServerError(
+                        "Failed to accept validation".to_string(),
+                    ));
+                }
+            }
+            Err(_) => {
+                return Err(ConsensusError::ServerError(
+                    "Unable to connect to BioMod server".to_string(),
+                ))
+            }
+        }
+
+        // Step 2: Collect validation proofs from the validators
+        self.process_consensus(sequence_data).await
+    }
+
+    /// Collects a proof from every validator concurrently as one round.
+    /// Validators that fail or time out are reported alongside the decision;
+    /// more failures than `max_faulty` abandon the round.
+    pub async fn process_consensus(
+        &mut self,
+        sequence_data: SequenceData,
+    ) -> Result<ConsensusReport, ConsensusError> {
+        self.current_round += 1;
+        let now = unix_timestamp();
+        let results = fan_out(
+            &self.validators,
+            self.collection_concurrency,
+            self.validator_timeout,
+            |validator| self.collect_validation_proof(&sequence_data, validator),
+        )
+        .await;
+        self.decide(&sequence_data, results, now)
+    }
+
+    /// Puts each collected proof through the checks `process_validation`
+    /// applies, counting the ones that fail as failures. An abandoned round
+    /// returns the proofs that passed in `TooManyFailures` without marking
+    /// them seen, so they can still be submitted; otherwise they are marked
+    /// seen and settle the round.
+    fn decide(
+        &mut self,
+        sequence_data: &SequenceData,
+        results: Vec<(Pubkey, Result<ValidationProof, ConsensusError>)>,
+        now: i64,
+    ) -> Result<ConsensusReport, ConsensusError> {
+        let (mut proofs, mut failures, mut digests) = (Vec::new(), Vec::new(), HashSet::new());
+        for (validator, result) in results {
+            let checked = result.and_then(|proof| {
+                self.verify_validation_proof(sequence_data, &proof, now)?;
+                if !digests.insert(proof.signing_digest()) {
+                    return Err(ConsensusError::ReplayedProof);
+                }
+                Ok(proof)
+            });
+            match checked {
+                Ok(proof) => proofs.push(proof),
+                Err(e) => failures.push((validator, e)),
+            }
+        }
+        if failures.len() > self.max_faulty() {
+            return Err(ConsensusError::TooManyFailures(ConsensusReport {
+                status: ConsensusStatus::Pending,
+                proofs,
+                failures,
+            }));
+        }
+        self.seen_proofs.extend(digests);
+        Ok(ConsensusReport {
+            status: self.check_consensus(&proofs, now),
+            proofs,
+            failures,
+        })
+    }
+
+    /// Asks `validator` for its own proof: from its node through the oracle
+    /// network if one is configured, otherwise with a request the BioMod
+    /// server relays to it.
+    pub async fn collect_validation_proof(
+        &self,
+        sequence_data: &SequenceData,
+        validator: &ValidatorInfo,
+    ) -> Result<ValidationProof, ConsensusError> {
+        let proof = match &self.oracle_network {
+            Some(oracle_network) => {
+                oracle_network
+                    .fetch_validation_proof_from_oracle(&validator.pubkey, sequence_data)
+                    .await?
+            }
+            None => {
+                let request = ValidationRequest {
+                    sequence_data: sequence_data.clone(),
+                    validator: validator.pubkey,
+                    timestamp: unix_timestamp(),
+                };
+                let validation_response = self
+                    .server_client
+                    .send_validation_request(request)
+                    .await
+                    .map_err(|e| ConsensusError::ServerError(e.to_string()))?;
+                match (
+                    validation_response.status.as_str(),
+                    validation_response.validation_proof,
+                ) {
+                    ("Validated", Some(proof)) => proof,
+                    _ => {
+                        return Err(ConsensusError::ProofError(
+                            validation_response
+                                .error_message
+                                .unwrap_or_else(|| "Validation failed".to_string()),
+                        ))
+                    }
+                }
+            }
+        };
+
+        // Servers and oracles only relay proofs; the validator's own signature is what counts.
+        if proof.validator != validator.pubkey {
+            return Err(ConsensusError::InvalidSignature);
+        }
+        self.verify_signature(sequence_data, &proof)?;
+        Ok(proof)
+    }
+
+    fn check_consensus(&self, validations: &[ValidationProof], now: i64) -> ConsensusStatus {
+        let signed: Vec<ValidationProof> = validations
+            .iter()
+            .filter(|&proof| proof.verify_signature().is_ok())
+            .cloned()
+            .collect();
+        self.settle(&signed, now)
+    }
+
+    /// Verifies `new_validation` and records it on `sequence_data`, then
+    /// settles the sequence as of `now`. A sequence past its expiration
+    /// becomes `Expired` without recording the proof; a settled one rejects
+    /// further proofs with `SequenceSettled`.
+    pub fn process_validation(
+        &mut self,
+        sequence_data: &mut SequenceData,
+        new_validation: ValidationProof,
+        now: i64,
+    ) -> Result<ConsensusStatus, ConsensusError> {
+        self.verify_validator(&new_validation.validator)?;
+        if sequence_data.consensus_status.is_settled() {
+            return Err(ConsensusError::SequenceSettled);
+        }
+        if now > sequence_data.expiration {
+            sequence_data.consensus_status = ConsensusStatus::Expired;
+            return Ok(ConsensusStatus::Expired);
+        }
+
+        self.verify_validation_proof(sequence_data, &new_validation, now)?;
+        self.seen_proofs.insert(new_validation.signing_digest());
+
+        let updated_status = self.update_consensus_status(sequence_data, new_validation, now);
+
+        Ok(updated_status)
+    }
+
+    fn verify_validator(&self, validator: &Pubkey) -> Result<(), ConsensusError> {
+        if !self.validators.iter().any(|info| info.pubkey == *validator) {
+            return Err(ConsensusError::UnknownValidator);
+        }
+        Ok(())
+    }
+
+    /// Acceptances must pass `ProofValidator::verify_acceptance` against the
+    /// root and commitment pinned on `sequence_data`, so acceptances of other
+    /// roots are kept as dissent, and under a calibration policy carry an
+    /// instrument it accepts as of `now`.
+    /// Rejections skip the zk, quality and calibration checks: failing them
+    /// is often the reason for the rejection.
+    fn verify_validation_proof(
+        &self,
+        sequence_data: &SequenceData,
+        proof: &ValidationProof,
+        now: i64,
+    ) -> Result<(), ConsensusError> {
+        self.verify_signature(sequence_data, proof)?;
+        if !proof.verdict.is_accept() {
+            return Ok(());
+        }
+
+        if !self.verify_zk_proof(sequence_data, proof) {
+            return Err(ConsensusError::InvalidProof);
+        }
+
+        if !self.verify_quality_metrics(&sequence_data.quality_metrics) {
+            return Err(ConsensusError::QualityBelowThreshold);
+        }
+
+        if let Some(policy) = &self.thresholds.calibration {
+            let validator = self
+                .validators
+                .iter()
+                .find(|info| info.pubkey == proof.validator)
+                .ok_or(ConsensusError::UnknownValidator)?;
+            policy
+                .weight(validator, proof, now)
+                .map_err(ConsensusError::CalibrationFailed)?;
+        }
+
+        Ok(())
+    }
+
+    fn verify_signature(
+        &self,
+        sequence_data: &SequenceData,
+        proof: &ValidationProof,
+    ) -> Result<(), ConsensusError> {
+        if proof.sequence_hash != sequence_data.sequence_hash {
+            return Err(ConsensusError::SequenceMismatch);
+        }
+        if proof.timestamp < sequence_data.timestamp || proof.timestamp > sequence_data.expiration {
+            return Err(ConsensusError::StaleProof);
+        }
+        if self.seen_proofs.contains(&proof.signing_digest()) {
+            return Err(ConsensusError::ReplayedProof);
+        }
+
+        proof.verify_signature().map_err(|e| match e {
+            SignatureError::Missing | SignatureError::Malformed => {
+                ConsensusError::MalformedSignature
+            }
+            _ => ConsensusError::InvalidSignature,
+        })
+    }
+
+    fn verify_zk_proof(&self, sequence_data: &SequenceData, proof: &ValidationProof) -> bool {
+        self.proof_validator
+            .verify_acceptance(
+                &proof.merkle_root,
+                &proof.zk_proof,
+                &sequence_data.merkle_root,
+                &sequence_data.zk_commitment,
+            )
+            .is_ok()
+    }
+
+    fn verify_quality_metrics(&self, metrics: &QualityMetrics) -> bool {
+        self.thresholds.accepts_quality(metrics)
+    }
+
+    fn update_consensus_status(
+        &self,
+        sequence_data: &mut SequenceData,
+        new_validation: ValidationProof,
+        now: i64,
+    ) -> ConsensusStatus {
+        sequence_data.validation_proofs.push(new_validation);
+        sequence_data.consensus_status = self.settle(&sequence_data.validation_proofs, now);
+        sequence_data.consensus_status
+    }
+
+    /// Weighs the validators whose latest vote in `validations` accepts
+    /// against the whole set as of `now`, whatever merkle roots they name.
+    pub fn evaluate(&self, validations: &[ValidationProof], now: i64) -> ConsensusOutcome {
+        let votes = latest_votes(validations);
+        let approving = self
+            .validators
+            .iter()
+            .filter_map(|info| {
+                let proof = votes
+                    .get(&info.pubkey)
+                    .filter(|proof| proof.verdict.is_accept())?;
+                Some((info.pubkey, self.vote_weight(info, proof, now)))
+            })
+            .collect();
+        weigh_consensus(
+            &self.validators,
+            &approving,
+            self.quorum,
+            self.thresholds.consensus_percentage,
+        )
+    }
+
+    fn settle(&self, validations: &[ValidationProof], now: i64) -> ConsensusStatus {
+        settle_consensus(
+            &self.validators,
+            validations,
+            self.quorum,
+            self.thresholds.consensus_percentage,
+            |info, proof| self.vote_weight(info, proof, now),
+        )
+    }
+
+    fn vote_weight(&self, validator: &ValidatorInfo, proof: &ValidationProof, now: i64) -> u8 {
+        self.thresholds.vote_weight(validator, proof, now)
+    }
+
+    /// The sequence's acceptances grouped by the merkle root they claim.
+    pub fn conflict_report(&self, sequence_data: &SequenceData) -> ConflictReport {
+        ConflictReport::new(
+            sequence_data.sequence_hash,
+            &self.validators,
+            &sequence_data.validation_proofs,
+        )
+    }
+
+    pub fn slashing_evidence(&self, sequence_data: &SequenceData) -> Vec<ConflictEvidence> {
+        self.conflict_report(sequence_data)
+            .slashing_evidence(sequence_data.consensus_status)
+    }
+}
+
+pub struct MultiValidator {
+    pub validators: Vec<ValidatorInfo>,
+    pub consensus_threshold: u8,
+}
+
+impl MultiValidator {
+    pub fn validate_sequence(&self, sequence: &crate::model::DNASequence) -> bool {
+        let mut validations = 0;
+        for validator in &self.validators {
+            if validator.validate(sequence) {
+                validations += 1;
+            }
+        }
+        validations >= self.consensus_threshold as usize
+    }
+}
+
+pub struct ValidatorManager {
+    pub validators: Vec<ValidatorInfo>,
+    pub max_active_validators: usize,
+    pub inactive_validator_ttl: i64,
+}
+
+impl ValidatorManager {
+    pub fn add_validator(&mut self, validator: ValidatorInfo) -> Result<(), ValidationError> {
+        if self.validators.len() >= self.max_active_validators {
+            return Err(ValidationError::MaxValidatorsReached);
+        }
+        self.validators.push(validator);
+        Ok(())
+    }
+
+    pub fn cleanup_inactive(&mut self, current_time: i64) {
+        self.validators.retain(|validator| {
+            current_time - validator.last_heartbeat <= self.inactive_validator_ttl
+        });
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct PerformanceMetrics {
+    pub total_validations: u64,
+    pub successful_validations: u64,
+    pub failed_validations: u64,
+    pub average_processing_time_ms: u64,
+    pub consensus_rounds: u64,
+}
+
+impl PerformanceMetrics {
+    pub fn update_metrics(&mut self, successful: bool, processing_time: u64) {
+        if successful {
+            self.successful_validations += 1;
+        } else {
+            self.failed_validations += 1;
+        }
+        self.total_validations += 1;
+        self.average_processing_time_ms =
+            (self.average_processing_time_ms * (self.total_validations - 1) + processing_time)
+                / self.total_validations;
+        self.consensus_rounds += 1;
+    }
+
+    pub fn print_metrics(&self) {
+        println!("Total Validations: {}", self.total_validations);
+        println!("Successful Validations: {}", self.successful_validations);
+        println!("Failed Validations: {}", self.failed_validations);
+        println!(
+            "Average Processing Time: {} ms",
+            self.average_processing_time_ms
+        );
+        println!("Consensus Rounds: {}", self.consensus_rounds);
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct PerformanceTracker {
+    pub avg_validation_time_ms: u64,
+    pub sequences_validated: u64,
+    pub consensus_rounds: u64,
+    pub validator_uptime: HashMap<Pubkey, u64>, // Seconds of uptime per validator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{NucleotideSequence, SequencerModel, MAX_REPUTATION};
+    use crate::oracle::{ModularOracleNetworkClient, OracleNetworkRequest, OracleNetworkResponse};
+    use crate::proof::{InstrumentRecord, MerkleTree, RejectReason, ZkProofBuilder};
+    use crate::security::{CalibrationPolicy, StaleCalibrationAction};
+    use ed25519_dalek::SigningKey;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const NOW: i64 = 1_700_000_200;
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn sample_sequence_data(sequence: &NucleotideSequence) -> SequenceData {
+        let quality_metrics = QualityMetrics {
+            coverage_depth: 35,
+            quality_score: 32.5,
+            error_rate: 0.0009,
+            confidence_intervals: vec![],
+            insert_size: None,
+        };
+        SequenceData::new(
+            sequence,
+            vec![],
+            quality_metrics,
+            1_700_000_000,
+            100_000_000,
+        )
+    }
+
+    fn sample_manager(seeds: &[u8]) -> ConsensusManager {
+        let validators = seeds
+            .iter()
+            .map(|&seed| {
+                ValidatorInfo::new(Pubkey::new_from_array(
+                    signing_key(seed).verifying_key().to_bytes(),
+                ))
+            })
+            .collect();
+        ConsensusManager::new(
+            validators,
+            67,
+            BioModServerClient::new("http://localhost:8080"),
+        )
+        .unwrap()
+    }
+
+    /// The merkle root and zk proof of `bases` and the commitment to pin
+    /// for them.
+    fn zk_proof_for(bases: &[u8]) -> ([u8; 32], Vec<u8>, [u8; 32]) {
+        let sequence = NucleotideSequence::from_ascii(bases).unwrap();
+        let tree = MerkleTree::from_sequence(&sequence);
+        let mut builder = ZkProofBuilder::new();
+        builder
+            .add_length_proof(sequence.len())
+            .add_merkle_proof(&tree);
+        let zk_proof = builder.build().unwrap();
+        let commitment = ProofValidator::default()
+            .zk_commitment(&tree.root(), &zk_proof)
+            .unwrap();
+        (tree.root(), zk_proof, commitment)
+    }
+
+    fn signed_proof(seed: u8, sequence_hash: [u8; 32], timestamp: i64) -> ValidationProof {
+        ValidationProof::new_signed(
+            &signing_key(seed),
+            sequence_hash,
+            [2; 32],
+            vec![3; 48],
+            timestamp,
+        )
+    }
+
+    #[test]
+    fn test_consensus_rejects_replayed_and_foreign_proofs() {
+        let sequence_data = sample_sequence_data(&NucleotideSequence::from_ascii(b"ACGT").unwrap());
+        let hash = sequence_data.sequence_hash;
+        let mut manager = sample_manager(&[7]);
+        let proof = signed_proof(7, hash, 1_700_000_100);
+        assert!(manager.verify_signature(&sequence_data, &proof).is_ok());
+
+        manager.seen_proofs.insert(proof.signing_digest());
+        assert_eq!(
+            manager.verify_signature(&sequence_data, &proof),
+            Err(ConsensusError::ReplayedProof)
+        );
+
+        let other = signed_proof(9, hash, 1_700_000_100);
+        let mut forged = other.clone();
+        forged.validator = signed_proof(10, hash, 1_700_000_100).validator;
+        assert_eq!(
+            manager.verify_signature(&sequence_data, &forged),
+            Err(ConsensusError::InvalidSignature)
+        );
+
+        let mut garbled = other.clone();
+        garbled.signature = vec![0; 12];
+        assert_eq!(
+            manager.verify_signature(&sequence_data, &garbled),
+            Err(ConsensusError::MalformedSignature)
+        );
+
+        let stale = signed_proof(9, hash, 1_600_000_000);
+        assert_eq!(
+            manager.verify_signature(&sequence_data, &stale),
+            Err(ConsensusError::StaleProof)
+        );
+
+        let foreign = signed_proof(9, [4; 32], 1_700_000_100);
+        assert_eq!(
+            manager.verify_signature(&sequence_data, &foreign),
+            Err(ConsensusError::SequenceMismatch)
+        );
+    }
+
+    #[test]
+    fn test_process_validation_reaches_consensus() {
+        let bases = b"GATTACA".repeat(40);
+        let (root, zk_proof, commitment) = zk_proof_for(&bases);
+        let mut sequence_data =
+            sample_sequence_data(&NucleotideSequence::from_ascii(&bases).unwrap())
+                .with_zk_commitment(root, commitment);
+        let mut manager = sample_manager(&[1, 2, 3]);
+        let proof_from = |seed: u8| {
+            ValidationProof::new_signed(
+                &signing_key(seed),
+                sequence_data.sequence_hash,
+                root,
+                zk_proof.clone(),
+                1_700_000_100,
+            )
+        };
+        let (first, second, third, outsider) =
+            (proof_from(1), proof_from(2), proof_from(3), proof_from(4));
+
+        assert_eq!(
+            manager.process_validation(&mut sequence_data, outsider, NOW),
+            Err(ConsensusError::UnknownValidator)
+        );
+        assert_eq!(
+            manager.process_validation(&mut sequence_data, first.clone(), NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            manager.process_validation(&mut sequence_data, first, NOW),
+            Err(ConsensusError::ReplayedProof)
+        );
+        // Two of three is 66%, just short of the 67% threshold.
+        assert_eq!(
+            manager.process_validation(&mut sequence_data, second, NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+
+        let unproven = ValidationProof::new_signed(
+            &signing_key(3),
+            third.sequence_hash,
+            third.merkle_root,
+            vec![],
+            third.timestamp,
+        );
+        assert_eq!(
+            manager.process_validation(&mut sequence_data, unproven, NOW),
+            Err(ConsensusError::InvalidProof)
+        );
+
+        assert_eq!(
+            manager.process_validation(&mut sequence_data, third, NOW),
+            Ok(ConsensusStatus::Confirmed)
+        );
+        assert_eq!(sequence_data.consensus_status, ConsensusStatus::Confirmed);
+        assert_eq!(sequence_data.validation_proofs.len(), 3);
+    }
+
+    /// Validators 1 to 3 with one sequencer each. Validator 3 recalibrated
+    /// last week; the others are past 90 days.
+    fn instrumented_manager() -> (ConsensusManager, Vec<InstrumentRecord>) {
+        const DAY: i64 = 24 * 60 * 60;
+        let sequencer = |serial: &str| SequencerModel {
+            manufacturer: "Illumina".to_string(),
+            model: "NovaSeq 6000".to_string(),
+            serial_number: serial.to_string(),
+            throughput: 6000,
+            error_rate: 0.001,
+            last_calibration: NOW - 100 * DAY,
+        };
+        let mut manager = sample_manager(&[1, 2, 3]);
+        for (info, serial) in manager.validators.iter_mut().zip(["A1", "A2", "A3"]) {
+            info.hardware_specs.sequencer_models.push(sequencer(serial));
+        }
+        manager.validators[2].hardware_specs.sequencer_models[0].last_calibration = NOW - 7 * DAY;
+        let instruments = manager
+            .validators
+            .iter()
+            .map(|info| InstrumentRecord::from(&info.hardware_specs.sequencer_models[0]))
+            .collect();
+        (manager, instruments)
+    }
+
+    #[test]
+    fn test_calibration_weighs_acceptances() {
+        let (manager, instruments) = instrumented_manager();
+
+        let bases = b"GATTACA".repeat(40);
+        let (root, zk_proof, commitment) = zk_proof_for(&bases);
+        let sequence_data = sample_sequence_data(&NucleotideSequence::from_ascii(&bases).unwrap())
+            .with_zk_commitment(root, commitment);
+        let sequence_hash = sequence_data.sequence_hash;
+        let accept = |seed: u8| {
+            let instrument = instruments[seed as usize - 1].clone();
+            ValidationProof::new_signed_with_instrument(
+                &signing_key(seed),
+                sequence_hash,
+                root,
+                zk_proof.clone(),
+                1_700_000_100,
+                instrument,
+            )
+        };
+
+        let strict = ValidationThresholds {
+            calibration: Some(CalibrationPolicy::default()),
+            ..ValidationThresholds::default()
+        };
+        let mut manager = manager.with_thresholds(strict).unwrap();
+        let mut strict_data = sequence_data.clone();
+        assert_eq!(
+            manager.process_validation(&mut strict_data, accept(1), NOW),
+            Err(ConsensusError::CalibrationFailed(
+                SecurityError::StaleCalibration
+            ))
+        );
+        let uninstrumented = ValidationProof::new_signed(
+            &signing_key(3),
+            sequence_data.sequence_hash,
+            root,
+            zk_proof.clone(),
+            1_700_000_100,
+        );
+        assert_eq!(
+            manager.process_validation(&mut strict_data, uninstrumented, NOW),
+            Err(ConsensusError::CalibrationFailed(
+                SecurityError::MissingInstrument
+            ))
+        );
+
+        // Stale instruments count at half weight: all three accepting hold
+        // two thirds of the total, short of 67%.
+        let lenient = ValidationThresholds {
+            calibration: Some(CalibrationPolicy {
+                stale_action: StaleCalibrationAction::DownWeight(50),
+                ..CalibrationPolicy::default()
+            }),
+            ..ValidationThresholds::default()
+        };
+        let mut manager = manager.with_thresholds(lenient).unwrap();
+        let mut lenient_data = sequence_data;
+        for seed in [1, 2] {
+            assert_eq!(
+                manager.process_validation(&mut lenient_data, accept(seed), NOW),
+                Ok(ConsensusStatus::Pending)
+            );
+        }
+        assert_eq!(
+            manager.process_validation(&mut lenient_data, accept(3), NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            manager.evaluate(&lenient_data.validation_proofs, NOW),
+            ConsensusOutcome::BelowSupermajority
+        );
+        let fresh = manager
+            .with_thresholds(ValidationThresholds::default())
+            .unwrap();
+        assert_eq!(
+            fresh.evaluate(&lenient_data.validation_proofs, NOW),
+            ConsensusOutcome::Reached
+        );
+    }
+
+    #[test]
+    fn test_engine_agrees_with_manager() {
+        use ConsensusStatus::{Confirmed, Pending};
+        let (_, instruments) = instrumented_manager();
+        let bases = b"GATTACA".repeat(40);
+        let (root, zk_proof, commitment) = zk_proof_for(&bases);
+        let sequence_data = sample_sequence_data(&NucleotideSequence::from_ascii(&bases).unwrap())
+            .with_zk_commitment(root, commitment);
+        let sequence_hash = sequence_data.sequence_hash;
+        let accept = |seed: u8| {
+            let instrument = instruments[seed as usize - 1].clone();
+            ValidationProof::new_signed_with_instrument(
+                &signing_key(seed),
+                sequence_hash,
+                root,
+                zk_proof.clone(),
+                1_700_000_100,
+                instrument,
+            )
+        };
+        let uninstrumented = ValidationProof::new_signed(
+            &signing_key(3),
+            sequence_hash,
+            root,
+            zk_proof.clone(),
+            1_700_000_100,
+        );
+        let lenient = ValidationThresholds {
+            calibration: Some(CalibrationPolicy {
+                stale_action: StaleCalibrationAction::DownWeight(50),
+                ..CalibrationPolicy::default()
+            }),
+            ..ValidationThresholds::default()
+        };
+        let poor = QualityMetrics {
+            coverage_depth: 4,
+            ..sequence_data.quality_metrics.clone()
+        };
+
+        // Both paths refuse the same votes and weigh the rest alike: stale
+        // instruments at half weight leave the lenient set short of 67%.
+        let votes = [uninstrumented, accept(1), accept(2), accept(3)];
+        let missing = || {
+            Err(ConsensusError::CalibrationFailed(
+                SecurityError::MissingInstrument,
+            ))
+        };
+        let quality = || Err(ConsensusError::QualityBelowThreshold);
+        for (thresholds, metrics, expected) in [
+            (
+                lenient.clone(),
+                sequence_data.quality_metrics.clone(),
+                [missing(), Ok(Pending), Ok(Pending), Ok(Pending)],
+            ),
+            (
+                ValidationThresholds::default(),
+                sequence_data.quality_metrics.clone(),
+                [
+                    Ok(Pending),
+                    Ok(Pending),
+                    Ok(Confirmed),
+                    Err(ConsensusError::SequenceSettled),
+                ],
+            ),
+            (lenient, poor, [quality(), quality(), quality(), quality()]),
+        ] {
+            let (manager, _) = instrumented_manager();
+            let mut proof_validator = ProofValidator::default();
+            proof_validator.register_root(root, commitment);
+            let mut engine = ConsensusEngine::new(manager.validators.clone(), manager.quorum as u8)
+                .with_thresholds(thresholds.clone())
+                .unwrap()
+                .with_proof_validator(proof_validator)
+                .with_round_duration(1000);
+            let mut manager = manager.with_thresholds(thresholds).unwrap();
+            let mut data = SequenceData {
+                quality_metrics: metrics.clone(),
+                ..sequence_data.clone()
+            };
+            engine
+                .propose(sequence_hash, metrics, sequence_data.timestamp)
+                .unwrap();
+            let outcomes: Vec<_> = votes
+                .iter()
+                .map(|vote| {
+                    let managed = manager.process_validation(&mut data, vote.clone(), NOW);
+                    assert_eq!(engine.register_validation(vote.clone(), NOW), managed);
+                    managed
+                })
+                .collect();
+            assert_eq!(outcomes, expected);
+        }
+    }
+
+    #[test]
+    fn test_rejection_dispute_and_expiry() {
+        let mut manager = sample_manager(&[1, 2, 3]);
+        let accept = |seed: u8, sequence_hash: [u8; 32], bases: &[u8], timestamp: i64| {
+            let (root, zk_proof, _) = zk_proof_for(&bases.repeat(40));
+            ValidationProof::new_signed(
+                &signing_key(seed),
+                sequence_hash,
+                root,
+                zk_proof,
+                timestamp,
+            )
+        };
+        let pinned = |bases: &[u8]| {
+            let (root, _, commitment) = zk_proof_for(&bases.repeat(40));
+            (root, commitment)
+        };
+        let reject = |seed: u8, sequence_hash: [u8; 32]| {
+            ValidationProof::new_signed_rejection(
+                &signing_key(seed),
+                sequence_hash,
+                [2; 32],
+                vec![],
+                1_700_000_100,
+                RejectReason::QualityBelowThreshold,
+            )
+        };
+
+        // Rejections are recorded even where an acceptance fails the quality check.
+        let mut poor = sample_sequence_data(&NucleotideSequence::from_ascii(b"ACGT").unwrap())
+            .with_zk_commitment(pinned(b"ACGT").0, pinned(b"ACGT").1);
+        poor.quality_metrics.coverage_depth = 5;
+        let hash = poor.sequence_hash;
+        assert_eq!(
+            manager.process_validation(&mut poor, accept(1, hash, b"ACGT", 1_700_000_100), NOW),
+            Err(ConsensusError::QualityBelowThreshold)
+        );
+        assert_eq!(
+            manager.process_validation(&mut poor, reject(1, hash), NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            manager.process_validation(&mut poor, reject(2, hash), NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            manager.process_validation(&mut poor, reject(3, hash), NOW),
+            Ok(ConsensusStatus::Rejected)
+        );
+        assert_eq!(
+            manager.process_validation(&mut poor, reject(3, hash), NOW),
+            Err(ConsensusError::SequenceSettled)
+        );
+
+        // A validator whose data yields another root accepts that root as
+        // dissent. Acceptances split across roots dispute the sequence.
+        let (root, commitment) = pinned(b"GATTACA");
+        let mut split = sample_sequence_data(&NucleotideSequence::from_ascii(b"GATTACA").unwrap())
+            .with_zk_commitment(root, commitment);
+        let hash = split.sequence_hash;
+        assert_eq!(
+            manager.process_validation(&mut split, accept(1, hash, b"GATTACA", 1_700_000_100), NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            manager.process_validation(&mut split, accept(2, hash, b"CATTAG", 1_700_000_100), NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            manager.process_validation(&mut split, accept(3, hash, b"GATTACA", 1_700_000_100), NOW),
+            Ok(ConsensusStatus::Disputed)
+        );
+        assert_eq!(split.consensus_status, ConsensusStatus::Disputed);
+        // The pinned root still needs the pinned commitment: a proof claiming
+        // more bases than the sequencer measured does not carry it.
+        let mut overclaimed = ZkProofBuilder::new();
+        let bases = NucleotideSequence::from_ascii(&b"GATTACA".repeat(40)).unwrap();
+        overclaimed
+            .add_length_proof(bases.len() + 1_000)
+            .add_merkle_proof(&MerkleTree::from_sequence(&bases));
+        let overclaimed = ValidationProof::new_signed(
+            &signing_key(2),
+            hash,
+            root,
+            overclaimed.build().unwrap(),
+            1_700_000_120,
+        );
+        assert_eq!(
+            manager.process_validation(&mut split, overclaimed, NOW),
+            Err(ConsensusError::InvalidProof)
+        );
+        // Validator 2's latest vote replaces its earlier one.
+        assert_eq!(
+            manager.process_validation(&mut split, accept(2, hash, b"GATTACA", 1_700_000_150), NOW),
+            Ok(ConsensusStatus::Confirmed)
+        );
+
+        // A dissent still standing when the others confirm the sequence is
+        // reported and becomes evidence against its validator.
+        let mut four = sample_manager(&[1, 2, 3, 4]);
+        let (root, commitment) = pinned(b"TTAGGC");
+        let mut outvoted =
+            sample_sequence_data(&NucleotideSequence::from_ascii(b"TTAGGC").unwrap())
+                .with_zk_commitment(root, commitment);
+        let hash = outvoted.sequence_hash;
+        for (seed, bases, status) in [
+            (1, &b"TTAGGC"[..], ConsensusStatus::Pending),
+            (2, b"CATTAG", ConsensusStatus::Pending),
+            (3, b"TTAGGC", ConsensusStatus::Disputed),
+        ] {
+            assert_eq!(
+                four.process_validation(
+                    &mut outvoted,
+                    accept(seed, hash, bases, 1_700_000_100),
+                    NOW
+                ),
+                Ok(status)
+            );
+        }
+        let report = four.conflict_report(&outvoted);
+        assert!(report.is_conflicting());
+        assert_eq!(
+            report.dissenters(&root),
+            vec![signed_proof(2, hash, 0).validator]
+        );
+        assert!(four.slashing_evidence(&outvoted).is_empty());
+        assert_eq!(
+            four.process_validation(
+                &mut outvoted,
+                accept(4, hash, b"TTAGGC", 1_700_000_100),
+                NOW
+            ),
+            Ok(ConsensusStatus::Confirmed)
+        );
+        let evidence = four.slashing_evidence(&outvoted);
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(
+            (evidence[0].validator, evidence[0].finalized_root),
+            (signed_proof(2, hash, 0).validator, root)
+        );
+        assert_eq!(evidence[0].finalizing_proofs.len(), 3);
+        assert_eq!(
+            evidence[0].verify(
+                &four.validators,
+                four.quorum,
+                four.thresholds.consensus_percentage,
+                outvoted.expiration
+            ),
+            Ok(())
+        );
+
+        let mut late = sample_sequence_data(&NucleotideSequence::from_ascii(b"TTGCA").unwrap());
+        let (hash, expiration) = (late.sequence_hash, late.expiration);
+        assert_eq!(
+            manager.process_validation(&mut late, reject(1, hash), expiration + 1),
+            Ok(ConsensusStatus::Expired)
+        );
+        assert!(late.validation_proofs.is_empty());
+        assert_eq!(
+            manager.process_validation(&mut late, reject(2, hash), expiration + 1),
+            Err(ConsensusError::SequenceSettled)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_collection() {
+        let validators = sample_manager(&[1, 2, 3, 4, 5]).validators;
+        // Counts a collection as running until its future completes or is
+        // dropped by the timeout.
+        struct Running<'a>(&'a AtomicUsize);
+        impl Drop for Running<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        let (in_flight, most_in_flight) = (AtomicUsize::new(0), AtomicUsize::new(0));
+        let results = fan_out(&validators, 2, Duration::from_millis(100), |validator| {
+            let (in_flight, most_in_flight) = (&in_flight, &most_in_flight);
+            async move {
+                let _running = Running(in_flight);
+                most_in_flight.fetch_max(
+                    in_flight.fetch_add(1, Ordering::SeqCst) + 1,
+                    Ordering::SeqCst,
+                );
+                let seed = (1..=5)
+                    .find(|&seed| signed_proof(seed, [5; 32], 0).validator == validator.pubkey)
+                    .unwrap();
+                // Validator 1 hangs; the others answer in reverse order of speed.
+                let delay = if seed == 1 {
+                    10_000
+                } else {
+                    50 - seed as u64 * 10
+                };
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                match seed {
+                    2 => Err(ConsensusError::ProofError("Validation failed".to_string())),
+                    _ => Ok(signed_proof(seed, [5; 32], 1_700_000_100)),
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(most_in_flight.load(Ordering::SeqCst), 2);
+        let validators_in_order: Vec<Pubkey> =
+            results.iter().map(|(validator, _)| *validator).collect();
+        assert_eq!(
+            validators_in_order,
+            validators
+                .iter()
+                .map(|info| info.pubkey)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(results[0].1, Err(ConsensusError::Timeout));
+        assert!(matches!(results[1].1, Err(ConsensusError::ProofError(_))));
+        assert!(results[2..].iter().all(|(_, proof)| proof.is_ok()));
+    }
+
+    /// Serves `requests` oracle requests on a local port, answering each
+    /// with a proof signed by the validator node it names, and returns the
+    /// stub's base URL.
+    fn oracle_stub(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(&stream);
+                let (mut line, mut content_length) = (String::new(), 0);
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    line.clear();
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let request: OracleNetworkRequest = serde_json::from_slice(&body).unwrap();
+                let seed = (1..=9)
+                    .find(|&seed| {
+                        signed_proof(seed, [0; 32], 0).validator == request.validator_node
+                    })
+                    .unwrap();
+                let response = OracleNetworkResponse {
+                    status: "Validated".to_string(),
+                    sequence_id: request.sequence_data.sequence_id(),
+                    validation_proof: Some(signed_proof(
+                        seed,
+                        request.sequence_data.sequence_hash,
+                        1_700_000_100,
+                    )),
+                    error_message: None,
+                };
+                let body = serde_json::to_vec(&response).unwrap();
+                write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", body.len()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_collection_asks_each_validator() {
+        let oracle_network =
+            OracleNetworkManager::new(ModularOracleNetworkClient::new(&oracle_stub(3)), 67);
+        let manager = sample_manager(&[1, 2, 3]).with_oracle_network(oracle_network);
+        let sequence_data =
+            sample_sequence_data(&NucleotideSequence::from_ascii(b"ACGTACGTAC").unwrap());
+        for validator in &manager.validators {
+            let proof = manager
+                .collect_validation_proof(&sequence_data, validator)
+                .await
+                .unwrap();
+            assert_eq!(proof.validator, validator.pubkey);
+        }
+    }
+
+    #[test]
+    fn test_partial_results_within_tolerance() {
+        // Four validators tolerate one failure by default.
+        let mut manager = sample_manager(&[1, 2, 3, 4]);
+        let (root, zk_proof, commitment) = zk_proof_for(b"ACGTACGTAC");
+        let sequence_data =
+            sample_sequence_data(&NucleotideSequence::from_ascii(b"ACGTACGTAC").unwrap())
+                .with_zk_commitment(root, commitment);
+        let hash = sequence_data.sequence_hash;
+        let proof = |seed: u8| {
+            ValidationProof::new_signed(
+                &signing_key(seed),
+                hash,
+                root,
+                zk_proof.clone(),
+                1_700_000_100,
+            )
+        };
+        let result = |seed: u8, ok: bool| {
+            let proof = proof(seed);
+            (
+                proof.validator,
+                if ok {
+                    Ok(proof)
+                } else {
+                    Err(ConsensusError::Timeout)
+                },
+            )
+        };
+
+        let report = manager
+            .decide(
+                &sequence_data,
+                vec![
+                    result(1, true),
+                    result(2, true),
+                    result(3, true),
+                    result(4, false),
+                ],
+                NOW,
+            )
+            .unwrap();
+        assert_eq!(report.status, ConsensusStatus::Confirmed);
+        assert_eq!(report.proofs.len(), 3);
+        assert_eq!(
+            report.failures,
+            vec![(proof(4).validator, ConsensusError::Timeout)]
+        );
+        // Proofs counted in a decided round are spent.
+        assert_eq!(
+            manager.process_validation(&mut sequence_data.clone(), proof(1), NOW),
+            Err(ConsensusError::ReplayedProof)
+        );
+
+        // Collected proofs get the same checks as submitted ones; failing
+        // them counts as a failure, and too many abandon the round with the
+        // proofs that passed still unspent.
+        let mut manager = sample_manager(&[1, 2, 3, 4]);
+        let unproven = signed_proof(2, hash, 1_700_000_100);
+        let backdated = ValidationProof::new_signed(
+            &signing_key(3),
+            hash,
+            root,
+            zk_proof.clone(),
+            1_699_999_999,
+        );
+        let mixed = vec![
+            result(1, true),
+            (unproven.validator, Ok(unproven)),
+            (backdated.validator, Ok(backdated)),
+            result(4, true),
+        ];
+        let Err(ConsensusError::TooManyFailures(abandoned)) =
+            manager.decide(&sequence_data, mixed, NOW)
+        else {
+            panic!("two failures abandon the round");
+        };
+        assert_eq!(abandoned.status, ConsensusStatus::Pending);
+        assert_eq!(abandoned.proofs, vec![proof(1), proof(4)]);
+        assert_eq!(
+            abandoned.failures,
+            vec![
+                (proof(2).validator, ConsensusError::InvalidProof),
+                (proof(3).validator, ConsensusError::StaleProof)
+            ]
+        );
+        assert_eq!(
+            manager.process_validation(&mut sequence_data.clone(), proof(1), NOW),
+            Ok(ConsensusStatus::Pending)
+        );
+
+        let two_down = || {
+            vec![
+                result(1, true),
+                result(2, true),
+                result(3, false),
+                result(4, false),
+            ]
+        };
+        let mut lenient = sample_manager(&[1, 2, 3, 4]).with_max_faulty(2);
+        assert_eq!(
+            lenient
+                .decide(&sequence_data, two_down(), NOW)
+                .unwrap()
+                .status,
+            ConsensusStatus::Pending
+        );
+        // A proof relayed twice in one round counts once.
+        let repeated = vec![result(1, true), result(1, true)];
+        assert_eq!(
+            sample_manager(&[1, 2, 3, 4])
+                .decide(&sequence_data, repeated, NOW)
+                .unwrap()
+                .failures,
+            vec![(proof(1).validator, ConsensusError::ReplayedProof)]
+        );
+    }
+
+    #[test]
+    fn test_weighted_consensus() {
+        let validator = |seed: u8, stake: u64, reputation_score: u32| ValidatorInfo {
+            stake,
+            reputation_score,
+            ..ValidatorInfo::new(Pubkey::new_from_array([seed; 32]))
+        };
+        let approving = |seeds: &[u8]| {
+            seeds
+                .iter()
+                .map(|&seed| (Pubkey::new_from_array([seed; 32]), FULL_WEIGHT))
+                .collect::<HashMap<_, _>>()
+        };
+        let validators = vec![
+            validator(1, 700, 0),
+            validator(2, 150, 0),
+            validator(3, 150, 0),
+        ];
+
+        // One heavily staked validator outweighs the other two together.
+        assert_eq!(
+            weigh_consensus(&validators, &approving(&[1]), 1, 67),
+            ConsensusOutcome::Reached
+        );
+        assert_eq!(
+            weigh_consensus(&validators, &approving(&[1]), 2, 67),
+            ConsensusOutcome::InsufficientQuorum
+        );
+        assert_eq!(
+            weigh_consensus(&validators, &approving(&[2, 3]), 2, 67),
+            ConsensusOutcome::BelowSupermajority
+        );
+        // Approvals from outside the set carry no weight and do not count towards the quorum.
+        assert_eq!(
+            weigh_consensus(&validators, &approving(&[2, 9]), 2, 67),
+            ConsensusOutcome::InsufficientQuorum
+        );
+        // A down-weighted approval counts for its share only; at zero weight
+        // it does not count at all.
+        let stale = |share: u8| HashMap::from([(Pubkey::new_from_array([1; 32]), share)]);
+        assert_eq!(
+            weigh_consensus(&validators, &stale(50), 1, 67),
+            ConsensusOutcome::BelowSupermajority
+        );
+        assert_eq!(
+            weigh_consensus(&validators, &stale(0), 1, 67),
+            ConsensusOutcome::InsufficientQuorum
+        );
+
+        // Equal stakes split evenly; reputation at most doubles a stake.
+        let pair = vec![validator(1, 100, 0), validator(2, 100, 0)];
+        assert_eq!(
+            weigh_consensus(&pair, &approving(&[1]), 1, 67),
+            ConsensusOutcome::Tie
+        );
+        assert_eq!(
+            weigh_consensus(&pair, &approving(&[1]), 1, 50),
+            ConsensusOutcome::Reached
+        );
+        let unproven = vec![validator(1, 110, 0), validator(2, 100, 0)];
+        assert_eq!(
+            weigh_consensus(&unproven, &approving(&[1]), 1, 67),
+            ConsensusOutcome::BelowSupermajority
+        );
+        let reputable = vec![validator(1, 110, 5 * MAX_REPUTATION), validator(2, 100, 0)];
+        assert_eq!(
+            weigh_consensus(&reputable, &approving(&[1]), 1, 67),
+            ConsensusOutcome::Reached
+        );
+
+        assert_eq!(
+            weigh_consensus(&[], &approving(&[]), 0, 67),
+            ConsensusOutcome::InsufficientQuorum
+        );
+
+        // Thresholds outside 1..=100 are refused up front.
+        let client = || BioModServerClient::new("http://localhost:8080");
+        for percentage in [0, 101] {
+            assert_eq!(
+                ConsensusManager::new(validators.clone(), percentage, client()).err(),
+                Some(ValidationError::InvalidConsensusPercentage(percentage))
+            );
+            let thresholds = ValidationThresholds {
+                consensus_percentage: percentage,
+                ..ValidationThresholds::default()
+            };
+            assert_eq!(
+                ConsensusManager::new(validators.clone(), 67, client())
+                    .unwrap()
+                    .with_thresholds(thresholds)
+                    .err(),
+                Some(ValidationError::InvalidConsensusPercentage(percentage))
+            );
+        }
+
+        // A supermajority split across roots is a dispute.
+        let even = vec![
+            validator(1, 100, 0),
+            validator(2, 100, 0),
+            validator(3, 100, 0),
+        ];
+        let vote = |seed: u8, root: u8| ValidationProof {
+            validator: Pubkey::new_from_array([seed; 32]),
+            merkle_root: [root; 32],
+            ..signed_proof(seed, [5; 32], 1_700_000_100)
+        };
+        let split = [vote(1, 0xaa), vote(2, 0xaa), vote(3, 0xbb)];
+        assert_eq!(
+            settle_consensus(&even, &split, 2, 67, |_, _| FULL_WEIGHT),
+            ConsensusStatus::Disputed
+        );
+        assert_eq!(
+            settle_consensus(&even, &split[..2], 2, 67, |_, _| FULL_WEIGHT),
+            ConsensusStatus::Pending
+        );
+        // A validator's latest vote is its newest one wherever it sits in the
+        // slice; equal timestamps resolve the same way in either order.
+        let changed = ValidationProof {
+            timestamp: 1_700_000_110,
+            ..vote(3, 0xaa)
+        };
+        assert_eq!(
+            settle_consensus(
+                &even,
+                &[changed, vote(1, 0xaa), vote(2, 0xaa), vote(3, 0xbb)],
+                2,
+                67,
+                |_, _| FULL_WEIGHT
+            ),
+            ConsensusStatus::Confirmed
+        );
+        let tied = |first: u8, second: u8| {
+            settle_consensus(
+                &even,
+                &[
+                    vote(1, 0xaa),
+                    vote(2, 0xaa),
+                    vote(3, first),
+                    vote(3, second),
+                ],
+                2,
+                67,
+                |_, _| FULL_WEIGHT,
+            )
+        };
+        assert_eq!(tied(0xaa, 0xbb), tied(0xbb, 0xaa));
+
+        let manager = sample_manager(&[1, 2, 3]).with_quorum(3);
+        let sequence_hash = [5; 32];
+        let proofs: Vec<ValidationProof> = [1, 2]
+            .iter()
+            .map(|&seed| signed_proof(seed, sequence_hash, 1_700_000_100))
+            .collect();
+        assert_eq!(
+            manager.evaluate(&proofs, NOW),
+            ConsensusOutcome::InsufficientQuorum
+        );
+        assert_eq!(
+            manager.check_consensus(&proofs, NOW),
+            ConsensusStatus::Pending
+        );
+
+        // A newer rejection withdraws validator 1's acceptance even when it
+        // comes first in the slice.
+        let withdrawn = ValidationProof::new_signed_rejection(
+            &signing_key(1),
+            sequence_hash,
+            [2; 32],
+            vec![],
+            1_700_000_110,
+            RejectReason::QualityBelowThreshold,
+        );
+        let mut proofs: Vec<ValidationProof> = [1, 2, 3]
+            .iter()
+            .map(|&seed| signed_proof(seed, sequence_hash, 1_700_000_100))
+            .collect();
+        proofs.insert(0, withdrawn);
+        assert_eq!(
+            sample_manager(&[1, 2, 3]).evaluate(&proofs, NOW),
+            ConsensusOutcome::BelowSupermajority
+        );
+    }
+}
@@ -0,0 +1,643 @@
+use crate::consensus::{
+    settle_consensus, ConflictEvidence, ConflictReport, ConsensusError, ConsensusStatus,
+};
+use crate::error::ValidationError;
+use crate::model::{
+    validate_consensus_percentage, QualityMetrics, ValidationThresholds, ValidatorInfo,
+};
+use crate::proof::{ProofValidator, SignatureError, ValidationProof};
+use solana_program::pubkey::Pubkey;
+use std::collections::HashMap;
+
+pub const DEFAULT_ROUND_DURATION: i64 = 60; // Seconds a round collects votes before it is re-run
+pub const DEFAULT_MAX_ROUNDS: u32 = 3;
+
+/// Where the engine is in the propose → collect → finalize cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundPhase {
+    /// No sequence in flight; `propose` opens the next round.
+    Propose,
+    /// Taking votes from the round's validators until its deadline.
+    Collect,
+    /// Settled with the given status. A new proposal may follow.
+    Finalized(ConsensusStatus),
+}
+
+#[derive(Debug, Default)]
+pub struct ValidatorState {
+    pub validations: Vec<ValidationProof>,
+}
+
+impl ValidatorState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// One attempt at settling a sequence. The validator set is snapshotted when
+/// the round opens, so registry changes only apply from the next round.
+#[derive(Debug)]
+pub struct ConsensusRound {
+    pub number: u64,  // Increases with every round the engine opens, re-runs included
+    pub attempt: u32, // Rounds spent on this sequence so far, starting at 1
+    pub sequence_hash: [u8; 32],
+    pub proposed_at: i64, // When the sequence was proposed; older votes are stale
+    pub quality_metrics: QualityMetrics, // The sequencer's, checked on every acceptance
+    pub validators: Vec<ValidatorInfo>,
+    pub deadline: i64,
+    pub phase: RoundPhase,
+    pub validator_states: HashMap<Pubkey, ValidatorState>,
+}
+
+impl ConsensusRound {
+    /// The round's votes grouped by the merkle root they claim.
+    pub fn conflict_report(&self) -> ConflictReport {
+        let votes: Vec<ValidationProof> = self
+            .validator_states
+            .values()
+            .flat_map(|state| state.validations.iter().cloned())
+            .collect();
+        ConflictReport::new(self.sequence_hash, &self.validators, &votes)
+    }
+
+    pub fn slashing_evidence(&self) -> Vec<ConflictEvidence> {
+        let status = match self.phase {
+            RoundPhase::Finalized(status) => status,
+            _ => ConsensusStatus::Pending,
+        };
+        self.conflict_report().slashing_evidence(status)
+    }
+
+    /// Settles on the latest votes, weighed as `ConsensusManager` weighs
+    /// them under `thresholds` as of `now`.
+    fn settle(
+        &mut self,
+        quorum: usize,
+        thresholds: &ValidationThresholds,
+        now: i64,
+    ) -> ConsensusStatus {
+        let votes: Vec<ValidationProof> = self
+            .validator_states
+            .values()
+            .flat_map(|state| state.validations.iter().cloned())
+            .collect();
+        let status = settle_consensus(
+            &self.validators,
+            &votes,
+            quorum,
+            thresholds.consensus_percentage,
+            |info, proof| thresholds.vote_weight(info, proof, now),
+        );
+        if status.is_settled() {
+            self.phase = RoundPhase::Finalized(status);
+        }
+        status
+    }
+}
+
+/// Runs consensus on one sequence at a time in rounds with deadlines. A
+/// round that times out is re-run keeping the votes already cast, so a
+/// slow validator only holds up a sequence the others cannot settle on
+/// their own; after `max_rounds` attempts the sequence expires. Validators
+/// that never vote still count towards the total weight, so a re-run needs
+/// the same supermajority of the whole set as the first round.
+pub struct ConsensusEngine {
+    validators: Vec<ValidatorInfo>,
+    consensus_threshold: u8, // Distinct validators needed for any outcome
+    thresholds: ValidationThresholds,
+    round_duration: i64,
+    max_rounds: u32,
+    proof_validator: ProofValidator, // Trusted roots for the zk proofs on acceptances
+    round: Option<ConsensusRound>,
+}
+
+impl ConsensusEngine {
+    pub fn new(validators: Vec<ValidatorInfo>, consensus_threshold: u8) -> Self {
+        Self {
+            validators,
+            consensus_threshold,
+            thresholds: ValidationThresholds::default(),
+            round_duration: DEFAULT_ROUND_DURATION,
+            max_rounds: DEFAULT_MAX_ROUNDS,
+            proof_validator: ProofValidator::default(),
+            round: None,
+        }
+    }
+
+    /// `percentage` of the validators' weight must agree, from 1 to 100.
+    pub fn with_supermajority(mut self, percentage: u8) -> Result<Self, ValidationError> {
+        validate_consensus_percentage(percentage)?;
+        self.thresholds.consensus_percentage = percentage;
+        Ok(self)
+    }
+
+    /// Quality, calibration and supermajority requirements, as for
+    /// `ConsensusManager::with_thresholds`.
+    pub fn with_thresholds(
+        mut self,
+        thresholds: ValidationThresholds,
+    ) -> Result<Self, ValidationError> {
+        thresholds.validate()?;
+        self.thresholds = thresholds;
+        Ok(self)
+    }
+
+    pub fn with_round_duration(mut self, seconds: i64) -> Self {
+        self.round_duration = seconds;
+        self
+    }
+
+    pub fn with_max_rounds(mut self, max_rounds: u32) -> Self {
+        self.max_rounds = max_rounds.max(1);
+        self
+    }
+
+    /// Acceptances must carry a zk proof for a root registered with
+    /// `proof_validator`.
+    pub fn with_proof_validator(mut self, proof_validator: ProofValidator) -> Self {
+        self.proof_validator = proof_validator;
+        self
+    }
+
+    /// Replaces the validator set from the next round on.
+    pub fn set_validators(&mut self, validators: Vec<ValidatorInfo>) {
+        self.validators = validators;
+    }
+
+    pub fn round(&self) -> Option<&ConsensusRound> {
+        self.round.as_ref()
+    }
+
+    pub fn phase(&self) -> RoundPhase {
+        self.round
+            .as_ref()
+            .map_or(RoundPhase::Propose, |round| round.phase)
+    }
+
+    /// Opens a round collecting votes on `sequence_hash`, sequenced with
+    /// `quality_metrics`, until `now + round_duration`, and returns its
+    /// number.
+    pub fn propose(
+        &mut self,
+        sequence_hash: [u8; 32],
+        quality_metrics: QualityMetrics,
+        now: i64,
+    ) -> Result<u64, ConsensusError> {
+        if self.phase() == RoundPhase::Collect {
+            return Err(ConsensusError::RoundInProgress);
+        }
+        let number = self.round.as_ref().map_or(0, |round| round.number) + 1;
+        self.round = Some(ConsensusRound {
+            number,
+            attempt: 1,
+            sequence_hash,
+            proposed_at: now,
+            quality_metrics,
+            validators: self.validators.clone(),
+            deadline: now.saturating_add(self.round_duration),
+            phase: RoundPhase::Collect,
+            validator_states: HashMap::new(),
+        });
+        Ok(number)
+    }
+
+    /// Handles the current round's deadline as of `now`. A timed-out round
+    /// is re-run with a fresh snapshot of the whole validator set, voters or
+    /// not, and the votes of validators still in it carried over, weighed
+    /// again as of `now`; they may settle the re-run straight away if the
+    /// set has shrunk. The last
+    /// allowed round expires the sequence instead.
+    pub fn advance(&mut self, now: i64) -> RoundPhase {
+        let quorum = self.consensus_threshold as usize;
+        let Some(round) = self.round.as_mut() else {
+            return RoundPhase::Propose;
+        };
+        if round.phase != RoundPhase::Collect || now <= round.deadline {
+            return round.phase;
+        }
+        if round.attempt >= self.max_rounds {
+            round.phase = RoundPhase::Finalized(ConsensusStatus::Expired);
+            return round.phase;
+        }
+
+        let validators = self.validators.clone();
+        let mut validator_states = std::mem::take(&mut round.validator_states);
+        validator_states
+            .retain(|validator, _| validators.iter().any(|info| info.pubkey == *validator));
+
+        let mut rerun = ConsensusRound {
+            number: round.number + 1,
+            attempt: round.attempt + 1,
+            sequence_hash: round.sequence_hash,
+            proposed_at: round.proposed_at,
+            quality_metrics: round.quality_metrics.clone(),
+            validators,
+            deadline: now.saturating_add(self.round_duration),
+            phase: RoundPhase::Collect,
+            validator_states,
+        };
+        rerun.settle(quorum, &self.thresholds, now);
+        *round = rerun;
+        round.phase
+    }
+
+    /// Records a vote in the current round, re-running it first if its
+    /// deadline has passed, and settles the round if the vote decides it.
+    /// Votes dated before the proposal or after the round's deadline are
+    /// stale; acceptances must also pass the zk, quality and calibration
+    /// checks, which rejections skip as in `ConsensusManager`.
+    /// Votes count with the weight `ConsensusManager` gives them.
+    pub fn register_validation(
+        &mut self,
+        proof: ValidationProof,
+        now: i64,
+    ) -> Result<ConsensusStatus, ConsensusError> {
+        self.advance(now);
+        let quorum = self.consensus_threshold as usize;
+        let round = self.round.as_mut().ok_or(ConsensusError::NoActiveRound)?;
+        match round.phase {
+            RoundPhase::Collect => {}
+            RoundPhase::Finalized(_) => return Err(ConsensusError::SequenceSettled),
+            RoundPhase::Propose => return Err(ConsensusError::NoActiveRound),
+        }
+
+        if proof.sequence_hash != round.sequence_hash {
+            return Err(ConsensusError::SequenceMismatch);
+        }
+        let validator = round
+            .validators
+            .iter()
+            .find(|info| info.pubkey == proof.validator)
+            .ok_or(ConsensusError::UnknownValidator)?;
+        if proof.timestamp < round.proposed_at || proof.timestamp > round.deadline {
+            return Err(ConsensusError::StaleProof);
+        }
+        let digest = proof.signing_digest();
+        if round
+            .validator_states
+            .values()
+            .flat_map(|state| &state.validations)
+            .any(|seen| seen.signing_digest() == digest)
+        {
+            return Err(ConsensusError::ReplayedProof);
+        }
+        proof.verify_signature().map_err(|e| match e {
+            SignatureError::Missing | SignatureError::Malformed => {
+                ConsensusError::MalformedSignature
+            }
+            _ => ConsensusError::InvalidSignature,
+        })?;
+        if proof.verdict.is_accept() {
+            if self
+                .proof_validator
+                .verify_zk(&proof.merkle_root, &proof.zk_proof)
+                .is_err()
+            {
+                return Err(ConsensusError::InvalidProof);
+            }
+            if !self.thresholds.accepts_quality(&round.quality_metrics) {
+                return Err(ConsensusError::QualityBelowThreshold);
+            }
+            if let Some(policy) = &self.thresholds.calibration {
+                policy
+                    .weight(validator, &proof, now)
+                    .map_err(ConsensusError::CalibrationFailed)?;
+            }
+        }
+
+        round
+            .validator_states
+            .entry(proof.validator)
+            .or_default()
+            .validations
+            .push(proof);
+        Ok(round.settle(quorum, &self.thresholds, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{NucleotideSequence, SequencerModel};
+    use crate::proof::{InstrumentRecord, MerkleTree, RejectReason, ZkProofBuilder};
+    use crate::security::{CalibrationPolicy, StaleCalibrationAction};
+    use ed25519_dalek::SigningKey;
+
+    const NOW: i64 = 1_700_000_000;
+    const HASH: [u8; 32] = [5; 32];
+    const READ: &[u8] = b"ACGTACGTAC";
+    const MISREAD: &[u8] = b"ACGTTCGTAC";
+
+    fn validators(seeds: &[u8]) -> Vec<ValidatorInfo> {
+        seeds
+            .iter()
+            .map(|&seed| {
+                ValidatorInfo::new(Pubkey::new_from_array(
+                    SigningKey::from_bytes(&[seed; 32])
+                        .verifying_key()
+                        .to_bytes(),
+                ))
+            })
+            .collect()
+    }
+
+    fn quality_metrics() -> QualityMetrics {
+        QualityMetrics {
+            coverage_depth: 35,
+            quality_score: 32.5,
+            error_rate: 0.0009,
+            confidence_intervals: vec![],
+            insert_size: None,
+        }
+    }
+
+    /// The merkle root of `bases` and a zk proof for it.
+    fn zk_proof_for(bases: &[u8]) -> ([u8; 32], Vec<u8>) {
+        let sequence = NucleotideSequence::from_ascii(bases).unwrap();
+        let tree = MerkleTree::from_sequence(&sequence);
+        let mut builder = ZkProofBuilder::new();
+        builder
+            .add_length_proof(sequence.len())
+            .add_merkle_proof(&tree);
+        (tree.root(), builder.build().unwrap())
+    }
+
+    /// An engine trusting the roots of both `READ` and `MISREAD`.
+    fn trusting_engine(seeds: &[u8], quorum: u8) -> ConsensusEngine {
+        let mut proof_validator = ProofValidator::default();
+        for bases in [READ, MISREAD] {
+            let (root, zk_proof) = zk_proof_for(bases);
+            let commitment = proof_validator.zk_commitment(&root, &zk_proof).unwrap();
+            proof_validator.register_root(root, commitment);
+        }
+        ConsensusEngine::new(validators(seeds), quorum).with_proof_validator(proof_validator)
+    }
+
+    fn vote_for(seed: u8, bases: &[u8], timestamp: i64) -> ValidationProof {
+        let (root, zk_proof) = zk_proof_for(bases);
+        ValidationProof::new_signed(
+            &SigningKey::from_bytes(&[seed; 32]),
+            HASH,
+            root,
+            zk_proof,
+            timestamp,
+        )
+    }
+
+    fn vote(seed: u8, timestamp: i64) -> ValidationProof {
+        vote_for(seed, READ, timestamp)
+    }
+
+    #[test]
+    fn test_round_state_machine() {
+        let mut engine = trusting_engine(&[1, 2, 3], 2);
+        assert_eq!(engine.phase(), RoundPhase::Propose);
+        assert_eq!(
+            engine.register_validation(vote(1, NOW), NOW),
+            Err(ConsensusError::NoActiveRound)
+        );
+
+        assert_eq!(engine.propose(HASH, quality_metrics(), NOW), Ok(1));
+        assert_eq!(
+            engine.propose([6; 32], quality_metrics(), NOW),
+            Err(ConsensusError::RoundInProgress)
+        );
+        // The snapshot is fixed: a validator added now only joins the next round.
+        engine.set_validators(validators(&[1, 2, 3, 4]));
+        assert_eq!(
+            engine.register_validation(vote(4, NOW), NOW + 1),
+            Err(ConsensusError::UnknownValidator)
+        );
+
+        // Votes from before the proposal or past the deadline are stale, and
+        // acceptances need a zk proof for a trusted root.
+        assert_eq!(
+            engine.register_validation(vote(1, NOW - 1), NOW + 1),
+            Err(ConsensusError::StaleProof)
+        );
+        assert_eq!(
+            engine.register_validation(vote(1, NOW + DEFAULT_ROUND_DURATION + 1), NOW + 1),
+            Err(ConsensusError::StaleProof)
+        );
+        let (root, _) = zk_proof_for(READ);
+        let unproven = ValidationProof::new_signed(
+            &SigningKey::from_bytes(&[1; 32]),
+            HASH,
+            root,
+            vec![3; 48],
+            NOW,
+        );
+        assert_eq!(
+            engine.register_validation(unproven, NOW + 1),
+            Err(ConsensusError::InvalidProof)
+        );
+
+        assert_eq!(
+            engine.register_validation(vote(1, NOW), NOW + 1),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            engine.register_validation(vote(1, NOW), NOW + 2),
+            Err(ConsensusError::ReplayedProof)
+        );
+        assert_eq!(
+            engine.register_validation(vote(2, NOW), NOW + 2),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            engine.register_validation(vote(3, NOW), NOW + 3),
+            Ok(ConsensusStatus::Confirmed)
+        );
+        assert_eq!(
+            engine.phase(),
+            RoundPhase::Finalized(ConsensusStatus::Confirmed)
+        );
+        assert_eq!(
+            engine.register_validation(vote(3, NOW + 1), NOW + 4),
+            Err(ConsensusError::SequenceSettled)
+        );
+
+        assert_eq!(engine.propose(HASH, quality_metrics(), NOW + 10), Ok(2));
+        let round = engine.round().unwrap();
+        assert_eq!(
+            (round.validators.len(), round.attempt, round.deadline),
+            (4, 1, NOW + 10 + DEFAULT_ROUND_DURATION)
+        );
+        // Rejections skip the zk check.
+        let reject = ValidationProof::new_signed_rejection(
+            &SigningKey::from_bytes(&[4; 32]),
+            HASH,
+            [2; 32],
+            vec![],
+            NOW + 10,
+            RejectReason::Contaminated,
+        );
+        assert_eq!(
+            engine.register_validation(reject, NOW + 11),
+            Ok(ConsensusStatus::Pending)
+        );
+
+        // Validator 4 switches to accepting another root and is outvoted.
+        engine
+            .register_validation(vote_for(4, MISREAD, NOW + 11), NOW + 12)
+            .unwrap();
+        assert_eq!(
+            engine.register_validation(vote(1, NOW + 11), NOW + 12),
+            Ok(ConsensusStatus::Pending)
+        );
+        assert_eq!(
+            engine.register_validation(vote(2, NOW + 11), NOW + 12),
+            Ok(ConsensusStatus::Disputed)
+        );
+        assert!(engine.round().unwrap().conflict_report().is_conflicting());
+        assert!(engine.round().unwrap().slashing_evidence().is_empty());
+        assert_eq!(
+            engine.register_validation(vote(3, NOW + 11), NOW + 12),
+            Ok(ConsensusStatus::Confirmed)
+        );
+        let evidence = engine.round().unwrap().slashing_evidence();
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(
+            (evidence[0].validator, evidence[0].finalized_root),
+            (validators(&[4])[0].pubkey, root)
+        );
+    }
+
+    #[test]
+    fn test_round_timeouts() {
+        let mut engine = trusting_engine(&[1, 2, 3], 2).with_round_duration(30);
+        engine.propose(HASH, quality_metrics(), NOW).unwrap();
+        engine.register_validation(vote(1, NOW), NOW + 5).unwrap();
+        assert_eq!(engine.advance(NOW + 30), RoundPhase::Collect);
+        assert_eq!(engine.round().unwrap().number, 1);
+
+        // The late vote from 2 lands in the re-run, which keeps the whole set:
+        // validators 1 and 2 hold two thirds of the weight, short of 67%.
+        assert_eq!(
+            engine.register_validation(vote(2, NOW + 31), NOW + 31),
+            Ok(ConsensusStatus::Pending)
+        );
+        let round = engine.round().unwrap();
+        assert_eq!(
+            (
+                round.number,
+                round.attempt,
+                round.deadline,
+                round.validators.len()
+            ),
+            (2, 2, NOW + 61, 3)
+        );
+        // Validator 3 never answers but still counts, so the last round
+        // expires the sequence instead of settling on the votes carried over.
+        assert_eq!(engine.advance(NOW + 62), RoundPhase::Collect);
+        let round = engine.round().unwrap();
+        assert_eq!(
+            (
+                round.number,
+                round.validators.len(),
+                round.validator_states.len()
+            ),
+            (3, 3, 2)
+        );
+        assert_eq!(
+            engine.advance(NOW + 93),
+            RoundPhase::Finalized(ConsensusStatus::Expired)
+        );
+
+        // Once validator 3 leaves the registry the carried-over votes settle
+        // the re-run.
+        let mut shrinking = trusting_engine(&[1, 2, 3], 2).with_round_duration(30);
+        shrinking.propose(HASH, quality_metrics(), NOW).unwrap();
+        shrinking
+            .register_validation(vote(1, NOW), NOW + 5)
+            .unwrap();
+        shrinking
+            .register_validation(vote(2, NOW), NOW + 6)
+            .unwrap();
+        shrinking.set_validators(validators(&[1, 2]));
+        assert_eq!(
+            shrinking.advance(NOW + 31),
+            RoundPhase::Finalized(ConsensusStatus::Confirmed)
+        );
+
+        // The re-run weighs the votes it carries over as the first round
+        // did: with both instruments past their calibration age they count
+        // at half weight, half of what is left, and the re-run stays open.
+        let mut instrumented = validators(&[1, 2, 3]);
+        for info in &mut instrumented {
+            info.hardware_specs.sequencer_models.push(SequencerModel {
+                manufacturer: "Illumina".to_string(),
+                model: "NovaSeq 6000".to_string(),
+                serial_number: "A1".to_string(),
+                throughput: 6000,
+                error_rate: 0.001,
+                last_calibration: NOW - 365 * 24 * 60 * 60,
+            });
+        }
+        let lenient = ValidationThresholds {
+            calibration: Some(CalibrationPolicy {
+                stale_action: StaleCalibrationAction::DownWeight(50),
+                ..CalibrationPolicy::default()
+            }),
+            ..ValidationThresholds::default()
+        };
+        let mut weighted = trusting_engine(&[1, 2, 3], 2)
+            .with_thresholds(lenient)
+            .unwrap()
+            .with_round_duration(30);
+        weighted.set_validators(instrumented.clone());
+        weighted.propose(HASH, quality_metrics(), NOW).unwrap();
+        for seed in [1, 2] {
+            let (root, zk_proof) = zk_proof_for(READ);
+            let instrument = InstrumentRecord::from(
+                &instrumented[seed as usize - 1]
+                    .hardware_specs
+                    .sequencer_models[0],
+            );
+            let proof = ValidationProof::new_signed_with_instrument(
+                &SigningKey::from_bytes(&[seed; 32]),
+                HASH,
+                root,
+                zk_proof,
+                NOW,
+                instrument,
+            );
+            assert_eq!(
+                weighted.register_validation(proof, NOW + 5),
+                Ok(ConsensusStatus::Pending)
+            );
+        }
+        weighted.set_validators(instrumented[..2].to_vec());
+        assert_eq!(weighted.advance(NOW + 31), RoundPhase::Collect);
+        assert_eq!(weighted.round().unwrap().attempt, 2);
+
+        // With nobody voting the sequence expires after the last allowed
+        // round.
+        let mut silent = trusting_engine(&[1, 2, 3], 2)
+            .with_round_duration(30)
+            .with_max_rounds(2);
+        silent.propose(HASH, quality_metrics(), NOW).unwrap();
+        assert_eq!(silent.advance(NOW + 31), RoundPhase::Collect);
+        assert_eq!(silent.round().unwrap().validators.len(), 3);
+        assert_eq!(
+            silent.advance(NOW + 62),
+            RoundPhase::Finalized(ConsensusStatus::Expired)
+        );
+        assert_eq!(
+            silent.register_validation(vote(1, NOW), NOW + 63),
+            Err(ConsensusError::SequenceSettled)
+        );
+
+        assert!(ConsensusEngine::new(validators(&[1]), 1)
+            .with_supermajority(100)
+            .is_ok());
+        for percentage in [0, 101] {
+            assert_eq!(
+                ConsensusEngine::new(validators(&[1]), 1)
+                    .with_supermajority(percentage)
+                    .err(),
+                Some(ValidationError::InvalidConsensusPercentage(percentage))
+            );
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use crate::consensus::ConsensusError;
+use crate::processing::ProcessingError;
+use crate::proof::ProofError;
+use crate::security::SecurityError;
+use solana_program::program_error::ProgramError;
+
+#[derive(Debug, PartialEq)]
+pub enum ValidationError {
+    MaxValidatorsReached,
+    MaxSequencesReached,
+    ServerError(String),
+    Timeout(String),
+    InvalidConsensusPercentage(u8), // Outside 1..=100
+}
+
+// One code per variant, as for `ConsensusError`. The program's two keep the
+// codes they have always had.
+impl From<ValidationError> for ProgramError {
+    fn from(e: ValidationError) -> Self {
+        ProgramError::Custom(match e {
+            ValidationError::MaxValidatorsReached => 1,
+            ValidationError::ServerError(_) => 2,
+            ValidationError::MaxSequencesReached => 3,
+            ValidationError::Timeout(_) => 4,
+            ValidationError::InvalidConsensusPercentage(_) => 5,
+        })
+    }
+}
+
+// Each variant gets its own code so clients can tell why the program
+// rejected an instruction.
+impl From<ConsensusError> for ProgramError {
+    fn from(e: ConsensusError) -> Self {
+        ProgramError::Custom(match e {
+            ConsensusError::ProofError(_) => 100,
+            ConsensusError::ServerError(_) => 101,
+            ConsensusError::OracleError(_) => 102,
+            ConsensusError::UnknownValidator => 103,
+            ConsensusError::InvalidSignature => 104,
+            ConsensusError::MalformedSignature => 105,
+            ConsensusError::ReplayedProof => 106,
+            ConsensusError::SequenceMismatch => 107,
+            ConsensusError::StaleProof => 108,
+            ConsensusError::InvalidProof => 109,
+            ConsensusError::QualityBelowThreshold => 110,
+            ConsensusError::SequenceSettled => 111,
+            ConsensusError::RoundInProgress => 112,
+            ConsensusError::NoActiveRound => 113,
+            ConsensusError::Timeout => 114,
+            ConsensusError::TooManyFailures(_) => 115,
+            ConsensusError::CalibrationFailed(_) => 116,
+            ConsensusError::UnfinalizedRoot => 117,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub enum BioChainError {
+    ValidationError(ValidationError),
+    ConsensusError(ConsensusError),
+    SecurityError(SecurityError),
+    ProcessingError(ProcessingError),
+    ProofError(ProofError),
+    ServerError(String),
+    TimeoutError(String),
+}
+
+impl BioChainError {
+    pub fn log_error(&self) {
+        match self {
+            BioChainError::ValidationError(e) => {
+                println!("Validation error: {:?}", e);
+            }
+            BioChainError::ConsensusError(e) => {
+                println!("Consensus error: {:?}", e);
+            }
+            BioChainError::SecurityError(e) => {
+                println!("Security error: {:?}", e);
+            }
+            BioChainError::ProcessingError(e) => {
+                println!("Processing error: {:?}", e);
+            }
+            BioChainError::ProofError(e) => {
+                println!("Proof validation error: {:?}", e);
+            }
+            BioChainError::ServerError(msg) => {
+                println!("Server communication error: {}", msg);
+            }
+            BioChainError::TimeoutError(msg) => {
+                println!("Timeout error: {}", msg);
+            }
+        }
+    }
+}
+
+impl From<ValidationError> for BioChainError {
+    fn from(e: ValidationError) -> Self {
+        BioChainError::ValidationError(e)
+    }
+}
+
+impl From<ConsensusError> for BioChainError {
+    fn from(e: ConsensusError) -> Self {
+        BioChainError::ConsensusError(e)
+    }
+}
+
+impl From<SecurityError> for BioChainError {
+    fn from(e: SecurityError) -> Self {
+        BioChainError::SecurityError(e)
+    }
+}
+
+impl From<ProcessingError> for BioChainError {
+    fn from(e: ProcessingError) -> Self {
+        BioChainError::ProcessingError(e)
+    }
+}
+
+impl From<ProofError> for BioChainError {
+    fn from(e: ProofError) -> Self {
+        BioChainError::ProofError(e)
+    }
+}
+
+impl From<BioChainError> for ProgramError {
+    fn from(_: BioChainError) -> Self {
+        ProgramError::Custom(2) // Custom error code for BioChainError
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validation_error_codes_are_distinct() {
+        let codes: Vec<ProgramError> = [
+            ValidationError::MaxValidatorsReached,
+            ValidationError::MaxSequencesReached,
+            ValidationError::ServerError(String::new()),
+            ValidationError::Timeout(String::new()),
+            ValidationError::InvalidConsensusPercentage(0),
+        ]
+        .into_iter()
+        .map(ProgramError::from)
+        .collect();
+        assert_eq!(codes, [1, 3, 2, 4, 5].map(ProgramError::Custom));
+    }
+}
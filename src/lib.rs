@@ -0,0 +1,11 @@
+//! Verification and tracking of genetic sequences on Solana.
+
+pub mod client;
+pub mod consensus;
+pub mod error;
+pub mod model;
+pub mod oracle;
+pub mod processing;
+pub mod program;
+pub mod proof;
+pub mod security;
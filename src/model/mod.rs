@@ -0,0 +1,296 @@
+pub mod nucleotide;
+
+use crate::consensus::ConsensusStatus;
+use crate::error::ValidationError;
+use crate::proof::ValidationProof;
+use crate::security::{CalibrationPolicy, FULL_WEIGHT};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub use nucleotide::{
+    iupac_code, iupac_complement, AmbiguityRun, EncodingError, Nucleotide, NucleotideSequence,
+};
+
+pub const MAX_REPUTATION: u32 = 1000; // Reputation beyond this adds no consensus weight
+pub const MAX_CONFIDENCE_INTERVALS: usize = 3; // One each for quality, error rate and depth
+
+/// Wall-clock seconds since the Unix epoch, for off-chain code where the
+/// `Clock` sysvar is unavailable.
+pub fn unix_timestamp() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct DNASequence {
+    pub sequence_hash: [u8; 32],           // Hash of the DNA sequence
+    pub metadata: SequenceMetadata,        // Descriptive information
+    pub validations: Vec<ValidationProof>, // Validation records
+}
+
+impl DNASequence {
+    pub fn new(sequence: &NucleotideSequence, metadata: SequenceMetadata) -> Self {
+        Self {
+            sequence_hash: sequence.sequence_hash(),
+            metadata,
+            validations: Vec::new(),
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct SequenceMetadata {
+    pub length: u64,       // Length of the sequence
+    pub organism: String,  // Organism source
+    pub sample_id: String, // Unique sample identifier
+    pub timestamp: i64,    // Creation timestamp
+}
+
+impl SequenceMetadata {
+    pub fn new(
+        sequence: &NucleotideSequence,
+        organism: String,
+        sample_id: String,
+        timestamp: i64,
+    ) -> Self {
+        Self {
+            length: sequence.len(),
+            organism,
+            sample_id,
+            timestamp,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
+pub struct SequenceData {
+    pub sequence_hash: [u8; 32],
+    pub length: u64,
+    pub validator_nodes: Vec<Pubkey>,
+    pub timestamp: i64,
+    pub quality_metrics: QualityMetrics,
+    pub validation_proofs: Vec<ValidationProof>,
+    pub consensus_status: ConsensusStatus,
+    pub expiration: i64,
+    pub merkle_root: [u8; 32], // Root of the sequencer's own proof, which `zk_commitment` belongs to
+    pub zk_commitment: [u8; 32], // Pinned by the sequencer, see `ProofValidator::zk_commitment`
+    pub payer: Pubkey,         // Funded the on-chain sequence account and gets its rent back
+}
+
+impl SequenceData {
+    pub fn new(
+        sequence: &NucleotideSequence,
+        validator_nodes: Vec<Pubkey>,
+        quality_metrics: QualityMetrics,
+        timestamp: i64,
+        ttl: i64,
+    ) -> Self {
+        Self {
+            sequence_hash: sequence.sequence_hash(),
+            length: sequence.len(),
+            validator_nodes,
+            timestamp,
+            quality_metrics,
+            validation_proofs: Vec::new(),
+            consensus_status: ConsensusStatus::Pending,
+            expiration: timestamp.saturating_add(ttl),
+            merkle_root: [0; 32],
+            zk_commitment: [0; 32],
+            payer: Pubkey::default(),
+        }
+    }
+
+    /// Pins the sequencer's merkle root and the commitment validators' zk
+    /// proofs of that root must carry. Until it is set no acceptance can
+    /// pass the zk check. Whether those proofs are zero-knowledge depends
+    /// on the backend; the default `HashChainBackend`'s are not.
+    pub fn with_zk_commitment(mut self, merkle_root: [u8; 32], zk_commitment: [u8; 32]) -> Self {
+        self.merkle_root = merkle_root;
+        self.zk_commitment = zk_commitment;
+        self
+    }
+
+    /// Lower-case hex of `sequence_hash`, the identifier used in server and
+    /// oracle URLs.
+    pub fn sequence_id(&self) -> String {
+        self.sequence_hash
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct QualityMetrics {
+    pub coverage_depth: u32,
+    pub quality_score: f32,
+    pub error_rate: f32,
+    pub confidence_intervals: Vec<(f32, f32)>, // 95% bootstrap intervals: quality, error rate, depth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insert_size: Option<InsertSizeStats>, // Paired-end runs only
+}
+
+/// Fragment lengths of properly paired reads, measured on the assembly from
+/// the start of the forward mate to the end of the reverse one.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct InsertSizeStats {
+    pub proper_pairs: u32,
+    pub median: u32,
+    pub mean: f32,
+    pub std_dev: f32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct AuditTrail {
+    pub sequence_id: String, // Unique identifier for tracking
+    pub action: String,      // Action performed (e.g., "validated", "stored")
+    pub validator: Pubkey,   // Validator that performed the action
+    pub timestamp: i64,      // Time of the action
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct ValidationReport {
+    pub sequence_id: String,
+    pub total_validations: u32,
+    pub successful_validations: u32,
+    pub failed_validations: u32,
+    pub consensus_status: ConsensusStatus,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct SequenceValidatorState {
+    pub owner: Pubkey,
+    pub validated_sequences: HashMap<[u8; 32], SequenceData>,
+    pub validators: Vec<ValidatorInfo>,
+    pub min_validations: u8,
+    pub last_cleanup: i64,
+    pub sequence_ttl: i64,
+    pub validation_thresholds: ValidationThresholds,
+    pub resource_limits: ResourceLimits,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ValidationThresholds {
+    pub minimum_quality_score: f32,
+    pub minimum_coverage: u32,
+    pub maximum_error_rate: f32,
+    pub consensus_percentage: u8,
+    pub calibration: Option<CalibrationPolicy>, // Instrument checks on acceptances, if any
+}
+
+impl ValidationThresholds {
+    /// Rejects a `consensus_percentage` outside 1..=100: at 0 any vote
+    /// settles a sequence and above 100 none ever can.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_consensus_percentage(self.consensus_percentage)
+    }
+
+    /// True if a sequence with `metrics` may be accepted.
+    pub fn accepts_quality(&self, metrics: &QualityMetrics) -> bool {
+        metrics.coverage_depth >= self.minimum_coverage
+            && metrics.quality_score >= self.minimum_quality_score
+            && metrics.error_rate <= self.maximum_error_rate
+    }
+
+    /// A vote's weight under the calibration policy as of `now`, full
+    /// without one.
+    pub fn vote_weight(&self, validator: &ValidatorInfo, proof: &ValidationProof, now: i64) -> u8 {
+        self.calibration.as_ref().map_or(FULL_WEIGHT, |policy| {
+            policy.vote_weight(validator, proof, now)
+        })
+    }
+}
+
+pub fn validate_consensus_percentage(percentage: u8) -> Result<(), ValidationError> {
+    match percentage {
+        1..=100 => Ok(()),
+        _ => Err(ValidationError::InvalidConsensusPercentage(percentage)),
+    }
+}
+
+impl Default for ValidationThresholds {
+    fn default() -> Self {
+        Self {
+            minimum_quality_score: 30.0,
+            minimum_coverage: 30,
+            maximum_error_rate: 0.001,
+            consensus_percentage: 67,
+            calibration: None,
+        }
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct ResourceLimits {
+    pub max_sequences_per_validator: u32,
+    pub max_proof_size: u32,
+    pub max_validators: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
+pub struct ValidatorInfo {
+    pub pubkey: Pubkey,
+    pub stake: u64,
+    pub reputation_score: u32,
+    pub total_validations: u64,
+    pub successful_validations: u64,
+    pub hardware_specs: ValidatorHardware,
+    pub last_heartbeat: i64,
+}
+
+impl ValidatorInfo {
+    /// A validator with no stake, reputation or declared hardware yet.
+    pub fn new(pubkey: Pubkey) -> Self {
+        Self {
+            pubkey,
+            stake: 0,
+            reputation_score: 0,
+            total_validations: 0,
+            successful_validations: 0,
+            hardware_specs: ValidatorHardware::default(),
+            last_heartbeat: 0,
+        }
+    }
+
+    /// Voting weight in consensus: stake, with an unstaked validator counting
+    /// as one lamport, scaled from 1x at no reputation to 2x at
+    /// `MAX_REPUTATION`. A set of unstaked validators is thus one vote each.
+    pub fn consensus_weight(&self) -> u128 {
+        self.stake.max(1) as u128
+            * (MAX_REPUTATION + self.reputation_score.min(MAX_REPUTATION)) as u128
+    }
+
+    /// True if `sequence` carries a correctly signed validation from this
+    /// validator for its own hash.
+    pub fn validate(&self, sequence: &DNASequence) -> bool {
+        sequence.validations.iter().any(|proof| {
+            proof.validator == self.pubkey
+                && proof.sequence_hash == sequence.sequence_hash
+                && proof.verify_signature().is_ok()
+        })
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ValidatorHardware {
+    pub cpu_cores: u32,         // Minimum 64 cores
+    pub ram_gb: u32,            // Minimum 256GB
+    pub storage_tb: u32,        // Minimum 4TB
+    pub network_bandwidth: u32, // Minimum 1Gbps
+    pub sequencer_models: Vec<SequencerModel>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone)]
+pub struct SequencerModel {
+    pub manufacturer: String,
+    pub model: String,
+    pub serial_number: String,
+    pub throughput: u32,
+    pub error_rate: f32,
+    pub last_calibration: i64,
+}
@@ -0,0 +1,458 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io;
+use std::ops::Range;
+
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+)]
+#[borsh(use_discriminant = true)]
+pub enum Nucleotide {
+    A = 0,
+    C = 1,
+    G = 2,
+    T = 3,
+}
+
+impl Nucleotide {
+    pub fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => Nucleotide::A,
+            1 => Nucleotide::C,
+            2 => Nucleotide::G,
+            _ => Nucleotide::T,
+        }
+    }
+
+    pub fn from_ascii(byte: u8) -> Option<Self> {
+        match byte.to_ascii_uppercase() {
+            b'A' => Some(Nucleotide::A),
+            b'C' => Some(Nucleotide::C),
+            b'G' => Some(Nucleotide::G),
+            b'T' => Some(Nucleotide::T),
+            _ => None,
+        }
+    }
+
+    pub fn to_ascii(self) -> u8 {
+        b"ACGT"[self as usize]
+    }
+
+    pub fn complement(self) -> Self {
+        Self::from_bits(3 - self as u8)
+    }
+}
+
+/// Returns the canonical upper-case IUPAC code for `byte`, or `None` if it is
+/// not a nucleotide symbol.
+pub fn iupac_code(byte: u8) -> Option<u8> {
+    let upper = byte.to_ascii_uppercase();
+    match upper {
+        b'A' | b'C' | b'G' | b'T' | b'R' | b'Y' | b'S' | b'W' | b'K' | b'M' | b'B' | b'D'
+        | b'H' | b'V' | b'N' => Some(upper),
+        _ => None,
+    }
+}
+
+pub fn iupac_complement(code: u8) -> u8 {
+    match code {
+        b'A' => b'T',
+        b'T' => b'A',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        other => other, // S, W and N are their own complement
+    }
+}
+
+/// A run of consecutive positions holding the same IUPAC ambiguity code.
+/// The packed stream stores `A` at these positions; the run is authoritative.
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+)]
+pub struct AmbiguityRun {
+    pub start: u64,
+    pub len: u64,
+    pub code: u8,
+}
+
+impl AmbiguityRun {
+    pub fn end(&self) -> u64 {
+        self.start + self.len
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodingError {
+    InvalidBase { position: u64, byte: u8 },
+    OutOfRange { start: u64, end: u64, len: u64 },
+    Malformed(&'static str), // Decoded fields break a container invariant
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingError::InvalidBase { position, byte } => {
+                write!(f, "invalid base {byte:#04x} at {position}")
+            }
+            EncodingError::OutOfRange { start, end, len } => {
+                write!(f, "range {start}..{end} out of bounds for {len} bases")
+            }
+            EncodingError::Malformed(reason) => write!(f, "malformed sequence: {reason}"),
+        }
+    }
+}
+
+/// Quaternary (2 bits per base) nucleotide container. Bases are packed
+/// most-significant pair first, four to a byte; IUPAC ambiguity codes are kept
+/// in a sorted side mask of runs so long `N` gaps cost a single entry.
+#[derive(BorshSerialize, Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(try_from = "RawNucleotideSequence")]
+pub struct NucleotideSequence {
+    packed: Vec<u8>,
+    len: u64,
+    ambiguity_mask: Vec<AmbiguityRun>,
+}
+
+/// `NucleotideSequence` as it comes off the wire, before its invariants
+/// are checked.
+#[derive(BorshDeserialize, Deserialize)]
+struct RawNucleotideSequence {
+    packed: Vec<u8>,
+    len: u64,
+    ambiguity_mask: Vec<AmbiguityRun>,
+}
+
+impl TryFrom<RawNucleotideSequence> for NucleotideSequence {
+    type Error = EncodingError;
+
+    fn try_from(raw: RawNucleotideSequence) -> Result<Self, Self::Error> {
+        if raw.packed.len() as u64 != raw.len.div_ceil(4) {
+            return Err(EncodingError::Malformed(
+                "packed length does not match base count",
+            ));
+        }
+        let used = raw.len % 4;
+        if used != 0
+            && raw
+                .packed
+                .last()
+                .is_some_and(|byte| byte & (0xff >> (2 * used)) != 0)
+        {
+            return Err(EncodingError::Malformed("trailing bits are set"));
+        }
+        let mut covered = 0;
+        for run in &raw.ambiguity_mask {
+            if run.len == 0
+                || run.start < covered
+                || Nucleotide::from_ascii(run.code).is_some()
+                || iupac_code(run.code) != Some(run.code)
+            {
+                return Err(EncodingError::Malformed("invalid ambiguity run"));
+            }
+            covered = match run.start.checked_add(run.len) {
+                Some(end) if end <= raw.len => end,
+                _ => return Err(EncodingError::Malformed("ambiguity run past the end")),
+            };
+        }
+        Ok(Self {
+            packed: raw.packed,
+            len: raw.len,
+            ambiguity_mask: raw.ambiguity_mask,
+        })
+    }
+}
+
+impl BorshDeserialize for NucleotideSequence {
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let raw = RawNucleotideSequence::deserialize_reader(reader)?;
+        Self::try_from(raw)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+    }
+}
+
+impl NucleotideSequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(bases: usize) -> Self {
+        Self {
+            packed: Vec::with_capacity(bases.div_ceil(4)),
+            len: 0,
+            ambiguity_mask: Vec::new(),
+        }
+    }
+
+    pub fn from_ascii(data: &[u8]) -> Result<Self, EncodingError> {
+        let mut sequence = Self::with_capacity(data.len());
+        for &byte in data {
+            sequence.push(byte)?;
+        }
+        Ok(sequence)
+    }
+
+    pub fn from_bases(bases: &[Nucleotide]) -> Self {
+        let mut sequence = Self::with_capacity(bases.len());
+        for &base in bases {
+            sequence.push_base(base);
+        }
+        sequence
+    }
+
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Packed 2-bit stream. Trailing bits of the last byte are always zero.
+    pub fn packed(&self) -> &[u8] {
+        &self.packed
+    }
+
+    pub fn ambiguity_mask(&self) -> &[AmbiguityRun] {
+        &self.ambiguity_mask
+    }
+
+    pub fn push_base(&mut self, base: Nucleotide) {
+        let shift = 6 - 2 * (self.len % 4) as u8;
+        if self.len.is_multiple_of(4) {
+            self.packed.push(0);
+        }
+        let last = self.packed.len() - 1;
+        self.packed[last] |= (base as u8) << shift;
+        self.len += 1;
+    }
+
+    pub fn push(&mut self, byte: u8) -> Result<(), EncodingError> {
+        if let Some(base) = Nucleotide::from_ascii(byte) {
+            self.push_base(base);
+            return Ok(());
+        }
+        let code = iupac_code(byte).ok_or(EncodingError::InvalidBase {
+            position: self.len,
+            byte,
+        })?;
+        self.push_ambiguous(code, 1);
+        Ok(())
+    }
+
+    fn push_ambiguous(&mut self, code: u8, count: u64) {
+        let start = self.len;
+        match self.ambiguity_mask.last_mut() {
+            Some(run) if run.code == code && run.end() == start => run.len += count,
+            _ => self.ambiguity_mask.push(AmbiguityRun {
+                start,
+                len: count,
+                code,
+            }),
+        }
+        for _ in 0..count {
+            self.push_base(Nucleotide::A);
+        }
+    }
+
+    fn raw_base(&self, index: u64) -> Nucleotide {
+        let byte = self.packed[(index / 4) as usize];
+        Nucleotide::from_bits(byte >> (6 - 2 * (index % 4) as u8))
+    }
+
+    fn ambiguity_at(&self, index: u64) -> Option<u8> {
+        let slot = self
+            .ambiguity_mask
+            .partition_point(|run| run.end() <= index);
+        self.ambiguity_mask
+            .get(slot)
+            .filter(|run| run.start <= index)
+            .map(|run| run.code)
+    }
+
+    /// The unambiguous base at `index`, or `None` if out of range or masked.
+    pub fn base(&self, index: u64) -> Option<Nucleotide> {
+        if index >= self.len || self.ambiguity_at(index).is_some() {
+            return None;
+        }
+        Some(self.raw_base(index))
+    }
+
+    /// The IUPAC symbol at `index`.
+    pub fn get(&self, index: u64) -> Option<u8> {
+        if index >= self.len {
+            return None;
+        }
+        Some(
+            self.ambiguity_at(index)
+                .unwrap_or_else(|| self.raw_base(index).to_ascii()),
+        )
+    }
+
+    pub fn is_ambiguous(&self, index: u64) -> bool {
+        self.ambiguity_at(index).is_some()
+    }
+
+    pub fn ambiguous_bases(&self) -> u64 {
+        self.ambiguity_mask.iter().map(|run| run.len).sum()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u8> + '_ {
+        let mut runs = self.ambiguity_mask.iter().peekable();
+        (0..self.len).map(move |index| {
+            while runs.peek().is_some_and(|run| run.end() <= index) {
+                runs.next();
+            }
+            match runs.peek() {
+                Some(run) if run.start <= index => run.code,
+                _ => self.raw_base(index).to_ascii(),
+            }
+        })
+    }
+
+    pub fn to_ascii(&self) -> Vec<u8> {
+        self.iter().collect()
+    }
+
+    pub fn reverse_complement(&self) -> Self {
+        let mut result = Self::with_capacity(self.len as usize);
+        for index in (0..self.len).rev() {
+            match self.ambiguity_at(index) {
+                Some(code) => result.push_ambiguous(iupac_complement(code), 1),
+                None => result.push_base(self.raw_base(index).complement()),
+            }
+        }
+        result
+    }
+
+    pub fn slice(&self, range: Range<u64>) -> Result<Self, EncodingError> {
+        if range.start > range.end || range.end > self.len {
+            return Err(EncodingError::OutOfRange {
+                start: range.start,
+                end: range.end,
+                len: self.len,
+            });
+        }
+        let mut result = Self::with_capacity((range.end - range.start) as usize);
+        for index in range.clone() {
+            result.push_base(self.raw_base(index));
+        }
+        result.ambiguity_mask = self
+            .ambiguity_mask
+            .iter()
+            .filter(|run| run.end() > range.start && run.start < range.end)
+            .map(|run| {
+                let start = run.start.max(range.start);
+                let end = run.end().min(range.end);
+                AmbiguityRun {
+                    start: start - range.start,
+                    len: end - start,
+                    code: run.code,
+                }
+            })
+            .collect();
+        Ok(result)
+    }
+
+    pub fn extend_from(&mut self, other: &NucleotideSequence) {
+        let offset = self.len;
+        for index in 0..other.len {
+            self.push_base(other.raw_base(index));
+        }
+        for run in &other.ambiguity_mask {
+            match self.ambiguity_mask.last_mut() {
+                Some(last) if last.code == run.code && last.end() == run.start + offset => {
+                    last.len += run.len
+                }
+                _ => self.ambiguity_mask.push(AmbiguityRun {
+                    start: run.start + offset,
+                    ..*run
+                }),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nucleotide_round_trip() {
+        let raw = b"ACGTNNNNacgtRYKM";
+        let sequence = NucleotideSequence::from_ascii(raw).unwrap();
+        assert_eq!(sequence.len(), 16);
+        assert_eq!(sequence.packed().len(), 4);
+        assert_eq!(sequence.to_ascii(), b"ACGTNNNNACGTRYKM".to_vec());
+        assert_eq!(sequence.ambiguity_mask().len(), 5);
+        assert_eq!(sequence.base(4), None);
+        assert_eq!(sequence.base(2), Some(Nucleotide::G));
+        assert!(NucleotideSequence::from_ascii(b"ACGX").is_err());
+    }
+
+    #[test]
+    fn test_reverse_complement_and_slice() {
+        let sequence = NucleotideSequence::from_ascii(b"AACGNNRT").unwrap();
+        assert_eq!(
+            sequence.reverse_complement().to_ascii(),
+            b"AYNNCGTT".to_vec()
+        );
+        assert_eq!(sequence.slice(3..7).unwrap().to_ascii(), b"GNNR".to_vec());
+        assert_eq!(sequence.slice(3..7).unwrap().ambiguity_mask()[0].start, 1);
+        assert!(sequence.slice(5..9).is_err());
+    }
+
+    #[test]
+    fn test_deserialization_checks_invariants() {
+        let sequence = NucleotideSequence::from_ascii(b"ACGTNNR").unwrap();
+        let bytes = borsh::to_vec(&sequence).unwrap();
+        assert_eq!(
+            NucleotideSequence::try_from_slice(&bytes).unwrap(),
+            sequence
+        );
+        let json = serde_json::to_string(&sequence).unwrap();
+        assert_eq!(
+            serde_json::from_str::<NucleotideSequence>(&json).unwrap(),
+            sequence
+        );
+
+        let tampered = |edit: fn(&mut NucleotideSequence)| {
+            let mut copy = sequence.clone();
+            edit(&mut copy);
+            copy
+        };
+        let broken = [
+            tampered(|s| s.len = 64),
+            tampered(|s| s.packed[1] |= 0b11),
+            tampered(|s| s.ambiguity_mask[1].start = 5),
+            tampered(|s| s.ambiguity_mask[1].len = 9),
+            tampered(|s| s.ambiguity_mask[0].len = 0),
+            tampered(|s| s.ambiguity_mask[1].code = b'A'),
+            tampered(|s| s.ambiguity_mask[1].start = u64::MAX),
+        ];
+        for bad in &broken {
+            assert!(NucleotideSequence::try_from_slice(&borsh::to_vec(bad).unwrap()).is_err());
+            assert!(serde_json::from_str::<NucleotideSequence>(
+                &serde_json::to_string(bad).unwrap()
+            )
+            .is_err());
+        }
+    }
+}
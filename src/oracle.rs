@@ -0,0 +1,134 @@
+use crate::consensus::ConsensusError;
+use crate::model::{unix_timestamp, SequenceData};
+use crate::proof::ValidationProof;
+use reqwest::{Client, Error};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OracleNetworkRequest {
+    pub sequence_data: SequenceData,
+    pub validator_node: Pubkey,
+    pub timestamp: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OracleNetworkResponse {
+    pub status: String,
+    pub sequence_id: String,
+    pub validation_proof: Option<ValidationProof>,
+    pub error_message: Option<String>,
+}
+
+pub struct ModularOracleNetworkClient {
+    client: Client,
+    base_url: String,
+}
+
+impl ModularOracleNetworkClient {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            client: Client::new(),
+            base_url: base_url.to_string(),
+        }
+    }
+
+    pub async fn send_oracle_request(
+        &self,
+        request: OracleNetworkRequest,
+    ) -> Result<OracleNetworkResponse, Error> {
+        let url = format!("{}/api/oracle/validate", self.base_url);
+
+        let response = self.client.post(url).json(&request).send().await?;
+
+        response.json::<OracleNetworkResponse>().await
+    }
+
+    pub async fn get_oracle_validation_status(
+        &self,
+        sequence_id: &str,
+    ) -> Result<OracleNetworkResponse, Error> {
+        let url = format!("{}/api/oracle/status/{}", self.base_url, sequence_id);
+
+        let response = self.client.get(url).send().await?;
+
+        response.json::<OracleNetworkResponse>().await
+    }
+}
+
+pub struct OracleNetworkManager {
+    oracle_client: ModularOracleNetworkClient,
+    validation_threshold: u8,
+}
+
+impl OracleNetworkManager {
+    pub fn new(oracle_client: ModularOracleNetworkClient, validation_threshold: u8) -> Self {
+        Self {
+            oracle_client,
+            validation_threshold,
+        }
+    }
+
+    /// Percentage of `validator_nodes` whose proofs are needed for consensus.
+    pub fn validation_threshold(&self) -> u8 {
+        self.validation_threshold
+    }
+
+    pub async fn gather_oracle_validations(
+        &self,
+        sequence_data: &SequenceData,
+    ) -> Result<Vec<ValidationProof>, ConsensusError> {
+        let mut validation_proofs = Vec::new();
+
+        // Communicate with every validator node in the Oracle Network
+        for validator_node in &sequence_data.validator_nodes {
+            validation_proofs.push(
+                self.fetch_validation_proof_from_oracle(validator_node, sequence_data)
+                    .await?,
+            );
+        }
+
+        Ok(validation_proofs)
+    }
+
+    pub async fn fetch_validation_proof_from_oracle(
+        &self,
+        validator_node: &Pubkey,
+        sequence_data: &SequenceData,
+    ) -> Result<ValidationProof, ConsensusError> {
+        let oracle_request = OracleNetworkRequest {
+            sequence_data: sequence_data.clone(),
+            validator_node: *validator_node,
+            timestamp: unix_timestamp(),
+        };
+
+        let oracle_response = self
+            .oracle_client
+            .send_oracle_request(oracle_request)
+            .await
+            .map_err(|e| ConsensusError::OracleError(e.to_string()))?;
+
+        match (
+            oracle_response.status.as_str(),
+            oracle_response.validation_proof,
+        ) {
+            ("Validated", Some(proof)) => {
+                // The oracle relays the node's proof; only the node's signature makes it count.
+                if proof.validator != *validator_node
+                    || proof.sequence_hash != sequence_data.sequence_hash
+                {
+                    return Err(ConsensusError::SequenceMismatch);
+                }
+                proof
+                    .verify_signature()
+                    .map_err(|_| ConsensusError::InvalidSignature)?;
+                Ok(proof)
+            }
+            _ => Err(ConsensusError::ProofError(
+                oracle_response
+                    .error_message
+                    .unwrap_or_else(|| "Validation failed".to_string()),
+            )),
+        }
+    }
+}
@@ -0,0 +1,323 @@
+use crate::processing::{FastxError, FastxReader, SequenceRecord};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_MIN_ADAPTER_OVERLAP: usize = 5; // Shorter read-end matches occur by chance too often
+pub const DEFAULT_MAX_ADAPTER_ERROR_RATE: f32 = 0.1;
+
+/// Which end of a read an adapter or primer is found at. 3' adapters are
+/// read through into when the insert is shorter than the read; 5' ones are
+/// sequenced before the insert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdapterEnd {
+    Five,
+    Three,
+}
+
+/// An adapter or primer sequence. IUPAC codes match any base they stand for,
+/// so degenerate primers can be listed as ordered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Adapter {
+    pub name: String,
+    pub sequence: Vec<u8>,
+    pub end: AdapterEnd,
+}
+
+impl Adapter {
+    pub fn new(name: &str, sequence: &[u8], end: AdapterEnd) -> Self {
+        Self {
+            name: name.to_string(),
+            sequence: sequence.to_vec(),
+            end,
+        }
+    }
+
+    /// Every record of a FASTA or FASTQ adapter list, all at `end`.
+    pub fn from_reader(reader: FastxReader, end: AdapterEnd) -> Result<Vec<Self>, FastxError> {
+        reader
+            .map(|record| {
+                record.map(|record| Self {
+                    name: record.name,
+                    sequence: record.bases,
+                    end,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Built-in adapter sets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdapterSet {
+    TruSeq,
+    Nextera,
+    NanoporeLigation,
+}
+
+impl AdapterSet {
+    pub fn adapters(self) -> Vec<Adapter> {
+        match self {
+            AdapterSet::TruSeq => vec![
+                Adapter::new(
+                    "TruSeq Read 1",
+                    b"AGATCGGAAGAGCACACGTCTGAACTCCAGTCA",
+                    AdapterEnd::Three,
+                ),
+                Adapter::new(
+                    "TruSeq Read 2",
+                    b"AGATCGGAAGAGCGTCGTGTAGGGAAAGAGTGT",
+                    AdapterEnd::Three,
+                ),
+            ],
+            AdapterSet::Nextera => vec![Adapter::new(
+                "Nextera transposase",
+                b"CTGTCTCTTATACACATCT",
+                AdapterEnd::Three,
+            )],
+            AdapterSet::NanoporeLigation => vec![
+                Adapter::new(
+                    "Nanopore ligation start",
+                    b"AATGTACTTCGTTCAGTTACGTATTGCT",
+                    AdapterEnd::Five,
+                ),
+                Adapter::new(
+                    "Nanopore ligation end",
+                    b"AGCAATACGTAACTGAACGAAGT",
+                    AdapterEnd::Three,
+                ),
+            ],
+        }
+    }
+}
+
+/// Reads trimmed and bases removed, overall and per adapter name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdapterReport {
+    pub reads_trimmed: usize,
+    pub bases_removed: usize,
+    pub by_adapter: BTreeMap<String, usize>, // Reads trimmed by each adapter
+}
+
+impl AdapterReport {
+    /// "TruSeq Read 1: 12, Nextera transposase: 3"
+    pub fn summary(&self) -> String {
+        self.by_adapter
+            .iter()
+            .map(|(name, reads)| format!("{}: {}", name, reads))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+fn iupac_bits(code: u8) -> u8 {
+    match code {
+        b'A' => 0b0001,
+        b'C' => 0b0010,
+        b'G' => 0b0100,
+        b'T' => 0b1000,
+        b'R' => 0b0101,
+        b'Y' => 0b1010,
+        b'S' => 0b0110,
+        b'W' => 0b1001,
+        b'K' => 0b1100,
+        b'M' => 0b0011,
+        b'B' => 0b1110,
+        b'D' => 0b1101,
+        b'H' => 0b1011,
+        b'V' => 0b0111,
+        b'N' => 0b1111,
+        _ => 0,
+    }
+}
+
+/// Trims adapters by ungapped matching, as cutadapt does without indels: an
+/// adapter is found where it lies wholly inside the read, or where a prefix
+/// of at least `min_overlap` bases (a suffix, for 5' adapters) runs off the
+/// read end, with at most `max_error_rate` mismatches. Ambiguous read bases
+/// never match.
+pub struct AdapterTrimmer {
+    adapters: Vec<Adapter>,
+    min_overlap: usize,
+    max_error_rate: f32,
+}
+
+impl AdapterTrimmer {
+    pub fn new(adapters: Vec<Adapter>, min_overlap: usize, max_error_rate: f32) -> Self {
+        Self {
+            adapters,
+            min_overlap: min_overlap.max(1),
+            max_error_rate,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adapters.is_empty()
+    }
+
+    /// Leftmost offset in `read` where `adapter` starts, allowing it to run
+    /// off the end.
+    fn find_three_prime(&self, read: &[u8], adapter: &[u8]) -> Option<usize> {
+        (0..read.len()).find(|&start| {
+            let overlap = adapter.len().min(read.len() - start);
+            if overlap < self.min_overlap.min(adapter.len()) {
+                return false;
+            }
+            let mismatches = read[start..start + overlap]
+                .iter()
+                .zip(adapter)
+                .filter(|&(&base, &code)| {
+                    iupac_bits(base).count_ones() != 1 || iupac_bits(base) & iupac_bits(code) == 0
+                })
+                .count();
+            mismatches as f32 <= self.max_error_rate * overlap as f32
+        })
+    }
+
+    /// Trims every adapter from `read`, recording what was removed.
+    pub fn trim(&self, read: &mut SequenceRecord, report: &mut AdapterReport) {
+        let original_len = read.len();
+        for adapter in &self.adapters {
+            let removed = match adapter.end {
+                AdapterEnd::Three => {
+                    self.find_three_prime(&read.bases, &adapter.sequence)
+                        .map(|start| {
+                            let removed = read.len() - start;
+                            read.truncate(start);
+                            removed
+                        })
+                }
+                AdapterEnd::Five => {
+                    // A 5' adapter is a 3' adapter of the reversed read.
+                    let reversed_read: Vec<u8> = read.bases.iter().rev().copied().collect();
+                    let reversed_adapter: Vec<u8> =
+                        adapter.sequence.iter().rev().copied().collect();
+                    self.find_three_prime(&reversed_read, &reversed_adapter)
+                        .map(|start| {
+                            let removed = read.len() - start;
+                            read.bases.drain(..removed);
+                            if let Some(qualities) = read.qualities.as_mut() {
+                                qualities.drain(..removed);
+                            }
+                            removed
+                        })
+                }
+            };
+            if removed.is_some() {
+                *report.by_adapter.entry(adapter.name.clone()).or_default() += 1;
+            }
+        }
+        if read.len() < original_len {
+            report.reads_trimmed += 1;
+            report.bases_removed += original_len - read.len();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::{ProcessingConfig, SequenceProcessor, NOVASEQ_6000};
+    use std::io::Cursor;
+
+    const INSERT: &[u8] = b"GATTACAGGCTTCAGTCCATGGACTAGCTTAG";
+
+    fn read(bases: &[u8]) -> SequenceRecord {
+        SequenceRecord {
+            name: "read".to_string(),
+            description: None,
+            bases: bases.to_vec(),
+            qualities: Some((0..bases.len() as u8).collect()),
+        }
+    }
+
+    fn trimmer(sets: &[AdapterSet]) -> AdapterTrimmer {
+        AdapterTrimmer::new(
+            sets.iter().flat_map(|set| set.adapters()).collect(),
+            DEFAULT_MIN_ADAPTER_OVERLAP,
+            DEFAULT_MAX_ADAPTER_ERROR_RATE,
+        )
+    }
+
+    #[test]
+    fn test_three_prime_adapters() {
+        let trimmer = trimmer(&[AdapterSet::TruSeq, AdapterSet::Nextera]);
+        let mut report = AdapterReport::default();
+
+        // Read-through into the whole adapter and beyond.
+        let mut through = read(&[INSERT, b"AGATCGGAAGAGCACACGTCTGAACTCCAGTCAATCTCGTAT"].concat());
+        trimmer.trim(&mut through, &mut report);
+        assert_eq!(through.bases, INSERT);
+        assert_eq!(through.qualities.as_ref().map(Vec::len), Some(INSERT.len()));
+
+        // A partial adapter with one mismatch at the read end.
+        let mut partial = read(&[INSERT, b"CTGTCTCTTATTCA"].concat());
+        trimmer.trim(&mut partial, &mut report);
+        assert_eq!(partial.bases, INSERT);
+
+        // Four bases of adapter are below the minimum overlap.
+        let mut short = read(&[INSERT, b"AGAT"].concat());
+        trimmer.trim(&mut short, &mut report);
+        assert_eq!(short.len(), INSERT.len() + 4);
+
+        assert_eq!(report.reads_trimmed, 2);
+        assert_eq!(report.bases_removed, 42 + 14);
+        assert_eq!(report.summary(), "Nextera transposase: 1, TruSeq Read 1: 1");
+    }
+
+    #[test]
+    fn test_five_prime_adapters_and_primer_lists() {
+        let mut report = AdapterReport::default();
+        let nanopore = trimmer(&[AdapterSet::NanoporeLigation]);
+        let mut long_read = read(&[&b"TACGTATTGCT"[..], INSERT, b"AGCAATACGTAACTG"].concat());
+        nanopore.trim(&mut long_read, &mut report);
+        assert_eq!(long_read.bases, INSERT);
+        assert_eq!(long_read.qualities.unwrap()[0], 11);
+        assert_eq!(report.by_adapter.len(), 2);
+
+        // A degenerate primer from a user FASTA list.
+        let primers = Adapter::from_reader(
+            FastxReader::new(Cursor::new(b">16S-27F\nAGAGTTTGATCMTGGCTCAG\n".to_vec())).unwrap(),
+            AdapterEnd::Five,
+        )
+        .unwrap();
+        assert_eq!(primers[0].name, "16S-27F");
+        let amplicon = AdapterTrimmer::new(primers, DEFAULT_MIN_ADAPTER_OVERLAP, 0.0);
+        for primer in [&b"AGAGTTTGATCATGGCTCAG"[..], b"AGAGTTTGATCCTGGCTCAG"] {
+            let mut read = read(&[primer, INSERT].concat());
+            amplicon.trim(&mut read, &mut report);
+            assert_eq!(read.bases, INSERT);
+        }
+        let mut mismatched = read(&[&b"AGAGTTTGATCGTGGCTCAG"[..], INSERT].concat());
+        amplicon.trim(&mut mismatched, &mut report);
+        assert_eq!(mismatched.len(), 20 + INSERT.len());
+    }
+
+    #[test]
+    fn test_adapter_trimming_logged() {
+        let fastq = [
+            &b"@read\n"[..],
+            INSERT,
+            b"AGATCGGAAGAGCACACG\n+\n",
+            &[b'I'; 50],
+            b"\n",
+        ]
+        .concat();
+        let processor = SequenceProcessor::new(ProcessingConfig {
+            seed_length: 11,
+            ..NOVASEQ_6000.processing_config()
+        });
+        let result = processor.process_sequence(fastq.clone()).unwrap();
+        assert_eq!(
+            result.processing_log[0],
+            "adapter trimming removed 18 bases from 1 reads (TruSeq Read 1: 1)"
+        );
+        assert_eq!(result.sequence.to_ascii(), INSERT);
+
+        let untrimmed = SequenceProcessor::new(ProcessingConfig {
+            seed_length: 11,
+            ..ProcessingConfig::default()
+        });
+        let result = untrimmed.process_sequence(fastq).unwrap();
+        assert!(result.processing_log[0].starts_with("quality control kept"));
+        assert_eq!(result.sequence.len(), 50);
+    }
+}
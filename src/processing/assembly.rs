@@ -0,0 +1,718 @@
+use crate::model::{iupac_complement, NucleotideSequence};
+use crate::processing::denovo::{assemble_de_novo, DeNovoReport};
+use crate::processing::metrics::GAP;
+use crate::processing::{
+    AlignedRead, AssemblyMode, ProcessingConfig, ProcessingError, SequenceRecord,
+};
+use std::collections::HashMap;
+
+pub const DEFAULT_SEED_LENGTH: usize = 15;
+pub const DEFAULT_MAX_INSERT_SIZE: u64 = 1000;
+pub const DEFAULT_BAND_WIDTH: usize = 32;
+const MAX_SEED_LENGTH: usize = 32;
+const MAX_SEED_OCCURRENCES: usize = 64; // Repeat seeds beyond this are ignored
+const MATCH_SCORE: i32 = 1;
+const MISMATCH_SCORE: i32 = -2;
+const GAP_SCORE: i32 = -3;
+const UNREACHABLE: i32 = i32::MIN / 2;
+
+pub(crate) fn reverse_complement(bases: &[u8]) -> Vec<u8> {
+    bases
+        .iter()
+        .rev()
+        .map(|&base| iupac_complement(base))
+        .collect()
+}
+
+fn base_code(base: u8) -> Option<u64> {
+    match base {
+        b'A' => Some(0),
+        b'C' => Some(1),
+        b'G' => Some(2),
+        b'T' => Some(3),
+        _ => None,
+    }
+}
+
+/// 2-bit packed k-mers of `bases` with their start offsets, skipping any
+/// k-mer that spans an ambiguous base.
+pub(crate) fn kmers(bases: &[u8], seed_length: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+    let mask = if seed_length == MAX_SEED_LENGTH {
+        u64::MAX
+    } else {
+        (1 << (2 * seed_length)) - 1
+    };
+    let mut code = 0u64;
+    let mut run = 0usize;
+    bases
+        .iter()
+        .enumerate()
+        .filter_map(move |(position, &base)| {
+            match base_code(base) {
+                Some(bits) => {
+                    code = ((code << 2) | bits) & mask;
+                    run += 1;
+                }
+                None => run = 0,
+            }
+            (run >= seed_length).then(|| (position + 1 - seed_length, code))
+        })
+}
+
+/// Exact k-mer index over a reference sequence.
+pub struct ReferenceIndex {
+    seed_length: usize,
+    reference: Vec<u8>,
+    seeds: HashMap<u64, Vec<u32>>,
+}
+
+impl ReferenceIndex {
+    pub fn new(
+        reference: &NucleotideSequence,
+        seed_length: usize,
+    ) -> Result<Self, ProcessingError> {
+        if seed_length == 0 || seed_length > MAX_SEED_LENGTH {
+            return Err(ProcessingError::InvalidSeedLength(seed_length));
+        }
+        if reference.is_empty() {
+            return Err(ProcessingError::EmptySequence);
+        }
+
+        let reference = reference.to_ascii();
+        let mut seeds: HashMap<u64, Vec<u32>> = HashMap::new();
+        for (position, code) in kmers(&reference, seed_length) {
+            seeds.entry(code).or_default().push(position as u32);
+        }
+
+        Ok(Self {
+            seed_length,
+            reference,
+            seeds,
+        })
+    }
+
+    pub fn reference(&self) -> &[u8] {
+        &self.reference
+    }
+
+    pub fn seed_length(&self) -> usize {
+        self.seed_length
+    }
+
+    /// Reference diagonal (reference position minus read position) with the
+    /// most seed hits; ties go to the leftmost diagonal.
+    fn best_diagonal(&self, bases: &[u8]) -> Option<i64> {
+        let mut votes: HashMap<i64, u32> = HashMap::new();
+        for (offset, code) in kmers(bases, self.seed_length) {
+            let Some(hits) = self
+                .seeds
+                .get(&code)
+                .filter(|hits| hits.len() <= MAX_SEED_OCCURRENCES)
+            else {
+                continue;
+            };
+            for &position in hits {
+                *votes.entry(position as i64 - offset as i64).or_default() += 1;
+            }
+        }
+        votes
+            .into_iter()
+            .max_by(|(left, left_votes), (right, right_votes)| {
+                left_votes.cmp(right_votes).then(right.cmp(left))
+            })
+            .map(|(diagonal, _)| diagonal)
+    }
+}
+
+/// A read aligned to an indexed sequence. `insertions` hold read bases with no
+/// reference column, keyed by the reference position they precede.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadMapping {
+    pub alignment: AlignedRead,
+    pub insertions: Vec<(u64, Vec<u8>)>,
+    pub reverse: bool,
+    pub score: i32,
+    pub identity: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum AlignOp {
+    Stop,
+    Match,
+    Insertion,
+    Deletion,
+}
+
+/// Maps reads by k-mer seeding followed by a banded semi-global alignment
+/// around the best-supported diagonal: the whole read is aligned, the
+/// reference ends are free.
+pub struct ReadMapper<'a> {
+    index: &'a ReferenceIndex,
+    band_width: usize,
+    min_identity: f32,
+}
+
+impl<'a> ReadMapper<'a> {
+    pub fn new(index: &'a ReferenceIndex, band_width: usize, min_identity: f32) -> Self {
+        Self {
+            index,
+            band_width,
+            min_identity,
+        }
+    }
+
+    /// Best mapping over both strands, or `None` if the read has no seed hit
+    /// or aligns below the identity threshold.
+    pub fn map(&self, read: &SequenceRecord) -> Option<ReadMapping> {
+        let forward = self.map_strand(&read.bases, read.qualities.clone(), false);
+        let reverse_qualities = read
+            .qualities
+            .as_ref()
+            .map(|qualities| qualities.iter().rev().copied().collect());
+        let reverse = self.map_strand(&reverse_complement(&read.bases), reverse_qualities, true);
+
+        match (forward, reverse) {
+            (Some(forward), Some(reverse)) if reverse.score > forward.score => Some(reverse),
+            (Some(forward), _) => Some(forward),
+            (None, reverse) => reverse,
+        }
+    }
+
+    fn map_strand(
+        &self,
+        bases: &[u8],
+        qualities: Option<Vec<u8>>,
+        reverse: bool,
+    ) -> Option<ReadMapping> {
+        let diagonal = self.index.best_diagonal(bases)?;
+        let (start, ops, score) = self.align(bases, diagonal)?;
+
+        let mut columns = Vec::with_capacity(bases.len());
+        let mut column_qualities = Vec::with_capacity(bases.len());
+        let mut insertions: Vec<(u64, Vec<u8>)> = Vec::new();
+        let (mut reference_position, mut read_position, mut matches) = (start, 0, 0);
+        for op in &ops {
+            match op {
+                AlignOp::Match => {
+                    let base = bases[read_position];
+                    if base == self.index.reference[reference_position as usize] {
+                        matches += 1;
+                    }
+                    columns.push(base);
+                    column_qualities.push(
+                        qualities
+                            .as_ref()
+                            .map_or(0, |qualities| qualities[read_position]),
+                    );
+                    reference_position += 1;
+                    read_position += 1;
+                }
+                AlignOp::Deletion => {
+                    columns.push(GAP);
+                    column_qualities.push(0);
+                    reference_position += 1;
+                }
+                AlignOp::Insertion => {
+                    match insertions.last_mut() {
+                        Some((position, inserted)) if *position == reference_position => {
+                            inserted.push(bases[read_position])
+                        }
+                        _ => insertions.push((reference_position, vec![bases[read_position]])),
+                    }
+                    read_position += 1;
+                }
+                AlignOp::Stop => unreachable!("stop is never emitted"),
+            }
+        }
+
+        let identity = matches as f32 / ops.len() as f32;
+        if identity < self.min_identity {
+            return None;
+        }
+        Some(ReadMapping {
+            alignment: AlignedRead {
+                start,
+                bases: columns,
+                qualities: qualities.map(|_| column_qualities),
+            },
+            insertions,
+            reverse,
+            score,
+            identity,
+        })
+    }
+
+    /// Banded DP within `band_width` of `diagonal`. Returns the reference
+    /// start, the edit operations and the score.
+    fn align(&self, bases: &[u8], diagonal: i64) -> Option<(u64, Vec<AlignOp>, i32)> {
+        let reference = &self.index.reference;
+        let band = self.band_width as i64;
+        let window_start = (diagonal - band).max(0);
+        let window_end = (diagonal + bases.len() as i64 + band).min(reference.len() as i64);
+        if window_end <= window_start {
+            return None;
+        }
+        let window = &reference[window_start as usize..window_end as usize];
+        let center = diagonal - window_start;
+        let width = 2 * self.band_width + 1;
+        let rows = bases.len() + 1;
+
+        // Cell (row, k) is window column `row + center - band + k`.
+        let column = |row: usize, k: usize| -> Option<usize> {
+            let j = row as i64 + center - band + k as i64;
+            (0..=window.len() as i64).contains(&j).then_some(j as usize)
+        };
+        let mut scores = vec![UNREACHABLE; rows * width];
+        let mut trace = vec![AlignOp::Stop; rows * width];
+        for (k, score) in scores[..width].iter_mut().enumerate() {
+            if column(0, k).is_some() {
+                *score = 0;
+            }
+        }
+
+        for row in 1..rows {
+            for k in 0..width {
+                let Some(j) = column(row, k) else {
+                    continue;
+                };
+                let cell = row * width + k;
+                let mut best = (UNREACHABLE, AlignOp::Stop);
+                if j > 0 {
+                    let previous = scores[cell - width];
+                    if previous > UNREACHABLE {
+                        best = (
+                            previous + substitution_score(bases[row - 1], window[j - 1]),
+                            AlignOp::Match,
+                        );
+                    }
+                }
+                if k + 1 < width && scores[cell - width + 1] > UNREACHABLE {
+                    let score = scores[cell - width + 1] + GAP_SCORE;
+                    if score > best.0 {
+                        best = (score, AlignOp::Insertion);
+                    }
+                }
+                if k > 0 && j > 0 && scores[cell - 1] > UNREACHABLE {
+                    let score = scores[cell - 1] + GAP_SCORE;
+                    if score > best.0 {
+                        best = (score, AlignOp::Deletion);
+                    }
+                }
+                scores[cell] = best.0;
+                trace[cell] = best.1;
+            }
+        }
+
+        let last_row = rows - 1;
+        let (mut k, score) = (0..width)
+            .filter(|&k| column(last_row, k).is_some())
+            .map(|k| (k, scores[last_row * width + k]))
+            .filter(|&(_, score)| score > UNREACHABLE)
+            .max_by(|left, right| left.1.cmp(&right.1).then(right.0.cmp(&left.0)))?;
+
+        let mut ops = Vec::with_capacity(rows);
+        let mut row = last_row;
+        loop {
+            let op = trace[row * width + k];
+            match op {
+                AlignOp::Stop => break,
+                AlignOp::Match => row -= 1,
+                AlignOp::Insertion => {
+                    row -= 1;
+                    k += 1;
+                }
+                AlignOp::Deletion => k -= 1,
+            }
+            ops.push(op);
+        }
+        ops.reverse();
+
+        let start = window_start as u64 + column(row, k)? as u64;
+        Some((start, ops, score))
+    }
+}
+
+fn substitution_score(read_base: u8, reference_base: u8) -> i32 {
+    match (base_code(read_base), base_code(reference_base)) {
+        (Some(read), Some(reference)) if read == reference => MATCH_SCORE,
+        (Some(_), Some(_)) => MISMATCH_SCORE,
+        _ => 0,
+    }
+}
+
+fn column_slot(base: u8) -> Option<usize> {
+    match base {
+        GAP => Some(4),
+        base => base_code(base).map(|code| code as usize),
+    }
+}
+
+/// Majority-vote consensus of `mappings` over `reference`. Each column takes
+/// the most frequent read base, ties going to the reference base; a column
+/// won outright by deletions is dropped, columns below `min_depth` become
+/// `N`, and an insertion is kept when more than half the reads spanning it
+/// carry the same inserted bases.
+pub fn call_consensus(reference: &[u8], mappings: &[ReadMapping], min_depth: u32) -> Vec<u8> {
+    let mut counts = vec![[0u32; 5]; reference.len()];
+    let mut spanning = vec![0i64; reference.len() + 1];
+    let mut insertions: HashMap<u64, HashMap<&[u8], u32>> = HashMap::new();
+    for mapping in mappings {
+        let alignment = &mapping.alignment;
+        for (offset, &base) in alignment.bases.iter().enumerate() {
+            if let Some(slot) = column_slot(base) {
+                counts[alignment.start as usize + offset][slot] += 1;
+            }
+        }
+        // Spans the gap before position p when it covers p - 1 and p.
+        spanning[alignment.start as usize + 1] += 1;
+        spanning[alignment.end() as usize] -= 1;
+        for (position, inserted) in &mapping.insertions {
+            *insertions
+                .entry(*position)
+                .or_default()
+                .entry(inserted)
+                .or_default() += 1;
+        }
+    }
+
+    let mut consensus = Vec::with_capacity(reference.len());
+    let mut spanning_reads = 0;
+    for (position, column) in counts.iter().enumerate() {
+        spanning_reads += spanning[position];
+        if let Some(candidates) = insertions.get(&(position as u64)) {
+            let best = candidates
+                .iter()
+                .max_by(|(left, left_count), (right, right_count)| {
+                    left_count.cmp(right_count).then(right.cmp(left))
+                });
+            if let Some((inserted, &count)) = best {
+                if position > 0 && 2 * count as i64 > spanning_reads {
+                    consensus.extend_from_slice(inserted);
+                }
+            }
+        }
+
+        let depth: u32 = column.iter().sum();
+        if depth < min_depth.max(1) {
+            consensus.push(b'N');
+            continue;
+        }
+        let reference_slot = column_slot(reference[position]);
+        let (slot, _) = column
+            .iter()
+            .enumerate()
+            .max_by(|(left, left_count), (right, right_count)| {
+                left_count
+                    .cmp(right_count)
+                    .then((Some(*left) == reference_slot).cmp(&(Some(*right) == reference_slot)))
+                    .then(right.cmp(left))
+            })
+            .expect("five slots");
+        if slot == 4 {
+            continue;
+        }
+        consensus.push(b"ACGT"[slot]);
+    }
+    consensus
+}
+
+/// Insert size of `first` and `second` if they map as a forward-reverse pair
+/// facing each other no more than `max_insert_size` apart.
+fn pair_insert(first: &ReadMapping, second: &ReadMapping, max_insert_size: u64) -> Option<u64> {
+    if first.reverse == second.reverse {
+        return None;
+    }
+    let (forward, reverse) = if first.reverse {
+        (&second.alignment, &first.alignment)
+    } else {
+        (&first.alignment, &second.alignment)
+    };
+    if forward.start > reverse.start || forward.end() > reverse.end() {
+        return None;
+    }
+    Some(reverse.end() - forward.start).filter(|&insert| insert <= max_insert_size)
+}
+
+/// How the mates of each pair landed on the assembly. Pairs with a mate that
+/// did not map count as neither.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PairPlacement {
+    pub insert_sizes: Vec<u64>, // One per properly paired fragment
+    pub discordant_pairs: usize,
+}
+
+/// An assembled sequence and the reads placed on it. `de_novo` is set when
+/// the sequence came from the de novo path and `pairs` when mates were given.
+pub struct Assembly {
+    pub sequence: NucleotideSequence,
+    pub alignments: Vec<AlignedRead>,
+    pub de_novo: Option<DeNovoReport>,
+    pub pairs: Option<PairPlacement>,
+}
+
+/// Reference-guided assembler: maps reads to the reference, calls a
+/// consensus from the pileup, then maps the reads again onto the consensus so
+/// the returned alignments are in assembly coordinates. Without a reference
+/// the longest read serves as one. In `AssemblyMode::DeNovo` the consensus
+/// step is replaced by a de Bruijn assembly.
+///
+/// Given mates, both reads of a discordant pair (same strand, not facing, or
+/// beyond `max_insert_size`) are kept out of the consensus and the returned
+/// alignments, since such fragments are usually chimeras or misplaced
+/// repeat copies.
+pub struct SequenceAssembler {
+    mode: AssemblyMode,
+    reference: Option<ReferenceIndex>,
+    seed_length: usize,
+    band_width: usize,
+    min_identity: f32,
+    min_consensus_depth: u32,
+    kmer_length: usize,
+    min_kmer_count: u32,
+    max_insert_size: u64,
+}
+
+impl SequenceAssembler {
+    pub fn new(config: &ProcessingConfig) -> Self {
+        Self {
+            mode: config.assembly_mode,
+            reference: None,
+            seed_length: config.seed_length,
+            band_width: config.band_width,
+            min_identity: config.min_identity,
+            min_consensus_depth: config.min_consensus_depth,
+            kmer_length: config.kmer_length,
+            min_kmer_count: config.min_kmer_count,
+            max_insert_size: config.max_insert_size,
+        }
+    }
+
+    pub fn with_reference(
+        mut self,
+        reference: &NucleotideSequence,
+    ) -> Result<Self, ProcessingError> {
+        self.reference = Some(ReferenceIndex::new(reference, self.seed_length)?);
+        Ok(self)
+    }
+
+    pub fn reference(&self) -> Option<&ReferenceIndex> {
+        self.reference.as_ref()
+    }
+
+    pub fn assemble(&self, reads: &[SequenceRecord]) -> Result<Assembly, ProcessingError> {
+        self.assemble_pairs(reads, &[])
+    }
+
+    /// Like `assemble`, with `mates` pairing up indices into `reads`.
+    pub fn assemble_pairs(
+        &self,
+        reads: &[SequenceRecord],
+        mates: &[(usize, usize)],
+    ) -> Result<Assembly, ProcessingError> {
+        let (consensus, de_novo) = match self.mode {
+            AssemblyMode::ReferenceGuided => (self.reference_consensus(reads, mates)?, None),
+            AssemblyMode::DeNovo => {
+                let (scaffold, report) =
+                    assemble_de_novo(reads, self.kmer_length, self.min_kmer_count)?;
+                (scaffold, Some(report))
+            }
+        };
+        let sequence = NucleotideSequence::from_ascii(&consensus)?;
+
+        let consensus_index = ReferenceIndex::new(&sequence, self.seed_length)?;
+        let mapper = ReadMapper::new(&consensus_index, self.band_width, self.min_identity);
+        let mut mappings: Vec<Option<ReadMapping>> =
+            reads.iter().map(|read| mapper.map(read)).collect();
+        let pairs = self.pair_mates(&mut mappings, mates);
+
+        Ok(Assembly {
+            sequence,
+            alignments: mappings
+                .into_iter()
+                .flatten()
+                .map(|mapping| mapping.alignment)
+                .collect(),
+            de_novo,
+            pairs: (!mates.is_empty()).then_some(pairs),
+        })
+    }
+
+    /// Drops both mappings of every discordant pair.
+    fn pair_mates(
+        &self,
+        mappings: &mut [Option<ReadMapping>],
+        mates: &[(usize, usize)],
+    ) -> PairPlacement {
+        let mut placement = PairPlacement::default();
+        for &(first, second) in mates {
+            let (Some(first_mapping), Some(second_mapping)) = (&mappings[first], &mappings[second])
+            else {
+                continue;
+            };
+            match pair_insert(first_mapping, second_mapping, self.max_insert_size) {
+                Some(insert) => placement.insert_sizes.push(insert),
+                None => {
+                    placement.discordant_pairs += 1;
+                    mappings[first] = None;
+                    mappings[second] = None;
+                }
+            }
+        }
+        placement
+    }
+
+    fn reference_consensus(
+        &self,
+        reads: &[SequenceRecord],
+        mates: &[(usize, usize)],
+    ) -> Result<Vec<u8>, ProcessingError> {
+        let backbone;
+        let index = match &self.reference {
+            Some(index) => index,
+            None => {
+                let longest = reads
+                    .iter()
+                    .max_by_key(|read| read.len())
+                    .ok_or(ProcessingError::EmptySequence)?;
+                backbone = ReferenceIndex::new(
+                    &NucleotideSequence::from_ascii(&longest.bases)?,
+                    self.seed_length,
+                )?;
+                &backbone
+            }
+        };
+
+        let mapper = ReadMapper::new(index, self.band_width, self.min_identity);
+        let mut mappings: Vec<Option<ReadMapping>> =
+            reads.iter().map(|read| mapper.map(read)).collect();
+        self.pair_mates(&mut mappings, mates);
+        let mappings: Vec<ReadMapping> = mappings.into_iter().flatten().collect();
+        if mappings.is_empty() {
+            return Err(ProcessingError::NoReadsMapped);
+        }
+        Ok(call_consensus(
+            index.reference(),
+            &mappings,
+            self.min_consensus_depth,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REFERENCE: &[u8] =
+        b"TTGACCGATGCATCGGATCCAGTACGTTAGCAAGCTTGCATGCCTGCAGGTCGACTCTAGAGGATCCCCGGGTACCGAGCTCGAATTC";
+
+    fn read(bases: &[u8]) -> SequenceRecord {
+        SequenceRecord {
+            name: String::new(),
+            description: None,
+            bases: bases.to_vec(),
+            qualities: Some(vec![35; bases.len()]),
+        }
+    }
+
+    /// Overlapping 30-base reads tiling `construct`, every other one
+    /// reverse-complemented.
+    fn tiled_reads(construct: &[u8]) -> Vec<SequenceRecord> {
+        (0..=construct.len() - 30)
+            .step_by(5)
+            .chain([construct.len() - 30])
+            .enumerate()
+            .map(|(i, start)| {
+                let bases = &construct[start..start + 30];
+                if i % 2 == 0 {
+                    read(bases)
+                } else {
+                    read(&reverse_complement(bases))
+                }
+            })
+            .collect()
+    }
+
+    fn config() -> ProcessingConfig {
+        ProcessingConfig {
+            seed_length: 11,
+            band_width: 8,
+            min_identity: 0.8,
+            ..ProcessingConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_mapper_reports_indels() {
+        let index =
+            ReferenceIndex::new(&NucleotideSequence::from_ascii(REFERENCE).unwrap(), 11).unwrap();
+        let mapper = ReadMapper::new(&index, 8, 0.8);
+
+        // Reference 10..60 with base 30 deleted and "GG" inserted before 45.
+        let mut bases = REFERENCE[10..30].to_vec();
+        bases.extend_from_slice(&REFERENCE[31..45]);
+        bases.extend_from_slice(b"GG");
+        bases.extend_from_slice(&REFERENCE[45..60]);
+        let mapping = mapper.map(&read(&reverse_complement(&bases))).unwrap();
+
+        assert!(mapping.reverse);
+        assert_eq!(mapping.alignment.start, 10);
+        assert_eq!(mapping.alignment.end(), 60);
+        assert_eq!(mapping.alignment.bases[20], GAP);
+        assert_eq!(mapping.alignment.qualities.as_ref().unwrap()[20], 0);
+        assert_eq!(mapping.insertions, vec![(45, b"GG".to_vec())]);
+
+        assert!(mapper.map(&read(b"ACACACACACACACACACAC")).is_none());
+        assert_eq!(
+            ReferenceIndex::new(&NucleotideSequence::from_ascii(REFERENCE).unwrap(), 33).err(),
+            Some(ProcessingError::InvalidSeedLength(33))
+        );
+    }
+
+    #[test]
+    fn test_reference_guided_assembly() {
+        // The construct differs from the reference by a substitution at 15,
+        // a deleted base at 45 and an insertion before 70.
+        let mut construct = REFERENCE[..45].to_vec();
+        construct[15] = b'T';
+        construct.extend_from_slice(&REFERENCE[46..70]);
+        construct.extend_from_slice(b"AAT");
+        construct.extend_from_slice(&REFERENCE[70..]);
+
+        let reference = NucleotideSequence::from_ascii(REFERENCE).unwrap();
+        let assembler = SequenceAssembler::new(&config())
+            .with_reference(&reference)
+            .unwrap();
+        let reads = tiled_reads(&construct);
+        let assembly = assembler.assemble(&reads).unwrap();
+
+        assert_eq!(assembly.sequence.to_ascii(), construct);
+        assert_eq!(assembly.alignments.len(), reads.len());
+        assert!(assembly
+            .alignments
+            .iter()
+            .all(|alignment| !alignment.bases.contains(&GAP)));
+
+        let metadata = crate::model::SequenceMetadata::new(
+            &assembly.sequence,
+            "E. coli".to_string(),
+            "pUC19".to_string(),
+            0,
+        );
+        let registered = crate::model::DNASequence::new(&assembly.sequence, metadata);
+        assert_eq!(
+            registered.sequence_hash,
+            NucleotideSequence::from_ascii(&construct)
+                .unwrap()
+                .sequence_hash()
+        );
+
+        let uncovered = assembler.assemble(&reads[..3]).unwrap().sequence.to_ascii();
+        assert_eq!(&uncovered[..40], &construct[..40]);
+        assert!(uncovered[40..].iter().all(|&base| base == b'N'));
+
+        assert_eq!(
+            assembler.assemble(&[read(b"ACACACACACACACACACAC")]).err(),
+            Some(ProcessingError::NoReadsMapped)
+        );
+    }
+}
@@ -0,0 +1,358 @@
+use crate::processing::assembly::{kmers, reverse_complement};
+use crate::processing::{ProcessingError, SequenceRecord};
+use std::collections::{HashMap, HashSet};
+use std::iter;
+use std::ops::Range;
+
+pub const DEFAULT_CORRECTION_KMER_LENGTH: usize = 21;
+pub const DEFAULT_MIN_SOLID_COUNT: u32 = 3;
+pub const DEFAULT_MAX_CORRECTION_RATE: f32 = 0.05;
+const MAX_KMER_LENGTH: usize = 32;
+const MAX_HOMOPOLYMER_SHIFT: usize = 2; // Run-length changes tried either way
+
+/// Which read correction stages run before assembly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CorrectionStrategy {
+    Disabled,
+    /// Substitutions only, for short reads with few indels (Illumina).
+    #[default]
+    KmerSpectrum,
+    /// Homopolymer run lengths only, for long reads (Nanopore).
+    Homopolymer,
+    /// Substitutions, then homopolymer run lengths.
+    Combined,
+}
+
+impl CorrectionStrategy {
+    pub fn corrects_substitutions(self) -> bool {
+        matches!(
+            self,
+            CorrectionStrategy::KmerSpectrum | CorrectionStrategy::Combined
+        )
+    }
+
+    pub fn corrects_indels(self) -> bool {
+        matches!(
+            self,
+            CorrectionStrategy::Homopolymer | CorrectionStrategy::Combined
+        )
+    }
+}
+
+/// Solid k-mers of a read set, counted over both strands. A k-mer seen at
+/// least `min_count` times is taken to be real sequence; anything rarer is
+/// assumed to carry a sequencing error.
+pub struct KmerSpectrum {
+    kmer_length: usize,
+    solid: HashSet<u64>,
+}
+
+impl KmerSpectrum {
+    pub fn from_reads(
+        reads: &[SequenceRecord],
+        kmer_length: usize,
+        min_count: u32,
+    ) -> Result<Self, ProcessingError> {
+        if !(2..=MAX_KMER_LENGTH).contains(&kmer_length) {
+            return Err(ProcessingError::InvalidSeedLength(kmer_length));
+        }
+
+        let mut counts: HashMap<u64, u32> = HashMap::new();
+        for read in reads {
+            for strand in [read.bases.clone(), reverse_complement(&read.bases)] {
+                for (_, code) in kmers(&strand, kmer_length) {
+                    *counts.entry(code).or_default() += 1;
+                }
+            }
+        }
+
+        Ok(Self {
+            kmer_length,
+            solid: counts
+                .into_iter()
+                .filter(|&(_, count)| count >= min_count)
+                .map(|(code, _)| code)
+                .collect(),
+        })
+    }
+
+    pub fn kmer_length(&self) -> usize {
+        self.kmer_length
+    }
+
+    pub fn solid_kmers(&self, bases: &[u8]) -> usize {
+        kmers(bases, self.kmer_length)
+            .filter(|(_, code)| self.solid.contains(code))
+            .count()
+    }
+
+    /// Whether the k-mer at `offset` is solid; a k-mer spanning an ambiguous
+    /// base never is.
+    fn solid_at(&self, bases: &[u8], offset: usize) -> bool {
+        kmers(&bases[offset..offset + self.kmer_length], self.kmer_length)
+            .any(|(_, code)| self.solid.contains(&code))
+    }
+
+    /// Offsets of the k-mers of `bases` overlapping `start..end`.
+    fn overlapping(&self, bases: &[u8], start: usize, end: usize) -> Range<usize> {
+        let first = start.saturating_sub(self.kmer_length - 1);
+        first..end.min(bases.len() + 1 - self.kmer_length).max(first)
+    }
+
+    fn any_solid(&self, bases: &[u8], start: usize, end: usize) -> bool {
+        self.overlapping(bases, start, end)
+            .any(|offset| self.solid_at(bases, offset))
+    }
+
+    fn all_solid(&self, bases: &[u8], start: usize, end: usize) -> bool {
+        self.overlapping(bases, start, end)
+            .all(|offset| self.solid_at(bases, offset))
+    }
+
+    /// Replaces bases none of whose k-mers are solid, when exactly one
+    /// alternative base makes all of them solid. Ambiguous bases are resolved
+    /// the same way. Returns the number of bases changed.
+    pub fn correct_substitutions(&self, read: &mut SequenceRecord) -> usize {
+        if read.len() < self.kmer_length {
+            return 0;
+        }
+
+        let mut corrected = 0;
+        for position in 0..read.len() {
+            if self.any_solid(&read.bases, position, position + 1) {
+                continue;
+            }
+            let original = read.bases[position];
+            let mut candidates = Vec::new();
+            for base in *b"ACGT" {
+                if base == original {
+                    continue;
+                }
+                read.bases[position] = base;
+                if self.all_solid(&read.bases, position, position + 1) {
+                    candidates.push(base);
+                }
+            }
+            read.bases[position] = match candidates[..] {
+                [base] => {
+                    corrected += 1;
+                    base
+                }
+                _ => original,
+            };
+        }
+        corrected
+    }
+
+    /// Lengthens or shortens homopolymer runs whose k-mers are not all solid,
+    /// by up to `MAX_HOMOPOLYMER_SHIFT` bases, when exactly one run length
+    /// makes them all solid. Added bases copy the quality of the run's last
+    /// base. Returns the number of runs changed.
+    pub fn correct_homopolymers(&self, read: &mut SequenceRecord) -> usize {
+        if read.len() < self.kmer_length {
+            return 0;
+        }
+
+        let mut corrected = 0;
+        let mut start = 0;
+        while start < read.len() {
+            let base = read.bases[start];
+            let mut end = start
+                + read.bases[start..]
+                    .iter()
+                    .take_while(|&&next| next == base)
+                    .count();
+            if b"ACGT".contains(&base) && !self.all_solid(&read.bases, start, end) {
+                let length = end - start;
+                let candidates: Vec<usize> = (length.saturating_sub(MAX_HOMOPOLYMER_SHIFT).max(1)
+                    ..=length + MAX_HOMOPOLYMER_SHIFT)
+                    .filter(|&candidate| candidate != length)
+                    .filter(|&candidate| {
+                        let mut bases = read.bases.clone();
+                        bases.splice(start..end, iter::repeat_n(base, candidate));
+                        bases.len() >= self.kmer_length
+                            && self.all_solid(&bases, start, start + candidate)
+                    })
+                    .collect();
+                if let [candidate] = candidates[..] {
+                    resize_run(read, start, end, candidate);
+                    end = start + candidate;
+                    corrected += 1;
+                }
+            }
+            start = end;
+        }
+        corrected
+    }
+
+    /// Accepts a corrected read if it changed at most `max_rate` of the
+    /// original bases and has no fewer solid k-mers than before.
+    pub fn verify(
+        &self,
+        original: &SequenceRecord,
+        corrected: &SequenceRecord,
+        edits: usize,
+        max_rate: f32,
+    ) -> bool {
+        edits as f32 <= max_rate * original.len() as f32
+            && self.solid_kmers(&corrected.bases) >= self.solid_kmers(&original.bases)
+    }
+}
+
+fn resize_run(read: &mut SequenceRecord, start: usize, end: usize, length: usize) {
+    let base = read.bases[start];
+    read.bases.splice(start..end, iter::repeat_n(base, length));
+    if let Some(qualities) = read.qualities.as_mut() {
+        let fill = qualities[end - 1];
+        let run: Vec<u8> = qualities[start..end]
+            .iter()
+            .copied()
+            .chain(iter::repeat(fill))
+            .take(length)
+            .collect();
+        qualities.splice(start..end, run);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::{AssemblyMode, ProcessingConfig, SequenceProcessor};
+
+    /// 120 pseudo-random bases around a run of five `A`s at 60..65.
+    fn genome() -> Vec<u8> {
+        let mut state = 0x9e37_79b9_u64;
+        let mut random = (0..113).map(|_| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            b"ACGT"[(state >> 62) as usize]
+        });
+        let mut genome: Vec<u8> = random.by_ref().take(59).collect();
+        genome.extend_from_slice(b"CAAAAAG");
+        genome.extend(random.take(54));
+        genome
+    }
+
+    fn read(bases: &[u8]) -> SequenceRecord {
+        SequenceRecord {
+            name: String::new(),
+            description: None,
+            bases: bases.to_vec(),
+            qualities: Some(vec![30; bases.len()]),
+        }
+    }
+
+    fn spectrum(genome: &[u8]) -> KmerSpectrum {
+        let reads: Vec<SequenceRecord> = (0..=genome.len() - 40)
+            .step_by(4)
+            .map(|start| read(&genome[start..start + 40]))
+            .collect();
+        KmerSpectrum::from_reads(&reads, 15, 2).unwrap()
+    }
+
+    #[test]
+    fn test_substitution_correction() {
+        let genome = genome();
+        let spectrum = spectrum(&genome);
+        let truth = read(&genome[20..60]);
+
+        let mut erroneous = truth.clone();
+        erroneous.bases[17] = if erroneous.bases[17] == b'T' {
+            b'G'
+        } else {
+            b'T'
+        };
+        erroneous.bases[33] = b'N';
+        let original = erroneous.clone();
+        assert_eq!(spectrum.correct_substitutions(&mut erroneous), 2);
+        assert_eq!(erroneous, truth);
+        assert!(spectrum.verify(&original, &erroneous, 2, 0.05));
+        assert!(!spectrum.verify(&original, &erroneous, 2, 0.01));
+
+        let mut clean = truth.clone();
+        assert_eq!(spectrum.correct_substitutions(&mut clean), 0);
+        let mut short = read(&genome[..10]);
+        assert_eq!(spectrum.correct_substitutions(&mut short), 0);
+
+        assert_eq!(
+            KmerSpectrum::from_reads(&[], 40, 2).err(),
+            Some(ProcessingError::InvalidSeedLength(40))
+        );
+    }
+
+    #[test]
+    fn test_homopolymer_correction() {
+        let genome = genome();
+        let spectrum = spectrum(&genome);
+        let truth = read(&genome[40..90]);
+
+        let mut deletion = read(&[&genome[40..60], &genome[61..90]].concat());
+        assert_eq!(spectrum.correct_homopolymers(&mut deletion), 1);
+        assert_eq!(deletion, truth);
+
+        let mut insertion = read(&[&genome[40..62], b"AA", &genome[62..90]].concat());
+        insertion.qualities.as_mut().unwrap()[23] = 7;
+        assert_eq!(spectrum.correct_homopolymers(&mut insertion), 1);
+        assert_eq!(insertion.bases, truth.bases);
+        assert_eq!(insertion.qualities.as_ref().map(Vec::len), Some(50));
+
+        // A substitution is not a run-length error and is left alone.
+        let mut substitution = truth.clone();
+        substitution.bases[5] = if substitution.bases[5] == b'T' {
+            b'G'
+        } else {
+            b'T'
+        };
+        let before = substitution.clone();
+        assert_eq!(spectrum.correct_homopolymers(&mut substitution), 0);
+        assert_eq!(substitution, before);
+    }
+
+    #[test]
+    fn test_reads_corrected_before_assembly() {
+        let genome = genome();
+        let mut reads: Vec<SequenceRecord> = (0..=genome.len() - 40)
+            .step_by(4)
+            .map(|start| read(&genome[start..start + 40]))
+            .collect();
+        let mut erroneous = read(&genome[30..70]);
+        erroneous.bases[20] = if erroneous.bases[20] == b'T' {
+            b'G'
+        } else {
+            b'T'
+        };
+        reads.push(erroneous);
+
+        let config = ProcessingConfig {
+            assembly_mode: AssemblyMode::DeNovo,
+            kmer_length: 15,
+            min_kmer_count: 1,
+            seed_length: 11,
+            correction_kmer_length: 15,
+            min_solid_kmer_count: 2,
+            ..ProcessingConfig::default()
+        };
+        let corrected = SequenceProcessor::new(config.clone())
+            .process_records(reads.clone())
+            .unwrap();
+        assert_eq!(
+            corrected.processing_log[1],
+            "substitution correction changed 1 bases in 1 reads"
+        );
+        assert_eq!(
+            corrected.processing_log[2],
+            "correction verification kept 1 and reverted 0 corrected reads"
+        );
+        assert_eq!(corrected.sequence.to_ascii(), genome);
+
+        let uncorrected = SequenceProcessor::new(ProcessingConfig {
+            error_correction: CorrectionStrategy::Disabled,
+            ..config
+        })
+        .process_records(reads)
+        .unwrap();
+        assert_eq!(uncorrected.processing_log[1], "error correction disabled");
+        assert!(uncorrected.sequence.ambiguous_bases() > 0);
+    }
+}
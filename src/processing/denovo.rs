@@ -0,0 +1,318 @@
+use crate::processing::assembly::{kmers, reverse_complement};
+use crate::processing::{ProcessingError, SequenceRecord};
+use std::collections::{HashMap, HashSet};
+
+pub const DEFAULT_KMER_LENGTH: usize = 31;
+pub const DEFAULT_MIN_KMER_COUNT: u32 = 2;
+const MAX_KMER_LENGTH: usize = 32;
+const CONTIG_GAP: usize = 100; // `N`s placed between contigs in the scaffold
+
+/// Summary of a de novo assembly. `branch_points` counts k-mers with more
+/// than one successor over both strands, so every unresolved bubble or repeat
+/// shows up at least twice.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeNovoReport {
+    pub contigs: usize,
+    pub total_length: u64,
+    pub longest_contig: u64,
+    pub n50: u64,
+    pub branch_points: usize,
+}
+
+/// Length `L` such that contigs of length `L` or more hold at least half of
+/// the assembled bases.
+pub fn n50(lengths: &[u64]) -> u64 {
+    let mut sorted = lengths.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    let total: u64 = sorted.iter().sum();
+    let mut running = 0;
+    for length in sorted {
+        running += length;
+        if 2 * running >= total {
+            return length;
+        }
+    }
+    0
+}
+
+/// De Bruijn graph over the k-mers of both read strands. K-mers seen fewer
+/// than `min_count` times are treated as sequencing errors and left out.
+pub struct DeBruijnGraph {
+    kmer_length: usize,
+    mask: u64,
+    counts: HashMap<u64, u32>,
+}
+
+impl DeBruijnGraph {
+    pub fn from_reads(
+        reads: &[SequenceRecord],
+        kmer_length: usize,
+        min_count: u32,
+    ) -> Result<Self, ProcessingError> {
+        if !(2..=MAX_KMER_LENGTH).contains(&kmer_length) {
+            return Err(ProcessingError::InvalidSeedLength(kmer_length));
+        }
+
+        let mut counts: HashMap<u64, u32> = HashMap::new();
+        for read in reads {
+            for strand in [read.bases.clone(), reverse_complement(&read.bases)] {
+                for (_, code) in kmers(&strand, kmer_length) {
+                    *counts.entry(code).or_default() += 1;
+                }
+            }
+        }
+        counts.retain(|_, count| *count >= min_count);
+
+        Ok(Self {
+            kmer_length,
+            mask: if kmer_length == MAX_KMER_LENGTH {
+                u64::MAX
+            } else {
+                (1 << (2 * kmer_length)) - 1
+            },
+            counts,
+        })
+    }
+
+    pub fn kmer_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    fn successors(&self, code: u64) -> Vec<u64> {
+        (0..4)
+            .map(|base| ((code << 2) | base) & self.mask)
+            .filter(|next| self.counts.contains_key(next))
+            .collect()
+    }
+
+    fn predecessors(&self, code: u64) -> Vec<u64> {
+        (0..4)
+            .map(|base| (code >> 2) | (base << (2 * (self.kmer_length - 1))))
+            .filter(|previous| self.counts.contains_key(previous))
+            .collect()
+    }
+
+    fn reverse_complement(&self, mut code: u64) -> u64 {
+        let mut reverse = 0;
+        for _ in 0..self.kmer_length {
+            reverse = (reverse << 2) | (3 - (code & 0b11));
+            code >>= 2;
+        }
+        reverse
+    }
+
+    fn decode(&self, code: u64) -> Vec<u8> {
+        (0..self.kmer_length)
+            .rev()
+            .map(|position| b"ACGT"[((code >> (2 * position)) & 0b11) as usize])
+            .collect()
+    }
+
+    /// Next k-mer along an unbranched path, if there is exactly one.
+    fn unique_successor(&self, code: u64) -> Option<u64> {
+        match self.successors(code)[..] {
+            [next] if self.predecessors(next).len() == 1 => Some(next),
+            _ => None,
+        }
+    }
+
+    fn unique_predecessor(&self, code: u64) -> Option<u64> {
+        match self.predecessors(code)[..] {
+            [previous] if self.successors(previous).len() == 1 => Some(previous),
+            _ => None,
+        }
+    }
+
+    /// Maximal unbranched paths, each reported once in whichever orientation
+    /// sorts first. K-mers are visited in sorted order so the result does not
+    /// depend on hashing.
+    pub fn unitigs(&self) -> Vec<Vec<u8>> {
+        let mut seeds: Vec<u64> = self.counts.keys().copied().collect();
+        seeds.sort_unstable();
+
+        let mut used = HashSet::new();
+        let mut unitigs = Vec::new();
+        for seed in seeds {
+            if used.contains(&seed) {
+                continue;
+            }
+            used.insert(seed);
+            used.insert(self.reverse_complement(seed));
+
+            let mut forward = Vec::new();
+            let mut current = seed;
+            while let Some(next) = self
+                .unique_successor(current)
+                .filter(|next| !used.contains(next))
+            {
+                used.insert(next);
+                used.insert(self.reverse_complement(next));
+                forward.push(next);
+                current = next;
+            }
+
+            let mut backward = Vec::new();
+            current = seed;
+            while let Some(previous) = self
+                .unique_predecessor(current)
+                .filter(|previous| !used.contains(previous))
+            {
+                used.insert(previous);
+                used.insert(self.reverse_complement(previous));
+                backward.push(previous);
+                current = previous;
+            }
+
+            let path: Vec<u64> = backward
+                .into_iter()
+                .rev()
+                .chain([seed])
+                .chain(forward)
+                .collect();
+            let mut contig = self.decode(path[0]);
+            contig.extend(
+                path[1..]
+                    .iter()
+                    .map(|&code| b"ACGT"[(code & 0b11) as usize]),
+            );
+
+            let reverse = reverse_complement(&contig);
+            unitigs.push(contig.min(reverse));
+        }
+        unitigs
+    }
+
+    pub fn branch_points(&self) -> usize {
+        self.counts
+            .keys()
+            .filter(|&&code| self.successors(code).len() > 1)
+            .count()
+    }
+}
+
+/// Assembles `reads` without a reference. Contigs are ordered longest first
+/// and joined into one scaffold with runs of `N`, so the result can still be
+/// hashed and registered as a single sequence.
+pub fn assemble_de_novo(
+    reads: &[SequenceRecord],
+    kmer_length: usize,
+    min_kmer_count: u32,
+) -> Result<(Vec<u8>, DeNovoReport), ProcessingError> {
+    let graph = DeBruijnGraph::from_reads(reads, kmer_length, min_kmer_count)?;
+    let mut contigs = graph.unitigs();
+    if contigs.is_empty() {
+        return Err(ProcessingError::EmptySequence);
+    }
+    contigs.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+
+    let lengths: Vec<u64> = contigs.iter().map(|contig| contig.len() as u64).collect();
+    let report = DeNovoReport {
+        contigs: contigs.len(),
+        total_length: lengths.iter().sum(),
+        longest_contig: lengths[0],
+        n50: n50(&lengths),
+        branch_points: graph.branch_points(),
+    };
+    let scaffold = contigs.join(&[b'N'; CONTIG_GAP][..]);
+    Ok((scaffold, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::processing::{AssemblyMode, ProcessingConfig, SequenceAssembler};
+
+    /// 200 pseudo-random bases, free of repeated 15-mers.
+    fn genome() -> Vec<u8> {
+        let mut state = 0x2545_f491_u64;
+        (0..200)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                b"ACGT"[(state >> 62) as usize]
+            })
+            .collect()
+    }
+
+    fn read(bases: &[u8]) -> SequenceRecord {
+        SequenceRecord {
+            name: String::new(),
+            description: None,
+            bases: bases.to_vec(),
+            qualities: Some(vec![35; bases.len()]),
+        }
+    }
+
+    /// Every 40-base window at a step of 8, each read once per strand.
+    fn reads(genome: &[u8]) -> Vec<SequenceRecord> {
+        (0..=genome.len() - 40)
+            .step_by(8)
+            .flat_map(|start| {
+                let bases = &genome[start..start + 40];
+                [read(bases), read(&reverse_complement(bases))]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_n50() {
+        assert_eq!(n50(&[2, 3, 4, 5, 6]), 5);
+        assert_eq!(n50(&[100]), 100);
+        assert_eq!(n50(&[]), 0);
+    }
+
+    #[test]
+    fn test_de_novo_contigs_and_error_kmers() {
+        let genome = genome();
+        let mut reads = reads(&genome);
+
+        let (scaffold, report) = assemble_de_novo(&reads, 15, 2).unwrap();
+        assert!(scaffold == genome || scaffold == reverse_complement(&genome));
+        assert_eq!(
+            (report.contigs, report.n50, report.branch_points),
+            (1, 200, 0)
+        );
+
+        // A single read with a substitution opens a bubble unless its k-mers
+        // are filtered as errors.
+        let mut erroneous = genome[80..120].to_vec();
+        erroneous[20] = if erroneous[20] == b'A' { b'C' } else { b'A' };
+        reads.push(read(&erroneous));
+        assert_eq!(assemble_de_novo(&reads, 15, 2).unwrap().0, scaffold);
+
+        let (bubbled, report) = assemble_de_novo(&reads, 15, 1).unwrap();
+        assert!(report.contigs > 1 && report.branch_points >= 2);
+        assert!(bubbled.contains(&b'N'));
+        assert_eq!(
+            report.total_length,
+            bubbled.iter().filter(|&&base| base != b'N').count() as u64
+        );
+
+        assert_eq!(
+            assemble_de_novo(&reads, 33, 2).err(),
+            Some(ProcessingError::InvalidSeedLength(33))
+        );
+        assert_eq!(
+            assemble_de_novo(&reads[..1], 15, 2).err(),
+            Some(ProcessingError::EmptySequence)
+        );
+    }
+
+    #[test]
+    fn test_assembler_de_novo_mode() {
+        let genome = genome();
+        let config = ProcessingConfig {
+            assembly_mode: AssemblyMode::DeNovo,
+            kmer_length: 15,
+            seed_length: 11,
+            ..ProcessingConfig::default()
+        };
+        let assembly = SequenceAssembler::new(&config)
+            .assemble(&reads(&genome))
+            .unwrap();
+        assert_eq!(assembly.sequence.len(), 200);
+        assert_eq!(assembly.alignments.len(), reads(&genome).len());
+        assert_eq!(assembly.de_novo.map(|report| report.contigs), Some(1));
+    }
+}
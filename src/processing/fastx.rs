@@ -0,0 +1,379 @@
+use crate::model::iupac_code;
+use flate2::read::MultiGzDecoder;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+pub const DEFAULT_PHRED_OFFSET: u8 = 33;
+const MAX_PHRED_SCORE: u8 = 93;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FastxError {
+    Io(String),
+    UnknownFormat(u8),
+    MissingHeader {
+        line: u64,
+    },
+    MissingSeparator {
+        line: u64,
+    },
+    InvalidBase {
+        line: u64,
+        byte: u8,
+    },
+    InvalidQuality {
+        line: u64,
+        byte: u8,
+    },
+    QualityLengthMismatch {
+        name: String,
+        bases: usize,
+        qualities: usize,
+    },
+    Truncated {
+        name: String,
+    },
+    MateMismatch {
+        r1: String,
+        r2: String,
+    },
+    MissingMate {
+        name: String,
+    },
+}
+
+impl From<io::Error> for FastxError {
+    fn from(e: io::Error) -> Self {
+        FastxError::Io(e.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FastxFormat {
+    Fasta,
+    Fastq,
+}
+
+/// One FASTA or FASTQ record. `bases` hold upper-case IUPAC codes and
+/// `qualities` Phred scores with the ASCII offset removed; FASTA records have
+/// no qualities.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceRecord {
+    pub name: String,
+    pub description: Option<String>,
+    pub bases: Vec<u8>,
+    pub qualities: Option<Vec<u8>>,
+}
+
+impl SequenceRecord {
+    pub fn len(&self) -> usize {
+        self.bases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bases.is_empty()
+    }
+
+    pub fn mean_quality(&self) -> Option<f32> {
+        let qualities = self
+            .qualities
+            .as_ref()
+            .filter(|qualities| !qualities.is_empty())?;
+        let total: u64 = qualities.iter().map(|&q| q as u64).sum();
+        Some(total as f32 / qualities.len() as f32)
+    }
+
+    /// Keeps `len` leading bases and their qualities.
+    pub fn truncate(&mut self, len: usize) {
+        self.bases.truncate(len);
+        if let Some(qualities) = self.qualities.as_mut() {
+            qualities.truncate(len);
+        }
+    }
+}
+
+/// Streaming FASTA/FASTQ reader. The format is taken from the first byte and
+/// gzip input is detected by its magic number, so plain and `.gz` files go
+/// through the same constructor. Multi-line FASTA is supported; FASTQ records
+/// must keep bases and qualities on one line each. The iterator stops after
+/// the first error.
+pub struct FastxReader {
+    reader: Box<dyn BufRead>,
+    format: FastxFormat,
+    phred_offset: u8,
+    line_number: u64,
+    pending_header: Option<Vec<u8>>,
+    done: bool,
+}
+
+impl FastxReader {
+    pub fn new<R: Read + 'static>(reader: R) -> Result<Self, FastxError> {
+        let mut reader: Box<dyn BufRead> = Box::new(BufReader::new(reader));
+        if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+            reader = Box::new(BufReader::new(MultiGzDecoder::new(reader)));
+        }
+
+        let format = match reader.fill_buf()?.first() {
+            Some(b'@') => FastxFormat::Fastq,
+            Some(b'>') | None => FastxFormat::Fasta,
+            Some(&byte) => return Err(FastxError::UnknownFormat(byte)),
+        };
+
+        Ok(Self {
+            reader,
+            format,
+            phred_offset: DEFAULT_PHRED_OFFSET,
+            line_number: 0,
+            pending_header: None,
+            done: false,
+        })
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, FastxError> {
+        Self::new(File::open(path)?)
+    }
+
+    /// ASCII offset of FASTQ quality characters (33 for Sanger/Illumina 1.8+).
+    pub fn with_phred_offset(mut self, phred_offset: u8) -> Self {
+        self.phred_offset = phred_offset;
+        self
+    }
+
+    pub fn format(&self) -> FastxFormat {
+        self.format
+    }
+
+    /// Next line without its terminator, or `None` at end of input.
+    fn read_line(&mut self) -> Result<Option<Vec<u8>>, FastxError> {
+        let mut line = Vec::new();
+        if self.reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        self.line_number += 1;
+        while matches!(line.last(), Some(b'\n' | b'\r')) {
+            line.pop();
+        }
+        Ok(Some(line))
+    }
+
+    /// Skips blank lines; `None` at end of input.
+    fn read_nonblank_line(&mut self) -> Result<Option<Vec<u8>>, FastxError> {
+        while let Some(line) = self.read_line()? {
+            if !line.is_empty() {
+                return Ok(Some(line));
+            }
+        }
+        Ok(None)
+    }
+
+    fn push_bases(&self, line: &[u8], bases: &mut Vec<u8>) -> Result<(), FastxError> {
+        for &byte in line {
+            let code = iupac_code(byte).ok_or(FastxError::InvalidBase {
+                line: self.line_number,
+                byte,
+            })?;
+            bases.push(code);
+        }
+        Ok(())
+    }
+
+    fn parse_header(header: &[u8]) -> (String, Option<String>) {
+        let text = String::from_utf8_lossy(&header[1..]);
+        match text.split_once(char::is_whitespace) {
+            Some((name, description)) => (name.to_string(), Some(description.trim().to_string())),
+            None => (text.trim().to_string(), None),
+        }
+    }
+
+    fn next_fasta(&mut self) -> Result<Option<SequenceRecord>, FastxError> {
+        let header = match self.pending_header.take() {
+            Some(header) => header,
+            None => match self.read_nonblank_line()? {
+                Some(line) => line,
+                None => return Ok(None),
+            },
+        };
+        if header[0] != b'>' {
+            return Err(FastxError::MissingHeader {
+                line: self.line_number,
+            });
+        }
+
+        let (name, description) = Self::parse_header(&header);
+        let mut bases = Vec::new();
+        while let Some(line) = self.read_line()? {
+            if line.first() == Some(&b'>') {
+                self.pending_header = Some(line);
+                break;
+            }
+            self.push_bases(&line, &mut bases)?;
+        }
+
+        Ok(Some(SequenceRecord {
+            name,
+            description,
+            bases,
+            qualities: None,
+        }))
+    }
+
+    fn next_fastq(&mut self) -> Result<Option<SequenceRecord>, FastxError> {
+        let header = match self.read_nonblank_line()? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        if header[0] != b'@' {
+            return Err(FastxError::MissingHeader {
+                line: self.line_number,
+            });
+        }
+        let (name, description) = Self::parse_header(&header);
+        let truncated = || FastxError::Truncated { name: name.clone() };
+
+        let sequence_line = self.read_line()?.ok_or_else(truncated)?;
+        let mut bases = Vec::with_capacity(sequence_line.len());
+        self.push_bases(&sequence_line, &mut bases)?;
+
+        let separator = self.read_line()?.ok_or_else(truncated)?;
+        if separator.first() != Some(&b'+') {
+            return Err(FastxError::MissingSeparator {
+                line: self.line_number,
+            });
+        }
+
+        let quality_line = self.read_line()?.ok_or_else(truncated)?;
+        let mut qualities = Vec::with_capacity(quality_line.len());
+        for &byte in &quality_line {
+            let score = byte
+                .checked_sub(self.phred_offset)
+                .filter(|&score| score <= MAX_PHRED_SCORE)
+                .ok_or(FastxError::InvalidQuality {
+                    line: self.line_number,
+                    byte,
+                })?;
+            qualities.push(score);
+        }
+        if qualities.len() != bases.len() {
+            return Err(FastxError::QualityLengthMismatch {
+                name,
+                bases: bases.len(),
+                qualities: qualities.len(),
+            });
+        }
+
+        Ok(Some(SequenceRecord {
+            name,
+            description,
+            bases,
+            qualities: Some(qualities),
+        }))
+    }
+}
+
+impl Iterator for FastxReader {
+    type Item = Result<SequenceRecord, FastxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let record = match self.format {
+            FastxFormat::Fasta => self.next_fasta(),
+            FastxFormat::Fastq => self.next_fastq(),
+        };
+        if !matches!(record, Ok(Some(_))) {
+            self.done = true;
+        }
+        record.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::{Cursor, Write};
+
+    const FASTQ: &[u8] = b"@read1 sample=7\nACGTN\n+\nIIII#\n@read2\nggcc\n+read2\n5555\n";
+
+    #[test]
+    fn test_fasta_and_fastq_records() {
+        let fasta = b">chr1 plasmid backbone\nACGT\nacgn\n\n>chr2\r\nRYKM\r\n";
+        let reader = FastxReader::new(Cursor::new(fasta.to_vec())).unwrap();
+        assert_eq!(reader.format(), FastxFormat::Fasta);
+        let records: Vec<_> = reader.collect::<Result<_, _>>().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].name, "chr1");
+        assert_eq!(records[0].description.as_deref(), Some("plasmid backbone"));
+        assert_eq!(records[0].bases, b"ACGTACGN".to_vec());
+        assert_eq!(records[0].qualities, None);
+        assert_eq!(records[1].bases, b"RYKM".to_vec());
+
+        let records: Vec<_> = FastxReader::new(Cursor::new(FASTQ.to_vec()))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(records[0].qualities, Some(vec![40, 40, 40, 40, 2]));
+        assert_eq!(records[0].mean_quality(), Some(32.4));
+        assert_eq!(records[1].bases, b"GGCC".to_vec());
+
+        let mut gzipped = GzEncoder::new(Vec::new(), Compression::default());
+        gzipped.write_all(FASTQ).unwrap();
+        let decoded: Vec<_> = FastxReader::new(Cursor::new(gzipped.finish().unwrap()))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(decoded, records);
+    }
+
+    #[test]
+    fn test_malformed_records() {
+        let parse = |data: &[u8]| {
+            FastxReader::new(Cursor::new(data.to_vec()))
+                .and_then(|reader| reader.collect::<Result<Vec<_>, _>>())
+        };
+
+        assert_eq!(
+            parse(b"ACGT\n").err(),
+            Some(FastxError::UnknownFormat(b'A'))
+        );
+        assert_eq!(
+            parse(b">seq\nACXT\n").err(),
+            Some(FastxError::InvalidBase {
+                line: 2,
+                byte: b'X'
+            })
+        );
+        assert_eq!(
+            parse(b"@read\nACGT\nIIII\n").err(),
+            Some(FastxError::MissingSeparator { line: 3 })
+        );
+        assert_eq!(
+            parse(b"@read\nACGT\n+\nIII\n").err(),
+            Some(FastxError::QualityLengthMismatch {
+                name: "read".to_string(),
+                bases: 4,
+                qualities: 3
+            })
+        );
+        assert_eq!(
+            parse(b"@read\nACGT\n+\n").err(),
+            Some(FastxError::Truncated {
+                name: "read".to_string()
+            })
+        );
+        assert_eq!(
+            parse(b"@read\nA\n+\n\x1f\n").err(),
+            Some(FastxError::InvalidQuality {
+                line: 4,
+                byte: 0x1f
+            })
+        );
+
+        let mut reader = FastxReader::new(Cursor::new(b"@read\nACGT\n+\n".to_vec())).unwrap();
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+}
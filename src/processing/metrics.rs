@@ -0,0 +1,274 @@
+use crate::model::{NucleotideSequence, QualityMetrics};
+use crate::processing::ProcessingError;
+
+pub const DEFAULT_BOOTSTRAP_ROUNDS: u32 = 200;
+const CONFIDENCE_LEVEL: f64 = 0.95;
+pub(crate) const GAP: u8 = b'-';
+
+/// A read projected onto assembly coordinates: one column per assembly
+/// position from `start`, `-` where the read has a deletion. Inserted read
+/// bases have no assembly column and are not represented.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlignedRead {
+    pub start: u64,
+    pub bases: Vec<u8>,
+    pub qualities: Option<Vec<u8>>, // Phred per column, 0 at deletions
+}
+
+impl AlignedRead {
+    pub fn end(&self) -> u64 {
+        self.start + self.bases.len() as u64
+    }
+}
+
+/// Sufficient statistics of one read, so bootstrap rounds only add numbers.
+#[derive(Debug, Clone, Copy, Default)]
+struct ReadTally {
+    quality_sum: u64,
+    quality_count: u64,
+    mismatches: u64,
+    columns: u64,
+    covered: u64,
+}
+
+impl ReadTally {
+    fn add(&mut self, other: &ReadTally) {
+        self.quality_sum += other.quality_sum;
+        self.quality_count += other.quality_count;
+        self.mismatches += other.mismatches;
+        self.columns += other.columns;
+        self.covered += other.covered;
+    }
+
+    /// (mean Phred, error rate, mean depth) over `sequence_len` positions.
+    fn statistics(&self, sequence_len: u64) -> [f64; 3] {
+        let mean_quality = match self.quality_count {
+            0 => 0.0,
+            count => self.quality_sum as f64 / count as f64,
+        };
+        let error_rate = match self.columns {
+            0 => 0.0,
+            columns => self.mismatches as f64 / columns as f64,
+        };
+        [
+            mean_quality,
+            error_rate,
+            self.covered as f64 / sequence_len as f64,
+        ]
+    }
+}
+
+/// Deterministic SplitMix64, so two labs bootstrapping the same reads get the
+/// same intervals.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+/// Number of aligned (non-deletion) read bases over each assembly position.
+pub fn coverage_profile(sequence_len: u64, alignments: &[AlignedRead]) -> Vec<u32> {
+    let mut depth = vec![0u32; sequence_len as usize];
+    for read in alignments {
+        for (offset, &base) in read.bases.iter().enumerate() {
+            if base == GAP {
+                continue;
+            }
+            if let Some(slot) = depth.get_mut(read.start as usize + offset) {
+                *slot += 1;
+            }
+        }
+    }
+    depth
+}
+
+/// Derives `QualityMetrics` from reads aligned to an assembled sequence.
+///
+/// `quality_score` is the mean Phred score of aligned bases, `error_rate` the
+/// fraction of aligned columns disagreeing with the assembly (deletions
+/// included), and `coverage_depth` the mean per-position depth rounded down.
+/// `confidence_intervals` holds percentile bootstrap intervals for those three
+/// values, in that order, from resampling whole reads.
+pub struct QualityMetricsEngine {
+    pub bootstrap_rounds: u32,
+    pub seed: u64,
+}
+
+impl Default for QualityMetricsEngine {
+    fn default() -> Self {
+        Self {
+            bootstrap_rounds: DEFAULT_BOOTSTRAP_ROUNDS,
+            seed: 0,
+        }
+    }
+}
+
+impl QualityMetricsEngine {
+    pub fn calculate_quality_metrics(
+        &self,
+        sequence: &NucleotideSequence,
+        alignments: &[AlignedRead],
+    ) -> Result<QualityMetrics, ProcessingError> {
+        if sequence.is_empty() {
+            return Err(ProcessingError::EmptySequence);
+        }
+        let assembly = sequence.to_ascii();
+        let tallies: Vec<ReadTally> = alignments
+            .iter()
+            .map(|read| Self::tally(&assembly, read))
+            .collect();
+
+        let mut total = ReadTally::default();
+        tallies.iter().for_each(|tally| total.add(tally));
+        let [quality_score, error_rate, mean_depth] = total.statistics(sequence.len());
+
+        Ok(QualityMetrics {
+            coverage_depth: mean_depth as u32,
+            quality_score: quality_score as f32,
+            error_rate: error_rate as f32,
+            confidence_intervals: self.bootstrap(&tallies, sequence.len()),
+            insert_size: None,
+        })
+    }
+
+    fn tally(assembly: &[u8], read: &AlignedRead) -> ReadTally {
+        let mut tally = ReadTally::default();
+        for (offset, &base) in read.bases.iter().enumerate() {
+            let Some(&expected) = assembly.get(read.start as usize + offset) else {
+                break;
+            };
+            tally.columns += 1;
+            if base != expected {
+                tally.mismatches += 1;
+            }
+            if base == GAP {
+                continue;
+            }
+            tally.covered += 1;
+            if let Some(quality) = read
+                .qualities
+                .as_ref()
+                .and_then(|qualities| qualities.get(offset))
+            {
+                tally.quality_sum += *quality as u64;
+                tally.quality_count += 1;
+            }
+        }
+        tally
+    }
+
+    fn bootstrap(&self, tallies: &[ReadTally], sequence_len: u64) -> Vec<(f32, f32)> {
+        if tallies.is_empty() || self.bootstrap_rounds == 0 {
+            return Vec::new();
+        }
+
+        let mut rng = SplitMix64(self.seed);
+        let mut samples: [Vec<f64>; 3] = Default::default();
+        for _ in 0..self.bootstrap_rounds {
+            let mut resampled = ReadTally::default();
+            for _ in 0..tallies.len() {
+                resampled.add(&tallies[rng.below(tallies.len())]);
+            }
+            for (values, statistic) in samples.iter_mut().zip(resampled.statistics(sequence_len)) {
+                values.push(statistic);
+            }
+        }
+
+        let tail = (1.0 - CONFIDENCE_LEVEL) / 2.0;
+        samples
+            .iter_mut()
+            .map(|values| {
+                values.sort_by(f64::total_cmp);
+                (
+                    percentile(values, tail) as f32,
+                    percentile(values, 1.0 - tail) as f32,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Nearest-rank percentile of sorted `values`.
+fn percentile(values: &[f64], fraction: f64) -> f64 {
+    let rank = (fraction * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn aligned(start: u64, bases: &[u8], quality: u8) -> AlignedRead {
+        AlignedRead {
+            start,
+            bases: bases.to_vec(),
+            qualities: Some(vec![quality; bases.len()]),
+        }
+    }
+
+    #[test]
+    fn test_quality_metrics_from_alignments() {
+        let sequence = NucleotideSequence::from_ascii(b"ACGTACGTAC").unwrap();
+        let alignments = vec![
+            aligned(0, b"ACGTACGTAC", 40),
+            aligned(0, b"ACGTTCGTAC", 30), // one substitution
+            aligned(5, b"CG-AC", 20),      // one deletion
+            aligned(8, b"ACGG", 20),       // runs off the end
+        ];
+
+        assert_eq!(
+            coverage_profile(10, &alignments),
+            vec![2, 2, 2, 2, 2, 3, 3, 2, 4, 4]
+        );
+
+        let engine = QualityMetricsEngine::default();
+        let metrics = engine
+            .calculate_quality_metrics(&sequence, &alignments)
+            .unwrap();
+        assert_eq!(metrics.coverage_depth, 2); // 26 bases over 10 positions
+        assert_eq!(metrics.error_rate, 2.0 / 27.0);
+        assert_eq!(
+            metrics.quality_score,
+            (400 + 300 + 4 * 20 + 2 * 20) as f32 / 26.0
+        );
+
+        assert_eq!(metrics.confidence_intervals.len(), 3);
+        for (&(low, high), value) in metrics.confidence_intervals.iter().zip([
+            metrics.quality_score,
+            metrics.error_rate,
+            2.6,
+        ]) {
+            assert!(
+                low <= value && value <= high,
+                "{} outside ({}, {})",
+                value,
+                low,
+                high
+            );
+        }
+        assert_eq!(
+            engine
+                .calculate_quality_metrics(&sequence, &alignments)
+                .unwrap(),
+            metrics
+        );
+
+        let unsupported = engine.calculate_quality_metrics(&sequence, &[]).unwrap();
+        assert_eq!(unsupported.coverage_depth, 0);
+        assert!(unsupported.confidence_intervals.is_empty());
+        assert_eq!(
+            engine.calculate_quality_metrics(&NucleotideSequence::new(), &alignments),
+            Err(ProcessingError::EmptySequence)
+        );
+    }
+}
@@ -0,0 +1,649 @@
+pub mod adapters;
+pub mod assembly;
+pub mod correction;
+pub mod denovo;
+pub mod fastx;
+pub mod metrics;
+pub mod paired;
+pub mod profile;
+
+use crate::model::{EncodingError, NucleotideSequence, QualityMetrics, SequencerModel};
+use std::io::Cursor;
+
+pub use adapters::{
+    Adapter, AdapterEnd, AdapterReport, AdapterSet, AdapterTrimmer, DEFAULT_MAX_ADAPTER_ERROR_RATE,
+    DEFAULT_MIN_ADAPTER_OVERLAP,
+};
+pub use assembly::{
+    call_consensus, Assembly, PairPlacement, ReadMapper, ReadMapping, ReferenceIndex,
+    SequenceAssembler, DEFAULT_BAND_WIDTH, DEFAULT_MAX_INSERT_SIZE, DEFAULT_SEED_LENGTH,
+};
+pub use correction::{
+    CorrectionStrategy, KmerSpectrum, DEFAULT_CORRECTION_KMER_LENGTH, DEFAULT_MAX_CORRECTION_RATE,
+    DEFAULT_MIN_SOLID_COUNT,
+};
+pub use denovo::{
+    assemble_de_novo, n50, DeBruijnGraph, DeNovoReport, DEFAULT_KMER_LENGTH, DEFAULT_MIN_KMER_COUNT,
+};
+pub use fastx::{FastxError, FastxFormat, FastxReader, SequenceRecord, DEFAULT_PHRED_OFFSET};
+pub use metrics::{coverage_profile, AlignedRead, QualityMetricsEngine, DEFAULT_BOOTSTRAP_ROUNDS};
+pub use paired::{insert_size_stats, mate_name, PairedReader, ReadPair};
+pub use profile::{QualityProfile, GENOME_ANALYZER_II, MINION, NOVASEQ_6000};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AssemblyMode {
+    /// Map reads to the configured reference, or to the longest read.
+    #[default]
+    ReferenceGuided,
+    /// Build contigs from a de Bruijn graph of the reads; any reference is ignored.
+    DeNovo,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcessingConfig {
+    pub min_base_quality: u8,  // Phred score below which 3' tails are trimmed
+    pub min_mean_quality: f32, // Reads averaging below this Phred score are dropped
+    pub bootstrap_rounds: u32, // Resamples behind the metrics' confidence intervals
+    pub bootstrap_seed: u64,
+    pub seed_length: usize, // k-mer length used to seed read mapping (1..=32)
+    pub band_width: usize,  // Diagonals either side of the seed searched during alignment
+    pub min_identity: f32,  // Mapped reads aligning below this identity are discarded
+    pub min_consensus_depth: u32, // Assembly positions with fewer reads are called `N`
+    pub assembly_mode: AssemblyMode,
+    pub kmer_length: usize, // De novo graph k-mer length (2..=32, odd avoids palindromes)
+    pub min_kmer_count: u32, // De novo k-mers seen fewer times are dropped as errors
+    pub error_correction: CorrectionStrategy,
+    pub correction_kmer_length: usize, // k-mer length of the correction spectrum (2..=32)
+    pub min_solid_kmer_count: u32,     // Occurrences for a k-mer to count as error-free
+    pub max_correction_rate: f32,      // Reads needing more edits per base are left uncorrected
+    pub phred_offset: u8,              // ASCII offset of FASTQ quality characters
+    pub max_insert_size: u64,          // Mates placed farther apart are discordant
+    pub adapters: Vec<Adapter>,        // Trimmed before quality filtering; none by default
+    pub min_adapter_overlap: usize,    // Shortest adapter fragment trimmed at a read end
+    pub max_adapter_error_rate: f32,   // Mismatches allowed per aligned adapter base
+}
+
+impl Default for ProcessingConfig {
+    fn default() -> Self {
+        Self {
+            min_base_quality: 20,
+            min_mean_quality: 20.0,
+            bootstrap_rounds: DEFAULT_BOOTSTRAP_ROUNDS,
+            bootstrap_seed: 0,
+            seed_length: DEFAULT_SEED_LENGTH,
+            band_width: DEFAULT_BAND_WIDTH,
+            min_identity: 0.9,
+            min_consensus_depth: 1,
+            assembly_mode: AssemblyMode::ReferenceGuided,
+            kmer_length: DEFAULT_KMER_LENGTH,
+            min_kmer_count: DEFAULT_MIN_KMER_COUNT,
+            error_correction: CorrectionStrategy::KmerSpectrum,
+            correction_kmer_length: DEFAULT_CORRECTION_KMER_LENGTH,
+            min_solid_kmer_count: DEFAULT_MIN_SOLID_COUNT,
+            max_correction_rate: DEFAULT_MAX_CORRECTION_RATE,
+            phred_offset: DEFAULT_PHRED_OFFSET,
+            max_insert_size: DEFAULT_MAX_INSERT_SIZE,
+            adapters: Vec::new(),
+            min_adapter_overlap: DEFAULT_MIN_ADAPTER_OVERLAP,
+            max_adapter_error_rate: DEFAULT_MAX_ADAPTER_ERROR_RATE,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ProcessingError {
+    InsufficientCoverage,
+    HighErrorRate,
+    EmptySequence,
+    NoReadsMapped,
+    InvalidSeedLength(usize),
+    UnknownSequencer(String),
+    Encoding(EncodingError),
+    Fastx(FastxError),
+}
+
+impl From<EncodingError> for ProcessingError {
+    fn from(e: EncodingError) -> Self {
+        ProcessingError::Encoding(e)
+    }
+}
+
+impl From<FastxError> for ProcessingError {
+    fn from(e: FastxError) -> Self {
+        ProcessingError::Fastx(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityChecker {
+    pub minimum_coverage: u32,
+    pub maximum_error_rate: f32,
+}
+
+impl QualityChecker {
+    pub fn check_quality(&self, metrics: &QualityMetrics) -> Result<(), ProcessingError> {
+        if metrics.coverage_depth < self.minimum_coverage {
+            return Err(ProcessingError::InsufficientCoverage);
+        }
+        if metrics.error_rate > self.maximum_error_rate {
+            return Err(ProcessingError::HighErrorRate);
+        }
+        Ok(())
+    }
+}
+
+pub struct QualityControl {
+    min_base_quality: u8,
+    min_mean_quality: f32,
+    adapter_trimmer: AdapterTrimmer,
+}
+
+impl QualityControl {
+    pub fn new(config: &ProcessingConfig) -> Self {
+        Self {
+            min_base_quality: config.min_base_quality,
+            min_mean_quality: config.min_mean_quality,
+            adapter_trimmer: AdapterTrimmer::new(
+                config.adapters.clone(),
+                config.min_adapter_overlap,
+                config.max_adapter_error_rate,
+            ),
+        }
+    }
+
+    pub fn trims_adapters(&self) -> bool {
+        !self.adapter_trimmer.is_empty()
+    }
+
+    /// Trims configured adapters and each read's low-quality 3' tail, then
+    /// drops reads that end up empty or whose mean quality is below the
+    /// configured minimum. Reads without qualities (FASTA) are only adapter
+    /// trimmed. Trimmed adapters are tallied in `adapters`.
+    pub fn filter(
+        &self,
+        records: Vec<SequenceRecord>,
+        adapters: &mut AdapterReport,
+    ) -> Result<Vec<SequenceRecord>, ProcessingError> {
+        Ok(records
+            .into_iter()
+            .filter_map(|record| self.filter_record(record, adapters))
+            .collect())
+    }
+
+    /// Filters each mate as `filter` does. A pair is kept when both mates
+    /// pass; a mate whose partner fails is returned as an orphan, to be
+    /// assembled as a single read.
+    pub fn filter_pairs(
+        &self,
+        pairs: Vec<ReadPair>,
+        adapters: &mut AdapterReport,
+    ) -> Result<(Vec<ReadPair>, Vec<SequenceRecord>), ProcessingError> {
+        let (mut kept, mut orphans) = (Vec::new(), Vec::new());
+        for pair in pairs {
+            match (
+                self.filter_record(pair.r1, adapters),
+                self.filter_record(pair.r2, adapters),
+            ) {
+                (Some(r1), Some(r2)) => kept.push(ReadPair { r1, r2 }),
+                (Some(orphan), None) | (None, Some(orphan)) => orphans.push(orphan),
+                (None, None) => {}
+            }
+        }
+        Ok((kept, orphans))
+    }
+
+    fn filter_record(
+        &self,
+        mut record: SequenceRecord,
+        adapters: &mut AdapterReport,
+    ) -> Option<SequenceRecord> {
+        self.adapter_trimmer.trim(&mut record, adapters);
+        if let Some(qualities) = &record.qualities {
+            let keep = qualities
+                .iter()
+                .rposition(|&quality| quality >= self.min_base_quality)
+                .map_or(0, |last| last + 1);
+            record.truncate(keep);
+        }
+        if record.is_empty() {
+            return None;
+        }
+        match record.mean_quality() {
+            Some(mean) if mean < self.min_mean_quality => None,
+            _ => Some(record),
+        }
+    }
+}
+
+pub struct ProcessedSequence {
+    pub sequence: NucleotideSequence,
+    pub metrics: QualityMetrics,
+    pub processing_log: Vec<String>,
+}
+
+pub struct SequenceProcessor {
+    config: ProcessingConfig,
+    quality_control: QualityControl,
+    assembler: SequenceAssembler,
+    metrics_engine: QualityMetricsEngine,
+    quality_checker: Option<QualityChecker>,
+}
+
+impl SequenceProcessor {
+    pub fn new(config: ProcessingConfig) -> Self {
+        Self {
+            quality_control: QualityControl::new(&config),
+            assembler: SequenceAssembler::new(&config),
+            metrics_engine: QualityMetricsEngine {
+                bootstrap_rounds: config.bootstrap_rounds,
+                seed: config.bootstrap_seed,
+            },
+            quality_checker: None,
+            config,
+        }
+    }
+
+    /// Processor configured by the `QualityProfile` matching `sequencer`,
+    /// rejecting results that miss the profile's thresholds.
+    pub fn for_sequencer(sequencer: &SequencerModel) -> Result<Self, ProcessingError> {
+        let profile = QualityProfile::for_sequencer(sequencer).ok_or_else(|| {
+            ProcessingError::UnknownSequencer(format!(
+                "{} {}",
+                sequencer.manufacturer, sequencer.model
+            ))
+        })?;
+        Ok(Self::new(profile.processing_config()).with_quality_checker(profile.checker()))
+    }
+
+    /// Fails processing when the final metrics do not pass `checker`.
+    pub fn with_quality_checker(mut self, checker: QualityChecker) -> Self {
+        self.quality_checker = Some(checker);
+        self
+    }
+
+    /// Assembles against `reference` instead of the longest read.
+    pub fn with_reference(
+        mut self,
+        reference: &NucleotideSequence,
+    ) -> Result<Self, ProcessingError> {
+        self.assembler = self.assembler.with_reference(reference)?;
+        Ok(self)
+    }
+
+    pub fn config(&self) -> &ProcessingConfig {
+        &self.config
+    }
+
+    /// Processes an in-memory FASTA or FASTQ file, optionally gzipped.
+    pub fn process_sequence(
+        &self,
+        raw_data: Vec<u8>,
+    ) -> Result<ProcessedSequence, ProcessingError> {
+        self.process_reader(self.fastx_reader(raw_data)?)
+    }
+
+    /// Processes in-memory R1 and R2 files of a paired-end run.
+    pub fn process_paired(
+        &self,
+        r1: Vec<u8>,
+        r2: Vec<u8>,
+    ) -> Result<ProcessedSequence, ProcessingError> {
+        let pairs = PairedReader::new(self.fastx_reader(r1)?, self.fastx_reader(r2)?)
+            .collect::<Result<Vec<_>, _>>()?;
+        self.process_pairs(pairs)
+    }
+
+    fn fastx_reader(&self, raw_data: Vec<u8>) -> Result<FastxReader, ProcessingError> {
+        Ok(FastxReader::new(Cursor::new(raw_data))?.with_phred_offset(self.config.phred_offset))
+    }
+
+    pub fn process_reader(
+        &self,
+        reader: FastxReader,
+    ) -> Result<ProcessedSequence, ProcessingError> {
+        let records = reader.collect::<Result<Vec<_>, _>>()?;
+        self.process_records(records)
+    }
+
+    pub fn process_records(
+        &self,
+        records: Vec<SequenceRecord>,
+    ) -> Result<ProcessedSequence, ProcessingError> {
+        let mut processing_log = Vec::new();
+
+        let (raw_reads, raw_bases) = (records.len(), total_bases(&records));
+        let mut adapters = AdapterReport::default();
+        let filtered_reads = self.quality_control.filter(records, &mut adapters)?;
+        self.log_adapters(&adapters, &mut processing_log);
+        processing_log.push(format!(
+            "quality control kept {} of {} reads ({} of {} bases)",
+            filtered_reads.len(),
+            raw_reads,
+            total_bases(&filtered_reads),
+            raw_bases
+        ));
+
+        self.assemble_reads(filtered_reads, &[], processing_log)
+    }
+
+    /// Like `process_records` for paired-end data. Insert sizes of properly
+    /// paired mates end up in `QualityMetrics.insert_size`.
+    pub fn process_pairs(
+        &self,
+        pairs: Vec<ReadPair>,
+    ) -> Result<ProcessedSequence, ProcessingError> {
+        let mut processing_log = Vec::new();
+
+        let raw_pairs = pairs.len();
+        let mut adapters = AdapterReport::default();
+        let (pairs, orphans) = self.quality_control.filter_pairs(pairs, &mut adapters)?;
+        self.log_adapters(&adapters, &mut processing_log);
+        processing_log.push(format!(
+            "quality control kept {} of {} pairs and {} orphaned mates",
+            pairs.len(),
+            raw_pairs,
+            orphans.len()
+        ));
+
+        let mates: Vec<(usize, usize)> = (0..pairs.len())
+            .map(|pair| (2 * pair, 2 * pair + 1))
+            .collect();
+        let reads = pairs
+            .into_iter()
+            .flat_map(|pair| [pair.r1, pair.r2])
+            .chain(orphans)
+            .collect();
+        self.assemble_reads(reads, &mates, processing_log)
+    }
+
+    fn log_adapters(&self, adapters: &AdapterReport, processing_log: &mut Vec<String>) {
+        if !self.quality_control.trims_adapters() {
+            return;
+        }
+        let mut line = format!(
+            "adapter trimming removed {} bases from {} reads",
+            adapters.bases_removed, adapters.reads_trimmed
+        );
+        if !adapters.by_adapter.is_empty() {
+            line += &format!(" ({})", adapters.summary());
+        }
+        processing_log.push(line);
+    }
+
+    /// Corrects, assembles and measures filtered reads. `mates` pair up
+    /// indices into `reads`.
+    fn assemble_reads(
+        &self,
+        reads: Vec<SequenceRecord>,
+        mates: &[(usize, usize)],
+        mut processing_log: Vec<String>,
+    ) -> Result<ProcessedSequence, ProcessingError> {
+        let filtered_reads = self.error_correction(reads, &mut processing_log)?;
+        let assembly = self.assembler.assemble_pairs(&filtered_reads, mates)?;
+        processing_log.push(format!(
+            "assembled {} bases, placed {} of {} reads",
+            assembly.sequence.len(),
+            assembly.alignments.len(),
+            filtered_reads.len()
+        ));
+        if let Some(report) = &assembly.de_novo {
+            processing_log.push(format!(
+                "de novo: {} contigs, N50 {}, longest {}, {} branch points, {} ambiguous bases",
+                report.contigs,
+                report.n50,
+                report.longest_contig,
+                report.branch_points,
+                assembly.sequence.ambiguous_bases()
+            ));
+        }
+
+        let mut metrics = self.calculate_metrics(&assembly.sequence, &assembly.alignments)?;
+        if let Some(pairs) = &assembly.pairs {
+            metrics.insert_size = insert_size_stats(&pairs.insert_sizes);
+            processing_log.push(match &metrics.insert_size {
+                Some(insert_size) => format!(
+                    "paired-end: {} proper pairs, {} discordant, insert size median {}, mean {:.1}, sd {:.1}",
+                    insert_size.proper_pairs, pairs.discordant_pairs, insert_size.median, insert_size.mean, insert_size.std_dev
+                ),
+                None => format!("paired-end: no proper pairs, {} discordant", pairs.discordant_pairs),
+            });
+        }
+        if let Some(checker) = &self.quality_checker {
+            checker.check_quality(&metrics)?;
+            processing_log.push(format!(
+                "quality check passed: coverage {} >= {}, error rate {} <= {}",
+                metrics.coverage_depth,
+                checker.minimum_coverage,
+                metrics.error_rate,
+                checker.maximum_error_rate
+            ));
+        }
+
+        Ok(ProcessedSequence {
+            sequence: assembly.sequence,
+            metrics,
+            processing_log,
+        })
+    }
+
+    /// Corrects reads against their own k-mer spectrum before assembly. Each
+    /// read's corrections are kept only if `verify_corrections` accepts them.
+    fn error_correction(
+        &self,
+        reads: Vec<SequenceRecord>,
+        processing_log: &mut Vec<String>,
+    ) -> Result<Vec<SequenceRecord>, ProcessingError> {
+        let strategy = self.config.error_correction;
+        if strategy == CorrectionStrategy::Disabled {
+            processing_log.push("error correction disabled".to_string());
+            return Ok(reads);
+        }
+
+        let spectrum = KmerSpectrum::from_reads(
+            &reads,
+            self.config.correction_kmer_length,
+            self.config.min_solid_kmer_count,
+        )?;
+        let mut corrected = reads.clone();
+        let mut edits = vec![0; reads.len()];
+        if strategy.corrects_substitutions() {
+            self.correct_substitutions(&spectrum, &mut corrected, &mut edits, processing_log);
+        }
+        if strategy.corrects_indels() {
+            self.correct_indels(&spectrum, &mut corrected, &mut edits, processing_log);
+        }
+        Ok(self.verify_corrections(&spectrum, reads, corrected, &edits, processing_log))
+    }
+
+    fn correct_substitutions(
+        &self,
+        spectrum: &KmerSpectrum,
+        reads: &mut [SequenceRecord],
+        edits: &mut [usize],
+        processing_log: &mut Vec<String>,
+    ) {
+        let (mut bases, mut corrected_reads) = (0, 0);
+        for (read, read_edits) in reads.iter_mut().zip(edits.iter_mut()) {
+            let changed = spectrum.correct_substitutions(read);
+            if changed > 0 {
+                bases += changed;
+                corrected_reads += 1;
+                *read_edits += changed;
+            }
+        }
+        processing_log.push(format!(
+            "substitution correction changed {} bases in {} reads",
+            bases, corrected_reads
+        ));
+    }
+
+    fn correct_indels(
+        &self,
+        spectrum: &KmerSpectrum,
+        reads: &mut [SequenceRecord],
+        edits: &mut [usize],
+        processing_log: &mut Vec<String>,
+    ) {
+        let (mut runs, mut corrected_reads) = (0, 0);
+        for (read, read_edits) in reads.iter_mut().zip(edits.iter_mut()) {
+            let changed = spectrum.correct_homopolymers(read);
+            if changed > 0 {
+                runs += changed;
+                corrected_reads += 1;
+                *read_edits += changed;
+            }
+        }
+        processing_log.push(format!(
+            "indel correction resized {} homopolymer runs in {} reads",
+            runs, corrected_reads
+        ));
+    }
+
+    /// Falls back to the original read wherever the corrected one fails
+    /// `KmerSpectrum::verify`.
+    fn verify_corrections(
+        &self,
+        spectrum: &KmerSpectrum,
+        original: Vec<SequenceRecord>,
+        corrected: Vec<SequenceRecord>,
+        edits: &[usize],
+        processing_log: &mut Vec<String>,
+    ) -> Vec<SequenceRecord> {
+        let (mut kept, mut reverted) = (0, 0);
+        let reads = original
+            .into_iter()
+            .zip(corrected)
+            .zip(edits)
+            .map(|((original, corrected), &read_edits)| {
+                if read_edits == 0 {
+                    original
+                } else if spectrum.verify(
+                    &original,
+                    &corrected,
+                    read_edits,
+                    self.config.max_correction_rate,
+                ) {
+                    kept += 1;
+                    corrected
+                } else {
+                    reverted += 1;
+                    original
+                }
+            })
+            .collect();
+        processing_log.push(format!(
+            "correction verification kept {} and reverted {} corrected reads",
+            kept, reverted
+        ));
+        reads
+    }
+
+    fn calculate_metrics(
+        &self,
+        sequence: &NucleotideSequence,
+        alignments: &[AlignedRead],
+    ) -> Result<QualityMetrics, ProcessingError> {
+        self.metrics_engine
+            .calculate_quality_metrics(sequence, alignments)
+    }
+}
+
+fn total_bases(records: &[SequenceRecord]) -> usize {
+    records.iter().map(SequenceRecord::len).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generate_test_sequence() -> Vec<u8> {
+        let mut fastq = Vec::new();
+        fastq.extend_from_slice(b"@read1\nACGTNACGTGATTACA\n+\nIIIIIIIIIIIIII##\n");
+        fastq.extend_from_slice(b"@read2\nAATCACGT\n+\nIIIIIIII\n");
+        fastq.extend_from_slice(b"@read3\nACGTAC\n+\n######\n");
+        fastq.extend_from_slice(b"@read4\nGGGGGGGGGG\n+\nIIIIIIIIII\n");
+        fastq
+    }
+
+    #[test]
+    fn test_quality_checker() {
+        let metrics = QualityMetrics {
+            coverage_depth: 35,
+            quality_score: 32.5,
+            error_rate: 0.0009,
+            confidence_intervals: vec![],
+            insert_size: None,
+        };
+        let checker = QualityChecker {
+            minimum_coverage: 30,
+            maximum_error_rate: 0.001,
+        };
+        assert!(checker.check_quality(&metrics).is_ok());
+        assert_eq!(
+            checker.check_quality(&QualityMetrics {
+                coverage_depth: 10,
+                ..metrics.clone()
+            }),
+            Err(ProcessingError::InsufficientCoverage)
+        );
+        assert_eq!(
+            checker.check_quality(&QualityMetrics {
+                error_rate: 0.01,
+                ..metrics
+            }),
+            Err(ProcessingError::HighErrorRate)
+        );
+    }
+
+    #[test]
+    fn test_sequence_validation() {
+        let processor = SequenceProcessor::new(ProcessingConfig {
+            seed_length: 5,
+            ..ProcessingConfig::default()
+        });
+        let test_sequence = generate_test_sequence();
+
+        let result = processor.process_sequence(test_sequence).unwrap();
+        // read3 is all Q2 and dropped, read1 loses its Q2 tail, read2 lands
+        // reverse-complemented at position 5 and read4 fits nowhere.
+        assert_eq!(result.sequence.to_ascii(), b"ACGTNACGTGATTA");
+        assert_eq!(result.metrics.coverage_depth, 1);
+        assert_eq!(result.metrics.quality_score, 40.0);
+        assert_eq!(result.metrics.error_rate, 0.0);
+        assert_eq!(result.metrics.confidence_intervals.len(), 3);
+        assert_eq!(
+            result.processing_log[0],
+            "quality control kept 3 of 4 reads (32 of 40 bases)"
+        );
+        assert_eq!(
+            result.processing_log[1],
+            "substitution correction changed 0 bases in 0 reads"
+        );
+        assert_eq!(
+            result.processing_log[2],
+            "correction verification kept 0 and reverted 0 corrected reads"
+        );
+        assert_eq!(
+            result.processing_log[3],
+            "assembled 14 bases, placed 2 of 3 reads"
+        );
+        assert_eq!(result.processing_log.len(), 4);
+
+        let fasta = processor
+            .process_sequence(b">contig\nACGTNACGTGATTACA\n".to_vec())
+            .unwrap();
+        assert_eq!(fasta.metrics.coverage_depth, 1);
+        assert_eq!(fasta.metrics.quality_score, 0.0);
+
+        assert_eq!(
+            processor
+                .process_sequence(b"@read\nAC\n+\n##\n".to_vec())
+                .err(),
+            Some(ProcessingError::EmptySequence)
+        );
+        assert!(matches!(
+            processor.process_sequence(vec![1, 2, 3]),
+            Err(ProcessingError::Fastx(FastxError::UnknownFormat(1)))
+        ));
+    }
+}
@@ -0,0 +1,244 @@
+use crate::model::InsertSizeStats;
+use crate::processing::{FastxError, FastxReader, SequenceRecord};
+
+/// Both mates of one sequenced fragment, as read from the R1 and R2 files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadPair {
+    pub r1: SequenceRecord,
+    pub r2: SequenceRecord,
+}
+
+impl ReadPair {
+    pub fn len(&self) -> usize {
+        self.r1.len() + self.r2.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.r1.is_empty() && self.r2.is_empty()
+    }
+}
+
+/// Name shared by both mates: `name` without a trailing `/1` or `/2`.
+/// Casava 1.8+ headers carry the read number in the description, so their
+/// names already match.
+pub fn mate_name(name: &str) -> &str {
+    name.strip_suffix("/1")
+        .or_else(|| name.strip_suffix("/2"))
+        .unwrap_or(name)
+}
+
+/// Reads R1 and R2 in lockstep. Mates must appear in the same order in both
+/// files; the iterator stops after the first mismatch or error.
+pub struct PairedReader {
+    r1: FastxReader,
+    r2: FastxReader,
+    done: bool,
+}
+
+impl PairedReader {
+    pub fn new(r1: FastxReader, r2: FastxReader) -> Self {
+        Self {
+            r1,
+            r2,
+            done: false,
+        }
+    }
+
+    fn next_pair(&mut self) -> Result<Option<ReadPair>, FastxError> {
+        match (self.r1.next().transpose()?, self.r2.next().transpose()?) {
+            (None, None) => Ok(None),
+            (Some(r1), Some(r2)) if mate_name(&r1.name) == mate_name(&r2.name) => {
+                Ok(Some(ReadPair { r1, r2 }))
+            }
+            (Some(r1), Some(r2)) => Err(FastxError::MateMismatch {
+                r1: r1.name,
+                r2: r2.name,
+            }),
+            (Some(record), None) | (None, Some(record)) => {
+                Err(FastxError::MissingMate { name: record.name })
+            }
+        }
+    }
+}
+
+impl Iterator for PairedReader {
+    type Item = Result<ReadPair, FastxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let pair = self.next_pair();
+        if !matches!(pair, Ok(Some(_))) {
+            self.done = true;
+        }
+        pair.transpose()
+    }
+}
+
+/// Summary of the insert sizes of properly paired mates, `None` without any.
+pub fn insert_size_stats(insert_sizes: &[u64]) -> Option<InsertSizeStats> {
+    if insert_sizes.is_empty() {
+        return None;
+    }
+    let mut sorted = insert_sizes.to_vec();
+    sorted.sort_unstable();
+    let count = sorted.len() as f64;
+    let mean = sorted.iter().sum::<u64>() as f64 / count;
+    let variance = sorted
+        .iter()
+        .map(|&size| (size as f64 - mean).powi(2))
+        .sum::<f64>()
+        / count;
+
+    Some(InsertSizeStats {
+        proper_pairs: sorted.len() as u32,
+        median: sorted[(sorted.len() - 1) / 2] as u32,
+        mean: mean as f32,
+        std_dev: variance.sqrt() as f32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::NucleotideSequence;
+    use crate::processing::assembly::reverse_complement;
+    use crate::processing::{ProcessingConfig, SequenceProcessor};
+    use std::io::Cursor;
+
+    /// 400 pseudo-random bases.
+    fn genome() -> Vec<u8> {
+        let mut state = 0x5151_7e3d_u64;
+        (0..400)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                b"ACGT"[(state >> 62) as usize]
+            })
+            .collect()
+    }
+
+    fn read(name: &str, bases: &[u8], quality: u8) -> SequenceRecord {
+        SequenceRecord {
+            name: name.to_string(),
+            description: None,
+            bases: bases.to_vec(),
+            qualities: Some(vec![quality; bases.len()]),
+        }
+    }
+
+    /// 60-base mates from each end of 200-base fragments, R2 on the reverse
+    /// strand as a forward-reverse library reads it.
+    fn pair(genome: &[u8], start: usize) -> ReadPair {
+        let fragment = &genome[start..start + 200];
+        ReadPair {
+            r1: read(&format!("frag{}/1", start), &fragment[..60], 35),
+            r2: read(
+                &format!("frag{}/2", start),
+                &reverse_complement(&fragment[140..]),
+                35,
+            ),
+        }
+    }
+
+    #[test]
+    fn test_paired_reader() {
+        let open = |data: &[u8]| FastxReader::new(Cursor::new(data.to_vec())).unwrap();
+        let r1 = b"@a/1\nACGT\n+\nIIII\n@b/1\nGGCC\n+\nIIII\n";
+
+        let pairs: Vec<_> = PairedReader::new(
+            open(r1),
+            open(b"@a/2\nTTTT\n+\nIIII\n@b/2\nAAAA\n+\nIIII\n"),
+        )
+        .collect::<Result<_, _>>()
+        .unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert_eq!(
+            (pairs[1].r1.bases.as_slice(), pairs[1].r2.bases.as_slice()),
+            (&b"GGCC"[..], &b"AAAA"[..])
+        );
+
+        let mismatched: Result<Vec<_>, _> = PairedReader::new(
+            open(r1),
+            open(b"@a/2\nTTTT\n+\nIIII\n@c/2\nAAAA\n+\nIIII\n"),
+        )
+        .collect();
+        assert_eq!(
+            mismatched.err(),
+            Some(FastxError::MateMismatch {
+                r1: "b/1".to_string(),
+                r2: "c/2".to_string()
+            })
+        );
+        let truncated: Result<Vec<_>, _> =
+            PairedReader::new(open(r1), open(b"@a/2\nTTTT\n+\nIIII\n")).collect();
+        assert_eq!(
+            truncated.err(),
+            Some(FastxError::MissingMate {
+                name: "b/1".to_string()
+            })
+        );
+
+        assert_eq!(mate_name("read7/2"), "read7");
+        assert_eq!(
+            mate_name("A00123:8:H7:1:1101:1000:2000"),
+            "A00123:8:H7:1:1101:1000:2000"
+        );
+    }
+
+    #[test]
+    fn test_insert_size_stats() {
+        assert_eq!(insert_size_stats(&[]), None);
+        let stats = insert_size_stats(&[300, 200, 250, 250]).unwrap();
+        assert_eq!(
+            (stats.proper_pairs, stats.median, stats.mean),
+            (4, 250, 250.0)
+        );
+        assert_eq!(stats.std_dev, 1250f32.sqrt());
+    }
+
+    #[test]
+    fn test_paired_end_processing() {
+        let genome = genome();
+        let mut pairs: Vec<ReadPair> = (0..=200)
+            .step_by(5)
+            .map(|start| pair(&genome, start))
+            .collect();
+        // A chimeric pair: both mates on the forward strand.
+        let mut chimera = pair(&genome, 100);
+        chimera.r2.bases = genome[240..300].to_vec();
+        pairs.push(chimera);
+        // R2 fails quality control, so R1 is assembled as an orphan.
+        let mut orphaned = pair(&genome, 50);
+        orphaned.r2.qualities = Some(vec![2; 60]);
+        pairs.push(orphaned);
+
+        let processor = SequenceProcessor::new(ProcessingConfig {
+            seed_length: 15,
+            ..ProcessingConfig::default()
+        })
+        .with_reference(&NucleotideSequence::from_ascii(&genome).unwrap())
+        .unwrap();
+        let result = processor.process_pairs(pairs).unwrap();
+
+        assert_eq!(result.sequence.to_ascii(), genome);
+        assert_eq!(
+            result.processing_log[0],
+            "quality control kept 42 of 43 pairs and 1 orphaned mates"
+        );
+        assert!(result.processing_log.contains(&"paired-end: 41 proper pairs, 1 discordant, insert size median 200, mean 200.0, sd 0.0".to_string()));
+        let insert_size = result.metrics.insert_size.unwrap();
+        assert_eq!(
+            (
+                insert_size.proper_pairs,
+                insert_size.median,
+                insert_size.std_dev
+            ),
+            (41, 200, 0.0)
+        );
+        // 41 proper pairs and the orphan; the chimera's mates are dropped.
+        assert_eq!(result.metrics.coverage_depth, (83 * 60 / 400) as u32);
+    }
+}
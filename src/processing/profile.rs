@@ -0,0 +1,199 @@
+use crate::model::SequencerModel;
+use crate::processing::{AdapterSet, CorrectionStrategy, ProcessingConfig, QualityChecker};
+
+/// Platform-specific thresholds and processing choices. Raw error rates
+/// differ by two orders of magnitude between short- and long-read
+/// instruments, so one set of limits either rejects every Nanopore run or
+/// waves through poor Illumina data.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityProfile {
+    pub name: &'static str,
+    pub minimum_coverage: u32,
+    pub maximum_error_rate: f32, // Read-vs-assembly mismatch rate, see `QualityMetricsEngine`
+    pub min_base_quality: u8,
+    pub min_mean_quality: f32,
+    pub error_correction: CorrectionStrategy,
+    pub correction_kmer_length: usize, // Shorter for error-dense reads so some k-mers stay solid
+    pub phred_offset: u8,
+    pub adapter_sets: &'static [AdapterSet],
+}
+
+pub const NOVASEQ_6000: QualityProfile = QualityProfile {
+    name: "Illumina NovaSeq 6000",
+    minimum_coverage: 30,
+    maximum_error_rate: 0.01,
+    min_base_quality: 20,
+    min_mean_quality: 25.0,
+    error_correction: CorrectionStrategy::KmerSpectrum,
+    correction_kmer_length: 21,
+    phred_offset: 33,
+    adapter_sets: &[AdapterSet::TruSeq, AdapterSet::Nextera],
+};
+
+pub const MINION: QualityProfile = QualityProfile {
+    name: "Oxford Nanopore MinION",
+    minimum_coverage: 20,
+    maximum_error_rate: 0.1,
+    min_base_quality: 7,
+    min_mean_quality: 10.0,
+    error_correction: CorrectionStrategy::Combined,
+    correction_kmer_length: 15,
+    phred_offset: 33,
+    adapter_sets: &[AdapterSet::NanoporeLigation],
+};
+
+/// Pre-1.8 Illumina pipelines wrote qualities with an ASCII offset of 64.
+pub const GENOME_ANALYZER_II: QualityProfile = QualityProfile {
+    name: "Illumina Genome Analyzer II",
+    phred_offset: 64,
+    ..NOVASEQ_6000
+};
+
+impl QualityProfile {
+    /// Profile for `sequencer`, matched on manufacturer and model without
+    /// regard to case. Other Illumina instruments share the NovaSeq profile
+    /// and other Nanopore devices the MinION one; unknown manufacturers have
+    /// none.
+    pub fn for_sequencer(sequencer: &SequencerModel) -> Option<&'static QualityProfile> {
+        let manufacturer = sequencer.manufacturer.to_ascii_lowercase();
+        let model = sequencer.model.to_ascii_lowercase();
+        if manufacturer.contains("illumina") {
+            Some(if model.contains("genome analyzer") {
+                &GENOME_ANALYZER_II
+            } else {
+                &NOVASEQ_6000
+            })
+        } else if manufacturer.contains("nanopore") {
+            Some(&MINION)
+        } else {
+            None
+        }
+    }
+
+    pub fn checker(&self) -> QualityChecker {
+        QualityChecker {
+            minimum_coverage: self.minimum_coverage,
+            maximum_error_rate: self.maximum_error_rate,
+        }
+    }
+
+    /// Default processing settings with this profile's filtering, adapter,
+    /// correction and encoding choices.
+    pub fn processing_config(&self) -> ProcessingConfig {
+        ProcessingConfig {
+            min_base_quality: self.min_base_quality,
+            min_mean_quality: self.min_mean_quality,
+            error_correction: self.error_correction,
+            correction_kmer_length: self.correction_kmer_length,
+            phred_offset: self.phred_offset,
+            adapters: self
+                .adapter_sets
+                .iter()
+                .flat_map(|set| set.adapters())
+                .collect(),
+            ..ProcessingConfig::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::QualityMetrics;
+    use crate::processing::{ProcessingError, SequenceProcessor};
+
+    fn sequencer(manufacturer: &str, model: &str) -> SequencerModel {
+        SequencerModel {
+            manufacturer: manufacturer.to_string(),
+            model: model.to_string(),
+            serial_number: "SN-0001".to_string(),
+            throughput: 6000,
+            error_rate: 0.001,
+            last_calibration: 0,
+        }
+    }
+
+    #[test]
+    fn test_profile_lookup() {
+        assert_eq!(
+            QualityProfile::for_sequencer(&sequencer("Illumina", "NovaSeq 6000")),
+            Some(&NOVASEQ_6000)
+        );
+        assert_eq!(
+            QualityProfile::for_sequencer(&sequencer("illumina", "MiSeq")),
+            Some(&NOVASEQ_6000)
+        );
+        assert_eq!(
+            QualityProfile::for_sequencer(&sequencer("Illumina", "Genome Analyzer IIx")),
+            Some(&GENOME_ANALYZER_II)
+        );
+        assert_eq!(
+            QualityProfile::for_sequencer(&sequencer("Oxford Nanopore", "MinION Mk1C")),
+            Some(&MINION)
+        );
+        assert_eq!(
+            QualityProfile::for_sequencer(&sequencer("Oxford Nanopore Technologies", "PromethION")),
+            Some(&MINION)
+        );
+        assert_eq!(
+            QualityProfile::for_sequencer(&sequencer("Acme", "Sequencer 1")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_profile_thresholds() {
+        let nanopore_run = QualityMetrics {
+            coverage_depth: 25,
+            quality_score: 14.0,
+            error_rate: 0.06,
+            confidence_intervals: vec![],
+            insert_size: None,
+        };
+        assert_eq!(
+            NOVASEQ_6000.checker().check_quality(&nanopore_run),
+            Err(ProcessingError::InsufficientCoverage)
+        );
+        assert_eq!(
+            NOVASEQ_6000.checker().check_quality(&QualityMetrics {
+                coverage_depth: 40,
+                ..nanopore_run.clone()
+            }),
+            Err(ProcessingError::HighErrorRate)
+        );
+        assert!(MINION.checker().check_quality(&nanopore_run).is_ok());
+
+        let processor =
+            SequenceProcessor::for_sequencer(&sequencer("Oxford Nanopore", "MinION")).unwrap();
+        assert_eq!(
+            processor.config().error_correction,
+            CorrectionStrategy::Combined
+        );
+        assert_eq!(processor.config().min_base_quality, 7);
+        assert!(matches!(
+            SequenceProcessor::for_sequencer(&sequencer("Acme", "Sequencer 1")),
+            Err(ProcessingError::UnknownSequencer(name)) if name == "Acme Sequencer 1"
+        ));
+    }
+
+    #[test]
+    fn test_profile_phred_encoding() {
+        // `h` is Q40 with an offset of 64; read with an offset of 33 it would
+        // be Q71.
+        let fastq = b"@read\nACGTACGTAC\n+\nhhhhhhhhhh\n".to_vec();
+        for (profile, quality) in [(&GENOME_ANALYZER_II, 40.0), (&NOVASEQ_6000, 71.0)] {
+            let processor = SequenceProcessor::new(ProcessingConfig {
+                seed_length: 5,
+                ..profile.processing_config()
+            });
+            assert_eq!(
+                processor
+                    .process_sequence(fastq.clone())
+                    .unwrap()
+                    .metrics
+                    .quality_score,
+                quality
+            );
+        }
+    }
+}
@@ -0,0 +1,607 @@
+//! Runs the program's instructions through the Solana runtime's
+//! `InvokeContext`, the way `solana-program-test` runs builtin programs.
+//! Account infos are handed to `process_instruction` in the runtime's
+//! serialized layout, and every change the program or the system program
+//! makes goes back through the runtime's `BorrowedAccount`, which enforces
+//! the signer, writable, ownership, lamport-balance and realloc rules.
+//! Hashing charges compute units as the `sol_sha256` syscall would, against
+//! the default per-instruction limit.
+
+use super::{find_sequence_address, process_instruction};
+use crate::model::{DNASequence, SequenceValidatorState};
+use agave_feature_set::FeatureSet;
+use borsh::BorshDeserialize;
+use solana_account::{AccountSharedData, ReadableAccount, WritableAccount};
+use solana_compute_budget::{
+    compute_budget::ComputeBudget, compute_budget_limits::DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT,
+};
+use solana_program::{
+    account_info::AccountInfo,
+    clock::Clock,
+    entrypoint::{
+        self, ProgramResult, BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER,
+        SUCCESS,
+    },
+    hash::Hash,
+    instruction::{Instruction, InstructionError},
+    program_error::{ProgramError, UNSUPPORTED_SYSVAR},
+    program_stubs::{set_syscall_stubs, SyscallStubs},
+    pubkey::Pubkey,
+    rent::Rent,
+    stable_layout::stable_instruction::StableInstruction,
+};
+use solana_program_runtime::{
+    declare_process_instruction,
+    invoke_context::{EnvironmentConfig, InvokeContext},
+    loaded_programs::{ProgramCacheEntry, ProgramCacheForTxBatch},
+    sysvar_cache::SysvarCache,
+};
+use solana_system_interface::{
+    error::SystemError, instruction::SystemInstruction, program as system_program,
+};
+use solana_timings::ExecuteTimings;
+use solana_transaction_context::{
+    BorrowedAccount, IndexOfAccount, InstructionAccount, InstructionContext, TransactionContext,
+};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::{Arc, Once};
+
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array([42; 32]);
+
+/// Lamports given to signers the bank has not seen before.
+const SIGNER_LAMPORTS: u64 = 10_000_000_000;
+
+thread_local! {
+    // The invoke context of the instruction running on this thread, for the
+    // syscall stubs.
+    static INVOKE_CONTEXT: Cell<usize> = const { Cell::new(0) };
+    // Units charged by `charge_sha256` during the current instruction, and
+    // whether they ran past its limit.
+    static CHARGED: Cell<(u64, bool)> = const { Cell::new((0, false)) };
+}
+
+/// Charges the running instruction for hashing `vals`, at the
+/// `sol_sha256` syscall's price. Hashing outside an instruction is free.
+pub fn charge_sha256(vals: &[&[u8]]) {
+    if INVOKE_CONTEXT.with(Cell::get) == 0 {
+        return;
+    }
+    let invoke_context = invoke_context();
+    let budget = invoke_context.get_compute_budget();
+    let units = vals
+        .iter()
+        .map(|val| {
+            budget
+                .mem_op_base_cost
+                .max(budget.sha256_byte_cost * (val.len() as u64 / 2))
+        })
+        .sum::<u64>()
+        + budget.sha256_base_cost;
+    let exceeded = invoke_context.consume_checked(units).is_err();
+    CHARGED.with(|charged| {
+        let (total, over) = charged.get();
+        charged.set((total + units, over || exceeded));
+    });
+}
+
+#[allow(clippy::mut_from_ref)]
+fn invoke_context<'a>() -> &'a mut InvokeContext<'static> {
+    let context = INVOKE_CONTEXT.with(Cell::get);
+    assert_ne!(context, 0, "no instruction is running");
+    unsafe { &mut *(context as *mut InvokeContext<'static>) }
+}
+
+/// Runtime errors the program can see; the rest only come from bugs in the
+/// harness.
+fn program_error(err: InstructionError) -> ProgramError {
+    ProgramError::try_from(err).unwrap_or_else(|err| panic!("{err:?}"))
+}
+
+/// Lays the instruction's accounts out the way the runtime passes them to
+/// on-chain programs, with room for each account to grow by
+/// `MAX_PERMITTED_DATA_INCREASE` bytes, so that `entrypoint::deserialize`
+/// and `AccountInfo::resize` behave as they would on-chain.
+fn serialize_parameters(
+    transaction_context: &TransactionContext,
+    instruction_context: &InstructionContext,
+) -> Result<Vec<u64>, InstructionError> {
+    let mut bytes = Vec::new();
+    let count = instruction_context.get_number_of_instruction_accounts();
+    bytes.extend((count as u64).to_le_bytes());
+    for index in 0..count {
+        if let Some(original) = instruction_context.is_instruction_account_duplicate(index)? {
+            bytes.push(original as u8);
+            bytes.extend([0; 7]);
+            continue;
+        }
+        let account =
+            instruction_context.try_borrow_instruction_account(transaction_context, index)?;
+        #[allow(deprecated)]
+        let executable = account.is_executable();
+        bytes.extend([
+            NON_DUP_MARKER,
+            account.is_signer() as u8,
+            account.is_writable() as u8,
+            executable as u8,
+        ]);
+        bytes.extend([0; 4]); // original data length, filled in by `deserialize`
+        bytes.extend(account.get_key().to_bytes());
+        bytes.extend(account.get_owner().to_bytes());
+        bytes.extend(account.get_lamports().to_le_bytes());
+        bytes.extend((account.get_data().len() as u64).to_le_bytes());
+        bytes.extend(account.get_data());
+        bytes.resize(
+            (bytes.len() + MAX_PERMITTED_DATA_INCREASE).next_multiple_of(BPF_ALIGN_OF_U128),
+            0,
+        );
+        bytes.extend(account.get_rent_epoch().to_le_bytes());
+    }
+    let instruction_data = instruction_context.get_instruction_data();
+    bytes.extend((instruction_data.len() as u64).to_le_bytes());
+    bytes.extend(instruction_data);
+    bytes.extend(
+        instruction_context
+            .get_last_program_key(transaction_context)?
+            .to_bytes(),
+    );
+
+    let mut input = vec![0u64; bytes.len().div_ceil(8)];
+    for (word, chunk) in input.iter_mut().zip(bytes.chunks(8)) {
+        let mut buffer = [0; 8];
+        buffer[..chunk.len()].copy_from_slice(chunk);
+        *word = u64::from_ne_bytes(buffer);
+    }
+    Ok(input)
+}
+
+/// Hands what the program did to `info` to the runtime, which refuses
+/// changes the current program may not make.
+fn commit(account: &mut BorrowedAccount, info: &AccountInfo) -> Result<(), InstructionError> {
+    if account.get_lamports() != info.lamports() {
+        account.set_lamports(info.lamports())?;
+    }
+    let data = info
+        .try_borrow_data()
+        .map_err(|_| InstructionError::AccountBorrowFailed)?;
+    if account.get_data() != *data {
+        account.set_data_from_slice(&data)?;
+    }
+    // Last, so that lamports and data can still change under the old owner.
+    if account.get_owner() != info.owner {
+        account.set_owner(info.owner.as_ref())?;
+    }
+    Ok(())
+}
+
+declare_process_instruction!(BioModProgram, 1, |invoke_context| {
+    INVOKE_CONTEXT.with(|context| context.set(invoke_context as *mut InvokeContext as usize));
+    let mut input = {
+        let transaction_context = &invoke_context.transaction_context;
+        serialize_parameters(
+            transaction_context,
+            transaction_context.get_current_instruction_context()?,
+        )?
+    };
+    let (program_id, infos, instruction_data) =
+        unsafe { entrypoint::deserialize(input.as_mut_ptr() as *mut u8) };
+    let result = process_instruction(program_id, &infos, instruction_data);
+    // On-chain the program would have been stopped where it ran out.
+    if CHARGED.with(Cell::get).1 {
+        return Err(InstructionError::ComputationalBudgetExceeded);
+    }
+    result.map_err(|err| InstructionError::from(u64::from(err)))?;
+
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    for (index, info) in infos.iter().enumerate() {
+        let index = index as IndexOfAccount;
+        if instruction_context
+            .is_instruction_account_duplicate(index)?
+            .is_none()
+        {
+            commit(
+                &mut instruction_context
+                    .try_borrow_instruction_account(transaction_context, index)?,
+                info,
+            )?;
+        }
+    }
+    Ok(())
+});
+
+// The system program instructions the program invokes, with the checks the
+// real system program makes.
+declare_process_instruction!(SystemProgram, 150, |invoke_context| {
+    let transaction_context = &invoke_context.transaction_context;
+    let instruction_context = transaction_context.get_current_instruction_context()?;
+    let account = |index: IndexOfAccount| {
+        instruction_context.try_borrow_instruction_account(transaction_context, index)
+    };
+    let require_signer =
+        |index: IndexOfAccount| match instruction_context.is_instruction_account_signer(index)? {
+            true => Ok(()),
+            false => Err(InstructionError::MissingRequiredSignature),
+        };
+    let allocate = |index: IndexOfAccount, space: u64| {
+        require_signer(index)?;
+        let mut account = account(index)?;
+        if !account.get_data().is_empty() || !system_program::check_id(account.get_owner()) {
+            return Err(InstructionError::Custom(
+                SystemError::AccountAlreadyInUse as u32,
+            ));
+        }
+        account.set_data_length(space as usize)
+    };
+    let assign = |index: IndexOfAccount, owner: &Pubkey| {
+        require_signer(index)?;
+        account(index)?.set_owner(owner.as_ref())
+    };
+    let transfer = |from: IndexOfAccount, to: IndexOfAccount, lamports: u64| {
+        require_signer(from)?;
+        let mut from = account(from)?;
+        if !from.get_data().is_empty() {
+            return Err(InstructionError::InvalidArgument);
+        }
+        if lamports > from.get_lamports() {
+            return Err(InstructionError::Custom(
+                SystemError::ResultWithNegativeLamports as u32,
+            ));
+        }
+        from.checked_sub_lamports(lamports)?;
+        drop(from);
+        account(to)?.checked_add_lamports(lamports)
+    };
+
+    let instruction = bincode::deserialize(instruction_context.get_instruction_data())
+        .map_err(|_| InstructionError::InvalidInstructionData)?;
+    match instruction {
+        SystemInstruction::CreateAccount {
+            lamports,
+            space,
+            owner,
+        } => {
+            if account(1)?.get_lamports() > 0 {
+                return Err(InstructionError::Custom(
+                    SystemError::AccountAlreadyInUse as u32,
+                ));
+            }
+            allocate(1, space)?;
+            assign(1, &owner)?;
+            transfer(0, 1, lamports)
+        }
+        SystemInstruction::Transfer { lamports } => transfer(0, 1, lamports),
+        SystemInstruction::Allocate { space } => allocate(0, space),
+        SystemInstruction::Assign { owner } => assign(0, &owner),
+        _ => Err(InstructionError::InvalidInstructionData),
+    }
+});
+
+fn find_info<'a, 'b>(
+    transaction_context: &TransactionContext,
+    account_infos: &'a [AccountInfo<'b>],
+    instruction_account: &InstructionAccount,
+) -> Result<&'a AccountInfo<'b>, InstructionError> {
+    let key = transaction_context
+        .get_key_of_account_at_index(instruction_account.index_in_transaction)?;
+    account_infos
+        .iter()
+        .find(|info| info.key == key)
+        .ok_or(InstructionError::MissingAccount)
+}
+
+fn write_sysvar<T: Clone>(sysvar: Result<Arc<T>, InstructionError>, var_addr: *mut u8) -> u64 {
+    match sysvar {
+        Ok(sysvar) => {
+            unsafe { *(var_addr as *mut T) = T::clone(&sysvar) };
+            SUCCESS
+        }
+        Err(_) => UNSUPPORTED_SYSVAR,
+    }
+}
+
+/// Routes the program's syscalls into the running invoke context.
+struct RuntimeStubs;
+
+impl SyscallStubs for RuntimeStubs {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        write_sysvar(invoke_context().get_sysvar_cache().get_clock(), var_addr)
+    }
+
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        write_sysvar(invoke_context().get_sysvar_cache().get_rent(), var_addr)
+    }
+
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let invoke_context = invoke_context();
+        let caller = {
+            let transaction_context = &invoke_context.transaction_context;
+            let instruction_context = transaction_context
+                .get_current_instruction_context()
+                .map_err(program_error)?;
+            *instruction_context
+                .get_last_program_key(transaction_context)
+                .map_err(program_error)?
+        };
+        let signers = signers_seeds
+            .iter()
+            .map(|seeds| Pubkey::create_program_address(seeds, &caller))
+            .collect::<Result<Vec<_>, _>>()?;
+        let instruction = StableInstruction::from(instruction.clone());
+        let (instruction_accounts, _) = invoke_context
+            .prepare_instruction(&instruction, &signers)
+            .map_err(program_error)?;
+
+        // The callee sees what the caller has done to the accounts so far.
+        {
+            let transaction_context = &invoke_context.transaction_context;
+            let instruction_context = transaction_context
+                .get_current_instruction_context()
+                .map_err(program_error)?;
+            for instruction_account in &instruction_accounts {
+                let mut account = instruction_context
+                    .try_borrow_instruction_account(
+                        transaction_context,
+                        instruction_account.index_in_caller,
+                    )
+                    .map_err(program_error)?;
+                let info = find_info(transaction_context, account_infos, instruction_account)
+                    .map_err(program_error)?;
+                commit(&mut account, info).map_err(program_error)?;
+            }
+        }
+        invoke_context
+            .native_invoke(instruction, &signers)
+            .map_err(program_error)?;
+
+        // And the caller sees what the callee did, growing account data no
+        // further than the runtime allows within one instruction.
+        let transaction_context = &invoke_context.transaction_context;
+        let instruction_context = transaction_context
+            .get_current_instruction_context()
+            .map_err(program_error)?;
+        for instruction_account in instruction_accounts
+            .iter()
+            .filter(|account| account.is_writable)
+        {
+            let account = instruction_context
+                .try_borrow_instruction_account(
+                    transaction_context,
+                    instruction_account.index_in_caller,
+                )
+                .map_err(program_error)?;
+            let info = find_info(transaction_context, account_infos, instruction_account)
+                .map_err(program_error)?;
+            **info.try_borrow_mut_lamports()? = account.get_lamports();
+            if info.owner != account.get_owner() {
+                info.assign(account.get_owner());
+            }
+            if info.data_len() != account.get_data().len() {
+                info.resize(account.get_data().len())?;
+            }
+            info.try_borrow_mut_data()?
+                .copy_from_slice(account.get_data());
+        }
+        Ok(())
+    }
+}
+
+/// Rent state of an account, as the runtime tracks it between transactions.
+#[derive(PartialEq, Eq)]
+enum RentState {
+    Uninitialized,
+    RentPaying { lamports: u64, data_size: usize },
+    RentExempt,
+}
+
+impl RentState {
+    fn of(account: &AccountSharedData, rent: &Rent) -> Self {
+        if account.lamports() == 0 {
+            RentState::Uninitialized
+        } else if rent.is_exempt(account.lamports(), account.data().len()) {
+            RentState::RentExempt
+        } else {
+            RentState::RentPaying {
+                lamports: account.lamports(),
+                data_size: account.data().len(),
+            }
+        }
+    }
+
+    /// Accounts may only be left rent-paying if they already were, with the
+    /// same size and no more lamports.
+    fn may_follow(&self, pre: &RentState) -> bool {
+        match (pre, self) {
+            (_, RentState::Uninitialized | RentState::RentExempt) => true,
+            (
+                RentState::RentPaying {
+                    lamports,
+                    data_size,
+                },
+                RentState::RentPaying {
+                    lamports: post_lamports,
+                    data_size: post_size,
+                },
+            ) => post_size == data_size && post_lamports <= lamports,
+            _ => false,
+        }
+    }
+}
+
+/// Accounts held in memory between instructions. Each instruction runs as
+/// its own transaction: changes are kept only if it succeeds and leaves
+/// every account it writes rent-exempt, empty, or no worse than before.
+/// Accounts left empty are purged.
+pub struct LocalBank {
+    accounts: HashMap<Pubkey, AccountSharedData>,
+    clock: Clock,
+    compute_units: u64, // Charged for hashing by the last instruction
+}
+
+impl LocalBank {
+    pub fn new(unix_timestamp: i64) -> Self {
+        static RUNTIME: Once = Once::new();
+        RUNTIME.call_once(|| {
+            set_syscall_stubs(Box::new(RuntimeStubs));
+        });
+        let mut accounts = HashMap::new();
+        for program in [PROGRAM_ID, system_program::ID] {
+            let mut account = AccountSharedData::new(1, 0, &solana_sdk_ids::native_loader::ID);
+            account.set_executable(true);
+            accounts.insert(program, account);
+        }
+        Self {
+            accounts,
+            clock: Clock {
+                unix_timestamp,
+                ..Clock::default()
+            },
+            compute_units: 0,
+        }
+    }
+
+    pub fn set_unix_timestamp(&mut self, unix_timestamp: i64) {
+        self.clock.unix_timestamp = unix_timestamp;
+    }
+
+    pub fn add_account(&mut self, key: Pubkey, lamports: u64, space: usize, owner: Pubkey) {
+        self.accounts
+            .insert(key, AccountSharedData::new(lamports, space, &owner));
+    }
+
+    pub fn account(&self, key: &Pubkey) -> &AccountSharedData {
+        &self.accounts[key]
+    }
+
+    pub fn compute_units(&self) -> u64 {
+        self.compute_units
+    }
+
+    pub fn process(&mut self, instruction: Instruction) -> Result<(), InstructionError> {
+        // Accounts first seen here are empty system accounts; signers get
+        // enough lamports to pay rent.
+        let mut keys = vec![PROGRAM_ID, system_program::ID];
+        for meta in &instruction.accounts {
+            let lamports = if meta.is_signer { SIGNER_LAMPORTS } else { 0 };
+            self.accounts
+                .entry(meta.pubkey)
+                .or_insert_with(|| AccountSharedData::new(lamports, 0, &system_program::ID));
+            if !keys.contains(&meta.pubkey) {
+                keys.push(meta.pubkey);
+            }
+        }
+        let instruction_accounts: Vec<InstructionAccount> = instruction
+            .accounts
+            .iter()
+            .enumerate()
+            .map(|(index, meta)| {
+                let index_in_transaction =
+                    keys.iter().position(|key| *key == meta.pubkey).unwrap() as IndexOfAccount;
+                let first = instruction
+                    .accounts
+                    .iter()
+                    .position(|other| other.pubkey == meta.pubkey)
+                    .unwrap();
+                InstructionAccount {
+                    index_in_transaction,
+                    index_in_caller: index_in_transaction,
+                    index_in_callee: first.min(index) as IndexOfAccount,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                }
+            })
+            .collect();
+
+        let rent = Rent::default();
+        let compute_budget = ComputeBudget {
+            compute_unit_limit: DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT as u64,
+            ..ComputeBudget::default()
+        };
+        let mut transaction_context = TransactionContext::new(
+            keys.iter()
+                .map(|key| (*key, self.accounts[key].clone()))
+                .collect(),
+            rent.clone(),
+            compute_budget.max_instruction_stack_depth,
+            compute_budget.max_instruction_trace_length,
+        );
+        let mut sysvar_cache = SysvarCache::default();
+        sysvar_cache.set_sysvar_for_tests(&self.clock);
+        sysvar_cache.set_sysvar_for_tests(&rent);
+        let mut programs = ProgramCacheForTxBatch::default();
+        programs.replenish(
+            PROGRAM_ID,
+            Arc::new(ProgramCacheEntry::new_builtin(0, 0, BioModProgram::vm)),
+        );
+        programs.replenish(
+            system_program::ID,
+            Arc::new(ProgramCacheEntry::new_builtin(0, 0, SystemProgram::vm)),
+        );
+        CHARGED.with(|charged| charged.set((0, false)));
+        let result = {
+            let environment = EnvironmentConfig::new(
+                Hash::default(),
+                0,
+                0,
+                &|_| 0,
+                Arc::new(FeatureSet::all_enabled()),
+                &sysvar_cache,
+            );
+            let mut invoke_context = InvokeContext::new(
+                &mut transaction_context,
+                &mut programs,
+                environment,
+                None,
+                compute_budget,
+            );
+            invoke_context.process_instruction(
+                &instruction.data,
+                &instruction_accounts,
+                &[0],
+                &mut 0,
+                &mut ExecuteTimings::default(),
+            )
+        };
+        INVOKE_CONTEXT.with(|context| context.set(0));
+        self.compute_units = CHARGED.with(Cell::get).0;
+        result?;
+
+        let accounts = transaction_context.deconstruct_without_keys()?;
+        for (key, account) in keys.iter().zip(&accounts) {
+            let writable = instruction
+                .accounts
+                .iter()
+                .any(|meta| meta.pubkey == *key && meta.is_writable);
+            if writable
+                && !RentState::of(account, &rent)
+                    .may_follow(&RentState::of(&self.accounts[key], &rent))
+            {
+                return Err(InstructionError::AccountNotRentExempt);
+            }
+        }
+        // Like the runtime, purge accounts left without lamports.
+        for (key, account) in keys.into_iter().zip(accounts) {
+            let account = if account.lamports() == 0 {
+                AccountSharedData::default()
+            } else {
+                account
+            };
+            self.accounts.insert(key, account);
+        }
+        Ok(())
+    }
+
+    pub fn state(&self, key: &Pubkey) -> SequenceValidatorState {
+        SequenceValidatorState::deserialize(&mut self.account(key).data()).unwrap()
+    }
+
+    pub fn sequence(&self, state: &Pubkey, sequence_hash: &[u8; 32]) -> DNASequence {
+        let (address, _) = find_sequence_address(&PROGRAM_ID, state, sequence_hash);
+        DNASequence::deserialize(&mut self.account(&address).data()).unwrap()
+    }
+}
@@ -0,0 +1,235 @@
+use crate::model::{
+    DNASequence, QualityMetrics, ResourceLimits, ValidationThresholds, ValidatorInfo,
+};
+use crate::program::find_sequence_address;
+use crate::proof::ValidationProof;
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+};
+use solana_system_interface::program as system_program;
+
+/// Instructions understood by the program, Borsh-encoded in the instruction
+/// data. They follow the off-chain `ConsensusManager` flow: validators are
+/// registered, a sequence is registered, validators submit signed proofs and
+/// anyone may then finalize consensus.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub enum BioModInstruction {
+    /// Sets up an empty validator registry owned by the signer. The state
+    /// account must be large enough for the registry filled to
+    /// `resource_limits`.
+    ///
+    /// Accounts: `[writable]` state, `[signer]` owner.
+    InitializeState {
+        min_validations: u8,
+        sequence_ttl: i64,
+        validation_thresholds: ValidationThresholds,
+        resource_limits: ResourceLimits,
+    },
+    /// Creates the sequence's account, funded by the payer, and opens it for
+    /// validation until the registry's `sequence_ttl` has passed. Acceptances
+    /// of `merkle_root`, the root of the sequencer's own proof, must carry
+    /// `zk_commitment`, its pinned zk commitment; acceptances of other roots
+    /// are recorded as dissent. The program checks zk proofs with
+    /// `HashChainBackend`, which is not zero-knowledge.
+    ///
+    /// Accounts: `[writable]` state, `[writable]` sequence, `[writable, signer]`
+    /// payer, `[]` system program.
+    RegisterSequence {
+        sequence: DNASequence,
+        quality_metrics: QualityMetrics,
+        merkle_root: [u8; 32],
+        zk_commitment: [u8; 32],
+    },
+    /// Records a registered validator's signed acceptance or rejection of an
+    /// unsettled sequence, replacing any earlier one from the same validator.
+    ///
+    /// Accounts: `[writable]` state, `[writable]` sequence, `[signer]` validator.
+    SubmitValidation { proof: ValidationProof },
+    /// Confirms, rejects or disputes the sequence once enough registered
+    /// validators have voted, or expires it. Permissionless.
+    ///
+    /// Accounts: `[writable]` state.
+    FinalizeConsensus { sequence_hash: [u8; 32] },
+    /// Withdraws a sequence and closes its account, refunding the rent to the
+    /// owner.
+    ///
+    /// Accounts: `[writable]` sequence, `[writable]` state, `[writable, signer]` owner.
+    RevokeSequence { sequence_hash: [u8; 32] },
+    /// Adds a validator to the registry, up to `ResourceLimits.max_validators`
+    /// and as long as its record leaves room for a full registry.
+    ///
+    /// Accounts: `[writable]` state, `[signer]` owner.
+    RegisterValidator { validator: ValidatorInfo },
+    /// Drops a validator from the registry. Its proofs stay on record but no
+    /// longer count towards consensus.
+    ///
+    /// Accounts: `[writable]` state, `[signer]` owner.
+    RemoveValidator { validator: Pubkey },
+    /// Removes the named sequences whose validation window closed without a
+    /// verdict and closes their accounts, refunding the payers. Confirmed and
+    /// rejected sequences stay until the owner revokes them. Permissionless,
+    /// so anyone can keep the registry within its limits.
+    ///
+    /// Accounts: `[writable]` state, then `[writable]` sequence and
+    /// `[writable]` payer for each sequence, in order.
+    ExpireSequences { sequence_hashes: Vec<[u8; 32]> },
+}
+
+pub fn initialize_state(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    owner: &Pubkey,
+    min_validations: u8,
+    sequence_ttl: i64,
+    validation_thresholds: ValidationThresholds,
+    resource_limits: ResourceLimits,
+) -> Instruction {
+    let instruction = BioModInstruction::InitializeState {
+        min_validations,
+        sequence_ttl,
+        validation_thresholds,
+        resource_limits,
+    };
+    Instruction::new_with_borsh(
+        *program_id,
+        &instruction,
+        vec![
+            AccountMeta::new(*state, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+pub fn register_sequence(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    payer: &Pubkey,
+    sequence: DNASequence,
+    quality_metrics: QualityMetrics,
+    merkle_root: [u8; 32],
+    zk_commitment: [u8; 32],
+) -> Instruction {
+    let (sequence_account, _) = find_sequence_address(program_id, state, &sequence.sequence_hash);
+    Instruction::new_with_borsh(
+        *program_id,
+        &BioModInstruction::RegisterSequence {
+            sequence,
+            quality_metrics,
+            merkle_root,
+            zk_commitment,
+        },
+        vec![
+            AccountMeta::new(*state, false),
+            AccountMeta::new(sequence_account, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+    )
+}
+
+/// The proof's validator signs the transaction.
+pub fn submit_validation(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    proof: ValidationProof,
+) -> Instruction {
+    let validator = proof.validator;
+    let (sequence_account, _) = find_sequence_address(program_id, state, &proof.sequence_hash);
+    Instruction::new_with_borsh(
+        *program_id,
+        &BioModInstruction::SubmitValidation { proof },
+        vec![
+            AccountMeta::new(*state, false),
+            AccountMeta::new(sequence_account, false),
+            AccountMeta::new_readonly(validator, true),
+        ],
+    )
+}
+
+pub fn finalize_consensus(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    sequence_hash: [u8; 32],
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BioModInstruction::FinalizeConsensus { sequence_hash },
+        vec![AccountMeta::new(*state, false)],
+    )
+}
+
+pub fn revoke_sequence(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    owner: &Pubkey,
+    sequence_hash: [u8; 32],
+) -> Instruction {
+    let (sequence_account, _) = find_sequence_address(program_id, state, &sequence_hash);
+    Instruction::new_with_borsh(
+        *program_id,
+        &BioModInstruction::RevokeSequence { sequence_hash },
+        vec![
+            AccountMeta::new(sequence_account, false),
+            AccountMeta::new(*state, false),
+            AccountMeta::new(*owner, true),
+        ],
+    )
+}
+
+pub fn register_validator(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    owner: &Pubkey,
+    validator: ValidatorInfo,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BioModInstruction::RegisterValidator { validator },
+        vec![
+            AccountMeta::new(*state, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+pub fn remove_validator(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    owner: &Pubkey,
+    validator: Pubkey,
+) -> Instruction {
+    Instruction::new_with_borsh(
+        *program_id,
+        &BioModInstruction::RemoveValidator { validator },
+        vec![
+            AccountMeta::new(*state, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+/// `expired` pairs each sequence hash with the payer recorded when it was
+/// registered.
+pub fn expire_sequences(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    expired: &[([u8; 32], Pubkey)],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new(*state, false)];
+    for (sequence_hash, payer) in expired {
+        let (sequence_account, _) = find_sequence_address(program_id, state, sequence_hash);
+        accounts.push(AccountMeta::new(sequence_account, false));
+        accounts.push(AccountMeta::new(*payer, false));
+    }
+    let sequence_hashes = expired
+        .iter()
+        .map(|(sequence_hash, _)| *sequence_hash)
+        .collect();
+    Instruction::new_with_borsh(
+        *program_id,
+        &BioModInstruction::ExpireSequences { sequence_hashes },
+        accounts,
+    )
+}
@@ -0,0 +1,1913 @@
+#[cfg(test)]
+pub(crate) mod bank;
+pub mod instruction;
+
+use crate::consensus::{settle_consensus, weigh_consensus, ConsensusError, ConsensusStatus};
+use crate::error::ValidationError;
+use crate::model::{
+    DNASequence, InsertSizeStats, QualityMetrics, ResourceLimits, SequenceData,
+    SequenceValidatorState, ValidationThresholds, ValidatorInfo, MAX_CONFIDENCE_INTERVALS,
+};
+use crate::proof::{ProofValidator, SignatureError, ValidationProof};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    clock::Clock,
+    entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
+    msg,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+use std::collections::HashMap;
+
+pub use instruction::BioModInstruction;
+
+pub const SEQUENCE_SEED: &[u8] = b"sequence";
+
+/// Program-derived address of the account holding the sequence with
+/// `sequence_hash` in the registry at `state`, and its bump seed. The
+/// registry is part of the seeds so that one registry cannot claim another's
+/// address for a sequence.
+pub fn find_sequence_address(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    sequence_hash: &[u8; 32],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SEQUENCE_SEED, state.as_ref(), sequence_hash], program_id)
+}
+
+/// Bytes allocated for a sequence account: the encoded sequence plus room
+/// for one proof of up to `max_proof_size` bytes from each of
+/// `max_validators` validators, so validations never need a reallocation.
+/// The account is created by a cross-program invocation, so this may not
+/// exceed `MAX_PERMITTED_DATA_INCREASE`.
+pub fn sequence_account_space(sequence: &DNASequence, limits: &ResourceLimits) -> usize {
+    let encoded = borsh::to_vec(sequence)
+        .map(|bytes| bytes.len())
+        .unwrap_or(0);
+    encoded + limits.max_validators as usize * limits.max_proof_size as usize
+}
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// Decodes `instruction_data` as a `BioModInstruction` and runs it.
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instruction = BioModInstruction::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    match instruction {
+        BioModInstruction::InitializeState {
+            min_validations,
+            sequence_ttl,
+            validation_thresholds,
+            resource_limits,
+        } => initialize_state(
+            program_id,
+            accounts,
+            min_validations,
+            sequence_ttl,
+            validation_thresholds,
+            resource_limits,
+        ),
+        BioModInstruction::RegisterSequence {
+            sequence,
+            quality_metrics,
+            merkle_root,
+            zk_commitment,
+        } => register_sequence(
+            program_id,
+            accounts,
+            sequence,
+            quality_metrics,
+            merkle_root,
+            zk_commitment,
+        ),
+        BioModInstruction::SubmitValidation { proof } => {
+            submit_validation(program_id, accounts, proof)
+        }
+        BioModInstruction::FinalizeConsensus { sequence_hash } => {
+            finalize_consensus(program_id, accounts, sequence_hash)
+        }
+        BioModInstruction::RevokeSequence { sequence_hash } => {
+            revoke_sequence(program_id, accounts, sequence_hash)
+        }
+        BioModInstruction::RegisterValidator { validator } => {
+            register_validator(program_id, accounts, validator)
+        }
+        BioModInstruction::RemoveValidator { validator } => {
+            remove_validator(program_id, accounts, validator)
+        }
+        BioModInstruction::ExpireSequences { sequence_hashes } => {
+            expire_sequences(program_id, accounts, sequence_hashes)
+        }
+    }
+}
+
+/// Creates the rent-exempt account at the sequence's program-derived address
+/// under the registry at `state`,
+/// sized by `sequence_account_space`, and stores the Borsh-encoded sequence in
+/// it. Sequences arrive without validations; those are added as validators
+/// submit them.
+///
+/// Accounts: `[writable]` sequence, `[writable, signer]` payer, `[]` system program.
+pub fn store_sequence(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    accounts: &[AccountInfo],
+    sequence: DNASequence,
+    limits: &ResourceLimits,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+    let sequence_account = next_account_info(accounts)?;
+    let payer = next_account_info(accounts)?;
+    let system_program_account = next_account_info(accounts)?;
+    require_signer(payer)?;
+    if !system_program::check_id(system_program_account.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let (address, bump) = find_sequence_address(program_id, state, &sequence.sequence_hash);
+    if *sequence_account.key != address {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    if sequence_account.owner == program_id || !sequence_account.data_is_empty() {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    if !sequence.validations.is_empty() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let space = sequence_account_space(&sequence, limits);
+    if space > MAX_PERMITTED_DATA_INCREASE {
+        return Err(ProgramError::InvalidRealloc);
+    }
+    let required = Rent::get()?.minimum_balance(space);
+    let seeds: &[&[u8]] = &[
+        SEQUENCE_SEED,
+        state.as_ref(),
+        &sequence.sequence_hash,
+        &[bump],
+    ];
+    let infos = [
+        payer.clone(),
+        sequence_account.clone(),
+        system_program_account.clone(),
+    ];
+    if sequence_account.lamports() == 0 {
+        let create = system_instruction::create_account(
+            payer.key,
+            sequence_account.key,
+            required,
+            space as u64,
+            program_id,
+        );
+        invoke_signed(&create, &infos, &[seeds])?;
+    } else {
+        // Anyone can send lamports to the address ahead of time, which makes
+        // `create_account` fail; top up, allocate and assign instead.
+        let shortfall = required.saturating_sub(sequence_account.lamports());
+        if shortfall > 0 {
+            invoke_signed(
+                &system_instruction::transfer(payer.key, sequence_account.key, shortfall),
+                &infos,
+                &[],
+            )?;
+        }
+        invoke_signed(
+            &system_instruction::allocate(sequence_account.key, space as u64),
+            &infos,
+            &[seeds],
+        )?;
+        invoke_signed(
+            &system_instruction::assign(sequence_account.key, program_id),
+            &infos,
+            &[seeds],
+        )?;
+    }
+    save_sequence(sequence_account, &sequence)
+}
+
+/// Reads the sequence with `sequence_hash` from its program-owned account,
+/// which must be the one derived for the registry at `state`.
+fn load_sequence(
+    program_id: &Pubkey,
+    state: &Pubkey,
+    account: &AccountInfo,
+    sequence_hash: &[u8; 32],
+) -> Result<DNASequence, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    if *account.key != find_sequence_address(program_id, state, sequence_hash).0 {
+        return Err(ProgramError::InvalidSeeds);
+    }
+    let data = account.try_borrow_data()?;
+    DNASequence::deserialize(&mut &data[..]).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn save_sequence(account: &AccountInfo, sequence: &DNASequence) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    sequence
+        .serialize(&mut &mut data[..])
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+/// Reads the registry from a program-owned account. The account keeps its
+/// allocated size, so bytes past the encoded state are ignored.
+fn load_state(
+    program_id: &Pubkey,
+    account: &AccountInfo,
+) -> Result<SequenceValidatorState, ProgramError> {
+    if account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    let data = account.try_borrow_data()?;
+    if data.iter().all(|&byte| byte == 0) {
+        return Err(ProgramError::UninitializedAccount);
+    }
+    SequenceValidatorState::deserialize(&mut &data[..])
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+fn save_state(account: &AccountInfo, state: &SequenceValidatorState) -> ProgramResult {
+    let mut data = account.try_borrow_mut_data()?;
+    state
+        .serialize(&mut &mut data[..])
+        .map_err(|_| ProgramError::AccountDataTooSmall)
+}
+
+fn require_signer(account: &AccountInfo) -> ProgramResult {
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(())
+}
+
+/// Sequences the registry may hold: `max_sequences_per_validator` for each
+/// of `max_validators` validators.
+fn sequence_capacity(limits: &ResourceLimits) -> usize {
+    limits.max_validators as usize * limits.max_sequences_per_validator as usize
+}
+
+/// Bytes a registry entry can grow to: a node list of `max_validators`
+/// keys, the most quality metrics `register_sequence` accepts and a proof of
+/// `max_proof_size` bytes from each of `max_validators` validators, all
+/// behind its key. `None` if that overflows.
+fn sequence_entry_space(limits: &ResourceLimits) -> Option<usize> {
+    let largest = SequenceData {
+        sequence_hash: [0; 32],
+        length: 0,
+        validator_nodes: Vec::new(),
+        timestamp: 0,
+        quality_metrics: QualityMetrics {
+            coverage_depth: 0,
+            quality_score: 0.0,
+            error_rate: 0.0,
+            confidence_intervals: vec![(0.0, 0.0); MAX_CONFIDENCE_INTERVALS],
+            insert_size: Some(InsertSizeStats {
+                proper_pairs: 0,
+                median: 0,
+                mean: 0.0,
+                std_dev: 0.0,
+            }),
+        },
+        validation_proofs: Vec::new(),
+        consensus_status: ConsensusStatus::Pending,
+        expiration: 0,
+        merkle_root: [0; 32],
+        zk_commitment: [0; 32],
+        payer: Pubkey::default(),
+    };
+    let per_validator =
+        (limits.max_proof_size as usize).checked_add(std::mem::size_of::<Pubkey>())?;
+    (limits.max_validators as usize)
+        .checked_mul(per_validator)?
+        .checked_add(borsh::object_length(&largest).ok()?)?
+        .checked_add(32)
+}
+
+/// Fails with `AccountDataTooSmall` unless `account` holds `state` with the
+/// registry full: `sequence_capacity` entries, each at `sequence_entry_space`.
+/// Checked whenever the fixed part of the state grows, so that registering
+/// sequences and validations cannot run out of room.
+fn require_registry_space(account: &AccountInfo, state: &SequenceValidatorState) -> ProgramResult {
+    let encoded = borsh::object_length(state).map_err(|_| ProgramError::InvalidAccountData)?;
+    let entries = borsh::object_length(&state.validated_sequences)
+        .map_err(|_| ProgramError::InvalidAccountData)?;
+    let required = sequence_entry_space(&state.resource_limits)
+        .and_then(|entry| entry.checked_mul(sequence_capacity(&state.resource_limits)))
+        .and_then(|reserved| reserved.checked_add(encoded - entries + std::mem::size_of::<u32>()));
+    match required {
+        Some(required) if required <= account.data_len() => Ok(()),
+        _ => Err(ProgramError::AccountDataTooSmall),
+    }
+}
+
+fn require_owner(state: &SequenceValidatorState, owner: &AccountInfo) -> ProgramResult {
+    require_signer(owner)?;
+    if state.owner != *owner.key {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+fn initialize_state(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_validations: u8,
+    sequence_ttl: i64,
+    validation_thresholds: ValidationThresholds,
+    resource_limits: ResourceLimits,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+    let state_account = next_account_info(accounts)?;
+    let owner = next_account_info(accounts)?;
+    require_signer(owner)?;
+    validation_thresholds.validate()?;
+    match load_state(program_id, state_account) {
+        Err(ProgramError::UninitializedAccount) => {}
+        Err(e) => return Err(e),
+        Ok(_) => return Err(ProgramError::AccountAlreadyInitialized),
+    }
+
+    let state = SequenceValidatorState {
+        owner: *owner.key,
+        validated_sequences: HashMap::new(),
+        validators: Vec::new(),
+        min_validations,
+        last_cleanup: Clock::get()?.unix_timestamp,
+        sequence_ttl,
+        validation_thresholds,
+        resource_limits,
+    };
+    require_registry_space(state_account, &state)?;
+    save_state(state_account, &state)
+}
+
+/// Records the sequence in the registry, then hands the remaining accounts to
+/// `store_sequence`.
+fn register_sequence(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    sequence: DNASequence,
+    quality_metrics: QualityMetrics,
+    merkle_root: [u8; 32],
+    zk_commitment: [u8; 32],
+) -> ProgramResult {
+    let (state_account, sequence_accounts) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let mut state = load_state(program_id, state_account)?;
+    if state
+        .validated_sequences
+        .contains_key(&sequence.sequence_hash)
+    {
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    if state.validated_sequences.len() >= sequence_capacity(&state.resource_limits) {
+        return Err(ValidationError::MaxSequencesReached.into());
+    }
+    if quality_metrics.confidence_intervals.len() > MAX_CONFIDENCE_INTERVALS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let sequence_data = SequenceData {
+        sequence_hash: sequence.sequence_hash,
+        length: sequence.metadata.length,
+        validator_nodes: state
+            .validators
+            .iter()
+            .map(|validator| validator.pubkey)
+            .collect(),
+        timestamp,
+        quality_metrics,
+        validation_proofs: Vec::new(),
+        consensus_status: ConsensusStatus::Pending,
+        expiration: timestamp.saturating_add(state.sequence_ttl),
+        merkle_root,
+        zk_commitment,
+        payer: *sequence_accounts
+            .get(1)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?
+            .key,
+    };
+    state
+        .validated_sequences
+        .insert(sequence.sequence_hash, sequence_data);
+    store_sequence(
+        program_id,
+        state_account.key,
+        sequence_accounts,
+        sequence,
+        &state.resource_limits,
+    )?;
+    save_state(state_account, &state)
+}
+
+/// Applies the checks `ConsensusManager::process_validation` makes off-chain,
+/// with the cluster clock in place of the proof's own timestamp for expiry
+/// and calibration age, and zk proofs matched against the pinned commitment
+/// without replaying them. Each validator holds one proof per sequence: a
+/// later one replaces it.
+fn submit_validation(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    proof: ValidationProof,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+    let state_account = next_account_info(accounts)?;
+    let sequence_account = next_account_info(accounts)?;
+    let validator = next_account_info(accounts)?;
+    require_signer(validator)?;
+    let mut state = load_state(program_id, state_account)?;
+    let mut sequence = load_sequence(
+        program_id,
+        state_account.key,
+        sequence_account,
+        &proof.sequence_hash,
+    )?;
+
+    if proof.validator != *validator.key {
+        return Err(ConsensusError::InvalidSignature.into());
+    }
+    let validator_info = state
+        .validators
+        .iter()
+        .find(|info| info.pubkey == proof.validator)
+        .cloned()
+        .ok_or(ConsensusError::UnknownValidator)?;
+    // Measured as stored, against the room `sequence_account_space` reserves.
+    if borsh::object_length(&proof).map_err(|_| ProgramError::InvalidArgument)?
+        > state.resource_limits.max_proof_size as usize
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    let validated = state
+        .validated_sequences
+        .values()
+        .filter(|data| {
+            data.sequence_hash != proof.sequence_hash
+                && data
+                    .validation_proofs
+                    .iter()
+                    .any(|seen| seen.validator == proof.validator)
+        })
+        .count();
+    if validated >= state.resource_limits.max_sequences_per_validator as usize {
+        return Err(ValidationError::MaxSequencesReached.into());
+    }
+    let now = Clock::get()?.unix_timestamp;
+    let thresholds = state.validation_thresholds.clone();
+    let sequence_data = state
+        .validated_sequences
+        .get_mut(&proof.sequence_hash)
+        .ok_or(ConsensusError::SequenceMismatch)?;
+    if sequence_data.consensus_status.is_settled() {
+        return Err(ConsensusError::SequenceSettled.into());
+    }
+    if now > sequence_data.expiration
+        || proof.timestamp < sequence_data.timestamp
+        || proof.timestamp > sequence_data.expiration
+    {
+        return Err(ConsensusError::StaleProof.into());
+    }
+    let earlier = sequence_data
+        .validation_proofs
+        .iter()
+        .position(|seen| seen.validator == proof.validator);
+    // Validators removed since keep their proofs, so the room reserved for
+    // `max_validators` of them can run out.
+    if earlier.is_none()
+        && sequence_data.validation_proofs.len() >= state.resource_limits.max_validators as usize
+    {
+        return Err(ValidationError::MaxValidatorsReached.into());
+    }
+    if let Some(index) = earlier {
+        let seen = &sequence_data.validation_proofs[index];
+        if seen.signing_digest() == proof.signing_digest() {
+            return Err(ConsensusError::ReplayedProof.into());
+        }
+        if proof.timestamp <= seen.timestamp {
+            return Err(ConsensusError::StaleProof.into());
+        }
+    }
+    proof.verify_signature().map_err(|e| match e {
+        SignatureError::Missing | SignatureError::Malformed => ConsensusError::MalformedSignature,
+        _ => ConsensusError::InvalidSignature,
+    })?;
+    if proof.verdict.is_accept() {
+        if ProofValidator::default()
+            .verify_acceptance_commitment(
+                &proof.merkle_root,
+                &proof.zk_proof,
+                &sequence_data.merkle_root,
+                &sequence_data.zk_commitment,
+            )
+            .is_err()
+        {
+            return Err(ConsensusError::InvalidProof.into());
+        }
+        if !thresholds.accepts_quality(&sequence_data.quality_metrics) {
+            return Err(ConsensusError::QualityBelowThreshold.into());
+        }
+        if let Some(policy) = &thresholds.calibration {
+            policy
+                .weight(&validator_info, &proof, now)
+                .map_err(ConsensusError::CalibrationFailed)?;
+        }
+    }
+
+    // A validator's new vote replaces its earlier one, so each keeps to the
+    // one proof of room reserved for it.
+    match earlier {
+        Some(index) => {
+            sequence_data.validation_proofs[index] = proof.clone();
+            let stored = sequence
+                .validations
+                .iter_mut()
+                .find(|seen| seen.validator == proof.validator)
+                .ok_or(ProgramError::InvalidAccountData)?;
+            *stored = proof;
+        }
+        None => {
+            sequence_data.validation_proofs.push(proof.clone());
+            sequence.validations.push(proof);
+        }
+    }
+    save_sequence(sequence_account, &sequence)?;
+    save_state(state_account, &state)
+}
+
+/// Settles the sequence with `settle_consensus`, counting the registered
+/// validators and requiring `min_validations` of them for any outcome. A
+/// sequence still unsettled past its expiration becomes `Expired`.
+fn finalize_consensus(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    sequence_hash: [u8; 32],
+) -> ProgramResult {
+    let state_account = next_account_info(&mut accounts.iter())?;
+    let mut state = load_state(program_id, state_account)?;
+    let now = Clock::get()?.unix_timestamp;
+    let quorum = state.min_validations as usize;
+    let percentage = state.validation_thresholds.consensus_percentage;
+    let sequence_data = state
+        .validated_sequences
+        .get(&sequence_hash)
+        .ok_or(ConsensusError::SequenceMismatch)?;
+    if sequence_data.consensus_status.is_settled() {
+        return Err(ConsensusError::SequenceSettled.into());
+    }
+
+    let thresholds = &state.validation_thresholds;
+    let vote_weight =
+        |info: &ValidatorInfo, proof: &ValidationProof| thresholds.vote_weight(info, proof, now);
+    let status = match settle_consensus(
+        &state.validators,
+        &sequence_data.validation_proofs,
+        quorum,
+        percentage,
+        vote_weight,
+    ) {
+        ConsensusStatus::Pending | ConsensusStatus::Disputed if now > sequence_data.expiration => {
+            ConsensusStatus::Expired
+        }
+        ConsensusStatus::Pending => {
+            let accepting: HashMap<Pubkey, u8> = state
+                .validators
+                .iter()
+                .filter_map(|info| {
+                    let proof = sequence_data
+                        .validation_proofs
+                        .iter()
+                        .rev()
+                        .find(|proof| proof.validator == info.pubkey)?;
+                    proof
+                        .verdict
+                        .is_accept()
+                        .then(|| (info.pubkey, vote_weight(info, proof)))
+                })
+                .collect();
+            msg!(
+                "consensus not reached: {:?}",
+                weigh_consensus(&state.validators, &accepting, quorum, percentage)
+            );
+            return Ok(());
+        }
+        status => status,
+    };
+    msg!("sequence {:?}", status);
+    let sequence_data = state
+        .validated_sequences
+        .get_mut(&sequence_hash)
+        .ok_or(ConsensusError::SequenceMismatch)?;
+    sequence_data.consensus_status = status;
+    save_state(state_account, &state)
+}
+
+fn revoke_sequence(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    sequence_hash: [u8; 32],
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+    let sequence_account = next_account_info(accounts)?;
+    let state_account = next_account_info(accounts)?;
+    let owner = next_account_info(accounts)?;
+    let mut state = load_state(program_id, state_account)?;
+    require_owner(&state, owner)?;
+    load_sequence(
+        program_id,
+        state_account.key,
+        sequence_account,
+        &sequence_hash,
+    )?;
+
+    if state.validated_sequences.remove(&sequence_hash).is_none() {
+        return Err(ConsensusError::SequenceMismatch.into());
+    }
+    close_sequence(sequence_account, owner)?;
+    save_state(state_account, &state)
+}
+
+/// Closes a sequence account: its rent goes to `recipient` and the runtime
+/// purges it once it holds no lamports, so the address can be used again.
+fn close_sequence(sequence_account: &AccountInfo, recipient: &AccountInfo) -> ProgramResult {
+    let lamports = recipient
+        .lamports()
+        .checked_add(sequence_account.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **recipient.try_borrow_mut_lamports()? = lamports;
+    **sequence_account.try_borrow_mut_lamports()? = 0;
+    sequence_account.try_borrow_mut_data()?.fill(0);
+    sequence_account.assign(&system_program::ID);
+    Ok(())
+}
+
+fn register_validator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    validator: ValidatorInfo,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+    let state_account = next_account_info(accounts)?;
+    let owner = next_account_info(accounts)?;
+    let mut state = load_state(program_id, state_account)?;
+    require_owner(&state, owner)?;
+
+    if state
+        .validators
+        .iter()
+        .any(|info| info.pubkey == validator.pubkey)
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    if state.validators.len() >= state.resource_limits.max_validators as usize {
+        return Err(ValidationError::MaxValidatorsReached.into());
+    }
+    state.validators.push(validator);
+    require_registry_space(state_account, &state)?;
+    save_state(state_account, &state)
+}
+
+fn remove_validator(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    validator: Pubkey,
+) -> ProgramResult {
+    let accounts = &mut accounts.iter();
+    let state_account = next_account_info(accounts)?;
+    let owner = next_account_info(accounts)?;
+    let mut state = load_state(program_id, state_account)?;
+    require_owner(&state, owner)?;
+
+    let count = state.validators.len();
+    state.validators.retain(|info| info.pubkey != validator);
+    if state.validators.len() == count {
+        return Err(ConsensusError::UnknownValidator.into());
+    }
+    save_state(state_account, &state)
+}
+
+/// Drops the named entries whose validation window closed without a
+/// verdict, that is unsettled past their `expiration` or finalized as
+/// `Expired`, and closes their accounts, refunding the rent to the payer
+/// recorded at registration. Confirmed and rejected sequences are the
+/// registry's record; only the owner removes them, with `revoke_sequence`.
+fn expire_sequences(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    sequence_hashes: Vec<[u8; 32]>,
+) -> ProgramResult {
+    let (state_account, closing) = accounts
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if closing.len() != 2 * sequence_hashes.len() {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    let mut state = load_state(program_id, state_account)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    for (sequence_hash, pair) in sequence_hashes.iter().zip(closing.chunks(2)) {
+        let (sequence_account, payer) = (&pair[0], &pair[1]);
+        load_sequence(
+            program_id,
+            state_account.key,
+            sequence_account,
+            sequence_hash,
+        )?;
+        let data = state
+            .validated_sequences
+            .get(sequence_hash)
+            .ok_or(ConsensusError::SequenceMismatch)?;
+        match data.consensus_status {
+            ConsensusStatus::Confirmed | ConsensusStatus::Rejected => {
+                return Err(ConsensusError::SequenceSettled.into())
+            }
+            ConsensusStatus::Expired => {}
+            ConsensusStatus::Pending | ConsensusStatus::Disputed if now > data.expiration => {}
+            ConsensusStatus::Pending | ConsensusStatus::Disputed => {
+                return Err(ProgramError::InvalidArgument)
+            }
+        }
+        if *payer.key != data.payer {
+            return Err(ProgramError::InvalidArgument);
+        }
+        state.validated_sequences.remove(sequence_hash);
+        close_sequence(sequence_account, payer)?;
+    }
+    state.last_cleanup = now;
+    msg!("expired {} sequences", sequence_hashes.len());
+    save_state(state_account, &state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bank::{LocalBank, PROGRAM_ID};
+    use super::instruction;
+    use super::*;
+    use crate::model::{NucleotideSequence, SequenceMetadata, SequencerModel};
+    use crate::proof::{
+        HashChainBackend, MerkleTree, ProofError, RejectReason, ZkParameters, ZkProofBuilder,
+        ZkThresholds,
+    };
+    use ed25519_dalek::SigningKey;
+    use solana_account::ReadableAccount;
+    use solana_program::instruction::InstructionError;
+
+    const NOW: i64 = 1_700_000_000;
+
+    /// What the runtime reports for an instruction that fails with `err`.
+    fn failure(err: impl Into<ProgramError>) -> Result<(), InstructionError> {
+        Err(InstructionError::from(u64::from(err.into())))
+    }
+
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn validator_key(seed: u8) -> Pubkey {
+        Pubkey::new_from_array(signing_key(seed).verifying_key().to_bytes())
+    }
+
+    fn quality_metrics() -> QualityMetrics {
+        QualityMetrics {
+            coverage_depth: 35,
+            quality_score: 32.5,
+            error_rate: 0.0009,
+            confidence_intervals: vec![],
+            insert_size: None,
+        }
+    }
+
+    fn resource_limits() -> ResourceLimits {
+        ResourceLimits {
+            max_sequences_per_validator: 2,
+            max_proof_size: 1024,
+            max_validators: 4,
+        }
+    }
+
+    fn sample_sequence(nucleotides: &NucleotideSequence) -> DNASequence {
+        let metadata =
+            SequenceMetadata::new(nucleotides, "E. coli".to_string(), "S-1".to_string(), NOW);
+        DNASequence::new(nucleotides, metadata)
+    }
+
+    /// A zk proof of `nucleotides` and the commitment to register with it.
+    fn sample_zk_proof(nucleotides: &NucleotideSequence) -> ([u8; 32], Vec<u8>, [u8; 32]) {
+        let tree = MerkleTree::from_sequence(nucleotides);
+        let mut builder = ZkProofBuilder::new();
+        builder
+            .add_length_proof(nucleotides.len())
+            .add_merkle_proof(&tree);
+        let zk_proof = builder.build().unwrap();
+        let commitment = ProofValidator::default()
+            .zk_commitment(&tree.root(), &zk_proof)
+            .unwrap();
+        (tree.root(), zk_proof, commitment)
+    }
+
+    /// A bank with an initialized registry at `[1; 32]`, owned by `[2; 32]`,
+    /// listing the validators with the given seeds.
+    fn bank_with_registry(validator_seeds: &[u8], limits: ResourceLimits) -> LocalBank {
+        let mut bank = LocalBank::new(NOW);
+        let (state, owner) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+        );
+        bank.add_account(state, 1_000_000, 65_536, PROGRAM_ID);
+        bank.process(instruction::initialize_state(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            2,
+            3600,
+            ValidationThresholds::default(),
+            limits,
+        ))
+        .unwrap();
+        for &seed in validator_seeds {
+            bank.process(instruction::register_validator(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                ValidatorInfo::new(validator_key(seed)),
+            ))
+            .unwrap();
+        }
+        bank
+    }
+
+    #[test]
+    fn test_instruction_dispatch() {
+        let mut bank = LocalBank::new(NOW);
+        let (state, owner) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+        );
+        bank.add_account(state, 1_000_000, 65_536, PROGRAM_ID);
+
+        assert_eq!(
+            process_instruction(&PROGRAM_ID, &[], &[0xff]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+        let mut unsigned = instruction::initialize_state(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            2,
+            3600,
+            ValidationThresholds::default(),
+            resource_limits(),
+        );
+        unsigned.accounts[1].is_signer = false;
+        assert_eq!(
+            bank.process(unsigned.clone()),
+            failure(ProgramError::MissingRequiredSignature)
+        );
+        let unanimous = ValidationThresholds {
+            consensus_percentage: 101,
+            ..ValidationThresholds::default()
+        };
+        assert_eq!(
+            bank.process(instruction::initialize_state(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                2,
+                3600,
+                unanimous,
+                resource_limits()
+            )),
+            failure(ValidationError::InvalidConsensusPercentage(101))
+        );
+        let initialize = instruction::initialize_state(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            2,
+            3600,
+            ValidationThresholds::default(),
+            resource_limits(),
+        );
+        assert_eq!(bank.process(initialize.clone()), Ok(()));
+        assert_eq!(
+            bank.process(initialize),
+            failure(ProgramError::AccountAlreadyInitialized)
+        );
+
+        let validator = ValidatorInfo::new(validator_key(7));
+        let intruder = Pubkey::new_from_array([3; 32]);
+        assert_eq!(
+            bank.process(instruction::register_validator(
+                &PROGRAM_ID,
+                &state,
+                &intruder,
+                validator.clone()
+            )),
+            failure(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            bank.process(instruction::register_validator(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                validator
+            )),
+            Ok(())
+        );
+        let registry = bank.state(&state);
+        assert_eq!(
+            (
+                registry.owner,
+                registry.validators.len(),
+                registry.last_cleanup
+            ),
+            (owner, 1, NOW)
+        );
+
+        // Accounts the program does not own are never read as its state.
+        let foreign = Pubkey::new_from_array([4; 32]);
+        bank.add_account(foreign, 0, 64, Pubkey::default());
+        assert_eq!(
+            bank.process(instruction::finalize_consensus(
+                &PROGRAM_ID,
+                &foreign,
+                [0; 32]
+            )),
+            failure(ProgramError::IncorrectProgramId)
+        );
+    }
+
+    #[test]
+    fn test_sequence_registration_and_consensus() {
+        let mut bank = bank_with_registry(&[1, 2, 3], resource_limits());
+        let (state, owner, payer) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([5; 32]),
+        );
+
+        let nucleotides = NucleotideSequence::from_ascii(&b"GATTACA".repeat(40)).unwrap();
+        let sequence = sample_sequence(&nucleotides);
+        let hash = sequence.sequence_hash;
+        let (address, _) = find_sequence_address(&PROGRAM_ID, &state, &hash);
+        let space = sequence_account_space(&sequence, &resource_limits());
+        let (root, zk_proof, commitment) = sample_zk_proof(&nucleotides);
+        let register = instruction::register_sequence(
+            &PROGRAM_ID,
+            &state,
+            &payer,
+            sequence,
+            quality_metrics(),
+            root,
+            commitment,
+        );
+        assert_eq!(bank.process(register.clone()), Ok(()));
+        let account = bank.account(&address);
+        assert_eq!(
+            (*account.owner(), account.data().len()),
+            (PROGRAM_ID, space)
+        );
+        assert_eq!(account.lamports(), Rent::default().minimum_balance(space));
+        assert_eq!(bank.sequence(&state, &hash).metadata.organism, "E. coli");
+        assert_eq!(
+            bank.process(register),
+            failure(ProgramError::AccountAlreadyInitialized)
+        );
+        assert_eq!(
+            bank.state(&state).validated_sequences[&hash].expiration,
+            NOW + 3600
+        );
+
+        let proof_from = |seed: u8| {
+            ValidationProof::new_signed(&signing_key(seed), hash, root, zk_proof.clone(), NOW + 100)
+        };
+
+        let first = proof_from(1);
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                proof_from(4)
+            )),
+            failure(ConsensusError::UnknownValidator)
+        );
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                first.clone()
+            )),
+            Ok(())
+        );
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                first.clone()
+            )),
+            failure(ConsensusError::ReplayedProof)
+        );
+        let mut forged = proof_from(2);
+        forged.merkle_root = [9; 32];
+        assert_eq!(
+            bank.process(instruction::submit_validation(&PROGRAM_ID, &state, forged)),
+            failure(ConsensusError::InvalidSignature)
+        );
+        // A zk proof with commitments other than the registered ones fails,
+        // however well its chains check out.
+        let mut inflated = ZkProofBuilder::new();
+        inflated
+            .add_length_proof(nucleotides.len())
+            .add_coverage_proof(90)
+            .add_merkle_proof(&MerkleTree::from_sequence(&nucleotides));
+        let overclaimed = ValidationProof::new_signed(
+            &signing_key(2),
+            hash,
+            root,
+            inflated.build().unwrap(),
+            NOW + 100,
+        );
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                overclaimed
+            )),
+            failure(ConsensusError::InvalidProof)
+        );
+        assert_eq!(bank.sequence(&state, &hash).validations, vec![first]);
+
+        // A later proof replaces the validator's earlier one; an older one is
+        // refused.
+        let revised =
+            ValidationProof::new_signed(&signing_key(1), hash, root, zk_proof.clone(), NOW + 200);
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                revised.clone()
+            )),
+            Ok(())
+        );
+        let backdated =
+            ValidationProof::new_signed(&signing_key(1), hash, root, zk_proof.clone(), NOW + 50);
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                backdated
+            )),
+            failure(ConsensusError::StaleProof)
+        );
+        assert_eq!(
+            bank.sequence(&state, &hash).validations,
+            vec![revised.clone()]
+        );
+        assert_eq!(
+            bank.state(&state).validated_sequences[&hash].validation_proofs,
+            vec![revised]
+        );
+
+        // One of three validators: still pending.
+        bank.process(instruction::finalize_consensus(&PROGRAM_ID, &state, hash))
+            .unwrap();
+        assert_eq!(
+            bank.state(&state).validated_sequences[&hash].consensus_status,
+            ConsensusStatus::Pending
+        );
+
+        // Validator 2's data yields another root. Its acceptance is recorded as
+        // dissent, and with the acceptances split across roots the sequence
+        // is disputed until it corrects its vote.
+        let (other_root, other_proof, _) =
+            sample_zk_proof(&NucleotideSequence::from_ascii(&b"CATTAG".repeat(40)).unwrap());
+        let dissent =
+            ValidationProof::new_signed(&signing_key(2), hash, other_root, other_proof, NOW + 100);
+        bank.process(instruction::submit_validation(&PROGRAM_ID, &state, dissent))
+            .unwrap();
+        bank.process(instruction::submit_validation(
+            &PROGRAM_ID,
+            &state,
+            proof_from(3),
+        ))
+        .unwrap();
+        bank.process(instruction::finalize_consensus(&PROGRAM_ID, &state, hash))
+            .unwrap();
+        assert_eq!(
+            bank.state(&state).validated_sequences[&hash].consensus_status,
+            ConsensusStatus::Disputed
+        );
+        let corrected =
+            ValidationProof::new_signed(&signing_key(2), hash, root, zk_proof.clone(), NOW + 300);
+        bank.process(instruction::submit_validation(
+            &PROGRAM_ID,
+            &state,
+            corrected,
+        ))
+        .unwrap();
+        bank.process(instruction::finalize_consensus(&PROGRAM_ID, &state, hash))
+            .unwrap();
+        assert_eq!(
+            bank.state(&state).validated_sequences[&hash].consensus_status,
+            ConsensusStatus::Confirmed
+        );
+        assert_eq!(bank.sequence(&state, &hash).validations.len(), 3);
+
+        assert_eq!(
+            bank.process(instruction::revoke_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                hash
+            )),
+            failure(ProgramError::InvalidArgument)
+        );
+        let owner_lamports = bank.account(&owner).lamports();
+        // The runtime keeps the program from touching accounts passed
+        // read-only.
+        let mut readonly = instruction::revoke_sequence(&PROGRAM_ID, &state, &owner, hash);
+        readonly.accounts[0].is_writable = false;
+        assert_eq!(
+            bank.process(readonly),
+            Err(InstructionError::ReadonlyLamportChange)
+        );
+        bank.process(instruction::revoke_sequence(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            hash,
+        ))
+        .unwrap();
+        assert!(bank.state(&state).validated_sequences.is_empty());
+        let closed = bank.account(&address);
+        assert_eq!(
+            (closed.lamports(), *closed.owner()),
+            (0, system_program::ID)
+        );
+        assert_eq!(
+            bank.account(&owner).lamports(),
+            owner_lamports + Rent::default().minimum_balance(space)
+        );
+    }
+
+    #[test]
+    fn test_sequence_account_checks() {
+        let mut bank = bank_with_registry(&[1], resource_limits());
+        let (state, payer) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([5; 32]),
+        );
+        let nucleotides = NucleotideSequence::from_ascii(b"ACGTACGTAC").unwrap();
+        let sequence = sample_sequence(&nucleotides);
+        let hash = sequence.sequence_hash;
+        let (address, _) = find_sequence_address(&PROGRAM_ID, &state, &hash);
+        let register = instruction::register_sequence(
+            &PROGRAM_ID,
+            &state,
+            &payer,
+            sequence.clone(),
+            quality_metrics(),
+            [0; 32],
+            [0; 32],
+        );
+
+        let mut elsewhere = register.clone();
+        elsewhere.accounts[1].pubkey = Pubkey::new_from_array([6; 32]);
+        assert_eq!(bank.process(elsewhere), failure(ProgramError::InvalidSeeds));
+        let mut unsigned = register.clone();
+        unsigned.accounts[2].is_signer = false;
+        assert_eq!(
+            bank.process(unsigned),
+            failure(ProgramError::MissingRequiredSignature)
+        );
+        let mut impostor = register.clone();
+        impostor.accounts[3].pubkey = Pubkey::new_from_array([7; 32]);
+        assert_eq!(
+            bank.process(impostor),
+            failure(ProgramError::IncorrectProgramId)
+        );
+        let mut prevalidated = sequence.clone();
+        prevalidated.validations.push(ValidationProof::new_signed(
+            &signing_key(1),
+            hash,
+            [0; 32],
+            vec![],
+            NOW,
+        ));
+        assert_eq!(
+            bank.process(instruction::register_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                prevalidated,
+                quality_metrics(),
+                [0; 32],
+                [0; 32]
+            )),
+            failure(ProgramError::InvalidArgument)
+        );
+        // Failed instructions leave nothing behind.
+        assert!(bank.state(&state).validated_sequences.is_empty());
+
+        // Another registry registering the hash first gets its own address
+        // and cannot stand in for this registry's account.
+        let rival = Pubkey::new_from_array([8; 32]);
+        bank.add_account(rival, 1_000_000, 65_536, PROGRAM_ID);
+        bank.process(instruction::initialize_state(
+            &PROGRAM_ID,
+            &rival,
+            &payer,
+            1,
+            3600,
+            ValidationThresholds::default(),
+            resource_limits(),
+        ))
+        .unwrap();
+        bank.process(instruction::register_sequence(
+            &PROGRAM_ID,
+            &rival,
+            &payer,
+            sequence.clone(),
+            quality_metrics(),
+            [0; 32],
+            [0; 32],
+        ))
+        .unwrap();
+        let (squatted, _) = find_sequence_address(&PROGRAM_ID, &rival, &hash);
+        assert_ne!(squatted, address);
+
+        // Lamports sent to the address beforehand do not block creation.
+        bank.add_account(address, 1_000, 0, system_program::ID);
+        assert_eq!(bank.process(register), Ok(()));
+        let mut misrouted = instruction::submit_validation(
+            &PROGRAM_ID,
+            &state,
+            ValidationProof::new_signed(&signing_key(1), hash, [0; 32], vec![], NOW + 100),
+        );
+        misrouted.accounts[1].pubkey = squatted;
+        assert_eq!(bank.process(misrouted), failure(ProgramError::InvalidSeeds));
+        let space = sequence_account_space(&sequence, &resource_limits());
+        assert_eq!(
+            bank.account(&address).lamports(),
+            Rent::default().minimum_balance(space)
+        );
+        assert_eq!(*bank.account(&address).owner(), PROGRAM_ID);
+
+        // Proofs larger than the registry allows are refused before they can
+        // overrun the space reserved for them.
+        let mut oversized =
+            ValidationProof::new_signed(&signing_key(1), hash, [0; 32], vec![0; 4096], NOW + 100);
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                oversized.clone()
+            )),
+            failure(ProgramError::InvalidArgument)
+        );
+        oversized.sequence_hash = [3; 32];
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                oversized
+            )),
+            failure(ProgramError::IncorrectProgramId)
+        );
+
+        // Accounts created through a cross-program invocation hold at most
+        // `MAX_PERMITTED_DATA_INCREASE` bytes.
+        let limits = ResourceLimits {
+            max_sequences_per_validator: 1,
+            max_proof_size: 4096,
+            max_validators: 3,
+        };
+        let mut roomy = bank_with_registry(&[1], limits);
+        assert_eq!(
+            roomy.process(instruction::register_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                sequence,
+                quality_metrics(),
+                [0; 32],
+                [0; 32]
+            )),
+            failure(ProgramError::InvalidRealloc)
+        );
+    }
+
+    #[test]
+    fn test_registry_limits_and_expiry() {
+        let limits = ResourceLimits {
+            max_sequences_per_validator: 1,
+            max_proof_size: 4096,
+            max_validators: 2,
+        };
+        let mut bank = bank_with_registry(&[1, 2], limits.clone());
+        let (state, owner, payer) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([5; 32]),
+        );
+
+        assert_eq!(
+            bank.process(instruction::register_validator(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                ValidatorInfo::new(validator_key(3))
+            )),
+            failure(ValidationError::MaxValidatorsReached)
+        );
+        assert_eq!(
+            bank.process(instruction::remove_validator(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                validator_key(2)
+            )),
+            failure(ProgramError::InvalidArgument)
+        );
+        bank.process(instruction::remove_validator(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            validator_key(2),
+        ))
+        .unwrap();
+        assert_eq!(
+            bank.process(instruction::remove_validator(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                validator_key(2)
+            )),
+            failure(ConsensusError::UnknownValidator)
+        );
+        bank.process(instruction::register_validator(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            ValidatorInfo::new(validator_key(3)),
+        ))
+        .unwrap();
+        let registered: Vec<Pubkey> = bank
+            .state(&state)
+            .validators
+            .iter()
+            .map(|info| info.pubkey)
+            .collect();
+        assert_eq!(registered, vec![validator_key(1), validator_key(3)]);
+
+        // Two validators with room for one sequence each.
+        let sequences: Vec<DNASequence> = [&b"ACGTACGTAC"[..], b"GATTACAGAT", b"CCGGAATTCC"]
+            .iter()
+            .map(|bases| sample_sequence(&NucleotideSequence::from_ascii(bases).unwrap()))
+            .collect();
+        let (root, zk_proof, commitment) =
+            sample_zk_proof(&NucleotideSequence::from_ascii(b"ACGTACGTAC").unwrap());
+        for sequence in &sequences[..2] {
+            bank.process(instruction::register_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                sequence.clone(),
+                quality_metrics(),
+                root,
+                commitment,
+            ))
+            .unwrap();
+        }
+        assert_eq!(
+            bank.process(instruction::register_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                sequences[2].clone(),
+                quality_metrics(),
+                [0; 32],
+                [0; 32]
+            )),
+            failure(ValidationError::MaxSequencesReached)
+        );
+
+        let proof = |sequence: &DNASequence| {
+            ValidationProof::new_signed(
+                &signing_key(1),
+                sequence.sequence_hash,
+                root,
+                zk_proof.clone(),
+                NOW + 100,
+            )
+        };
+        bank.process(instruction::submit_validation(
+            &PROGRAM_ID,
+            &state,
+            proof(&sequences[0]),
+        ))
+        .unwrap();
+        // Revising a vote does not count as validating another sequence.
+        let revised = ValidationProof::new_signed(
+            &signing_key(1),
+            sequences[0].sequence_hash,
+            root,
+            zk_proof.clone(),
+            NOW + 200,
+        );
+        bank.process(instruction::submit_validation(&PROGRAM_ID, &state, revised))
+            .unwrap();
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                proof(&sequences[1])
+            )),
+            failure(ValidationError::MaxSequencesReached)
+        );
+
+        // Nothing has expired yet; an hour and a second later both entries
+        // have. Their rent goes back to the payer, not to whoever asks, and
+        // their room to new sequences.
+        let expired: Vec<([u8; 32], Pubkey)> = sequences[..2]
+            .iter()
+            .map(|sequence| (sequence.sequence_hash, payer))
+            .collect();
+        assert_eq!(
+            bank.process(instruction::expire_sequences(&PROGRAM_ID, &state, &expired)),
+            failure(ProgramError::InvalidArgument)
+        );
+        bank.set_unix_timestamp(NOW + 3601);
+        assert_eq!(
+            bank.process(instruction::expire_sequences(
+                &PROGRAM_ID,
+                &state,
+                &[(sequences[0].sequence_hash, owner)]
+            )),
+            failure(ProgramError::InvalidArgument)
+        );
+        let payer_lamports = bank.account(&payer).lamports();
+        let rent: u64 = expired
+            .iter()
+            .map(|(hash, _)| {
+                bank.account(&find_sequence_address(&PROGRAM_ID, &state, hash).0)
+                    .lamports()
+            })
+            .sum();
+        bank.process(instruction::expire_sequences(&PROGRAM_ID, &state, &expired))
+            .unwrap();
+        let registry = bank.state(&state);
+        assert!(registry.validated_sequences.is_empty());
+        assert_eq!(registry.last_cleanup, NOW + 3601);
+        assert_eq!(bank.account(&payer).lamports(), payer_lamports + rent);
+        bank.process(instruction::register_sequence(
+            &PROGRAM_ID,
+            &state,
+            &payer,
+            sequences[2].clone(),
+            quality_metrics(),
+            [0; 32],
+            [0; 32],
+        ))
+        .unwrap();
+        // The closed addresses are free to register again.
+        bank.process(instruction::register_sequence(
+            &PROGRAM_ID,
+            &state,
+            &payer,
+            sequences[0].clone(),
+            quality_metrics(),
+            [0; 32],
+            [0; 32],
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_zk_check_compute_budget() {
+        // Proofs claiming the largest thresholds the backend takes. Replaying
+        // their chains would take 3 × 65,536 hashes, far past the budget of
+        // an instruction; matching commitments takes one.
+        let mut bank = bank_with_registry(&[1, 2], resource_limits());
+        let (state, payer) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([5; 32]),
+        );
+        let params = ZkParameters::default();
+        let largest = |bases: &[u8]| {
+            let tree = MerkleTree::from_sequence(&NucleotideSequence::from_ascii(bases).unwrap());
+            let mut builder = ZkProofBuilder::new();
+            builder
+                .require(ZkThresholds {
+                    min_length: params.max_chain * params.length_unit,
+                    min_coverage: params.max_chain as u32,
+                    min_quality: params.max_chain as u32,
+                })
+                .add_length_proof(params.max_chain * params.length_unit)
+                .add_coverage_proof(params.max_chain as u32)
+                .add_quality_proof(&QualityMetrics {
+                    quality_score: params.max_chain as f32,
+                    ..quality_metrics()
+                })
+                .add_merkle_proof(&tree);
+            (tree.root(), builder.build().unwrap())
+        };
+        let (root, zk_proof) = largest(b"GATTACAGAT");
+        let commitment = ProofValidator::default()
+            .zk_commitment(&root, &zk_proof)
+            .unwrap();
+        assert_eq!(
+            ProofValidator::default().verify_acceptance(&root, &zk_proof, &root, &commitment),
+            Ok(())
+        );
+        // Without the replay nothing shows a requirement is met.
+        let demanding = ProofValidator::new(
+            Box::new(HashChainBackend),
+            params.clone(),
+            ZkThresholds {
+                min_coverage: 30,
+                ..ZkThresholds::default()
+            },
+        );
+        assert_eq!(
+            demanding.verify_acceptance_commitment(&root, &zk_proof, &root, &commitment),
+            Err(ProofError::StatementBelowRequirement)
+        );
+        let sequence = sample_sequence(&NucleotideSequence::from_ascii(b"GATTACAGAT").unwrap());
+        let hash = sequence.sequence_hash;
+        bank.process(instruction::register_sequence(
+            &PROGRAM_ID,
+            &state,
+            &payer,
+            sequence,
+            quality_metrics(),
+            root,
+            commitment,
+        ))
+        .unwrap();
+
+        let accept = ValidationProof::new_signed(&signing_key(1), hash, root, zk_proof, NOW + 100);
+        let (other_root, other_proof) = largest(b"CATTAGCATT");
+        let dissent =
+            ValidationProof::new_signed(&signing_key(2), hash, other_root, other_proof, NOW + 100);
+        for proof in [accept, dissent] {
+            bank.process(instruction::submit_validation(&PROGRAM_ID, &state, proof))
+                .unwrap();
+            assert!(
+                bank.compute_units() < 1_000,
+                "{} units",
+                bank.compute_units()
+            );
+        }
+    }
+
+    #[test]
+    fn test_registry_space() {
+        let limits = ResourceLimits {
+            max_sequences_per_validator: 2,
+            max_proof_size: 2048,
+            max_validators: 3,
+        };
+        let mut bank = LocalBank::new(NOW);
+        let (state, owner, payer) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([2; 32]),
+            Pubkey::new_from_array([5; 32]),
+        );
+        let initialize = |limits: &ResourceLimits| {
+            instruction::initialize_state(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                2,
+                3600,
+                ValidationThresholds::default(),
+                limits.clone(),
+            )
+        };
+
+        // Six sequences with three proofs each would not fit the account.
+        bank.add_account(state, 1_000_000, 65_536, PROGRAM_ID);
+        let greedy = ResourceLimits {
+            max_proof_size: 4096,
+            ..limits.clone()
+        };
+        assert_eq!(
+            bank.process(initialize(&greedy)),
+            failure(ProgramError::AccountDataTooSmall)
+        );
+        bank.process(initialize(&limits)).unwrap();
+        for seed in [1, 2, 3] {
+            bank.process(instruction::register_validator(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                ValidatorInfo::new(validator_key(seed)),
+            ))
+            .unwrap();
+        }
+
+        // Fill every entry to its limits: full quality metrics and a proof of
+        // the largest size from each validator, with validators swapped out
+        // to get around the per-validator limit.
+        let metrics = QualityMetrics {
+            confidence_intervals: vec![(30.0, 35.0); MAX_CONFIDENCE_INTERVALS],
+            insert_size: Some(InsertSizeStats {
+                proper_pairs: 1000,
+                median: 350,
+                mean: 352.5,
+                std_dev: 40.0,
+            }),
+            ..quality_metrics()
+        };
+        let largest = |seed: u8, hash: [u8; 32]| {
+            let empty = ValidationProof::new_signed_rejection(
+                &signing_key(seed),
+                hash,
+                [0; 32],
+                vec![],
+                NOW + 100,
+                RejectReason::QualityBelowThreshold,
+            );
+            let padding = limits.max_proof_size as usize - borsh::object_length(&empty).unwrap();
+            ValidationProof::new_signed_rejection(
+                &signing_key(seed),
+                hash,
+                [0; 32],
+                vec![0; padding],
+                NOW + 100,
+                RejectReason::QualityBelowThreshold,
+            )
+        };
+        let mut hashes = Vec::new();
+        for index in 0..6u8 {
+            let sequence = sample_sequence(
+                &NucleotideSequence::from_ascii(&b"ACGT".repeat(index as usize + 1)).unwrap(),
+            );
+            hashes.push(sequence.sequence_hash);
+            bank.process(instruction::register_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                sequence,
+                metrics.clone(),
+                [0; 32],
+                [0; 32],
+            ))
+            .unwrap();
+        }
+        let mut voters = vec![1, 2, 3];
+        for pair in hashes.chunks(2) {
+            for &seed in &voters {
+                for &hash in pair {
+                    bank.process(instruction::submit_validation(
+                        &PROGRAM_ID,
+                        &state,
+                        largest(seed, hash),
+                    ))
+                    .unwrap();
+                }
+            }
+            for seed in voters.iter_mut() {
+                bank.process(instruction::remove_validator(
+                    &PROGRAM_ID,
+                    &state,
+                    &owner,
+                    validator_key(*seed),
+                ))
+                .unwrap();
+                *seed += 3;
+                bank.process(instruction::register_validator(
+                    &PROGRAM_ID,
+                    &state,
+                    &owner,
+                    ValidatorInfo::new(validator_key(*seed)),
+                ))
+                .unwrap();
+            }
+        }
+        let registry = bank.state(&state);
+        assert!(registry
+            .validated_sequences
+            .values()
+            .all(|data| data.validation_proofs.len() == 3));
+
+        // Past the limits: no fourth proof on an entry, no more intervals
+        // and no validator record eating into the reserved room.
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                largest(voters[0], hashes[0])
+            )),
+            failure(ValidationError::MaxValidatorsReached)
+        );
+        bank.process(instruction::remove_validator(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            validator_key(voters[0]),
+        ))
+        .unwrap();
+        let mut bulky = ValidatorInfo::new(validator_key(20));
+        bulky.hardware_specs.sequencer_models = vec![
+            SequencerModel {
+                manufacturer: "Illumina".repeat(256),
+                model: "NovaSeq X".to_string(),
+                serial_number: "LH00001".to_string(),
+                throughput: 16_000,
+                error_rate: 0.001,
+                last_calibration: NOW,
+            };
+            16
+        ];
+        assert_eq!(
+            bank.process(instruction::register_validator(
+                &PROGRAM_ID,
+                &state,
+                &owner,
+                bulky
+            )),
+            failure(ProgramError::AccountDataTooSmall)
+        );
+        bank.process(instruction::revoke_sequence(
+            &PROGRAM_ID,
+            &state,
+            &owner,
+            hashes[5],
+        ))
+        .unwrap();
+        let crowded = QualityMetrics {
+            confidence_intervals: vec![(30.0, 35.0); MAX_CONFIDENCE_INTERVALS + 1],
+            ..quality_metrics()
+        };
+        let sequence = sample_sequence(&NucleotideSequence::from_ascii(b"TTTTTTTT").unwrap());
+        assert_eq!(
+            bank.process(instruction::register_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                sequence,
+                crowded,
+                [0; 32],
+                [0; 32]
+            )),
+            failure(ProgramError::InvalidArgument)
+        );
+    }
+
+    #[test]
+    fn test_rejection_and_expiry() {
+        let mut bank = bank_with_registry(&[1, 2, 3], resource_limits());
+        let (state, payer) = (
+            Pubkey::new_from_array([1; 32]),
+            Pubkey::new_from_array([5; 32]),
+        );
+        let poor = QualityMetrics {
+            coverage_depth: 4,
+            ..quality_metrics()
+        };
+        let mut hashes = Vec::new();
+        for (bases, metrics) in [
+            (&b"ACGTACGTAC"[..], poor),
+            (b"GATTACAGAT", quality_metrics()),
+        ] {
+            let sequence = sample_sequence(&NucleotideSequence::from_ascii(bases).unwrap());
+            hashes.push(sequence.sequence_hash);
+            bank.process(instruction::register_sequence(
+                &PROGRAM_ID,
+                &state,
+                &payer,
+                sequence,
+                metrics,
+                [0; 32],
+                [0; 32],
+            ))
+            .unwrap();
+        }
+        let (rejected, unsettled) = (hashes[0], hashes[1]);
+
+        // Rejections need neither a zk proof nor passing quality metrics.
+        let reject = |seed: u8, hash: [u8; 32]| {
+            ValidationProof::new_signed_rejection(
+                &signing_key(seed),
+                hash,
+                [0; 32],
+                vec![],
+                NOW + 100,
+                RejectReason::QualityBelowThreshold,
+            )
+        };
+        let accept =
+            ValidationProof::new_signed(&signing_key(1), rejected, [0; 32], vec![], NOW + 100);
+        assert_eq!(
+            bank.process(instruction::submit_validation(&PROGRAM_ID, &state, accept)),
+            failure(ConsensusError::InvalidProof)
+        );
+        for seed in [1, 2] {
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                reject(seed, rejected),
+            ))
+            .unwrap();
+        }
+        bank.process(instruction::finalize_consensus(
+            &PROGRAM_ID,
+            &state,
+            rejected,
+        ))
+        .unwrap();
+        assert_eq!(
+            bank.state(&state).validated_sequences[&rejected].consensus_status,
+            ConsensusStatus::Pending
+        );
+        bank.process(instruction::submit_validation(
+            &PROGRAM_ID,
+            &state,
+            reject(3, rejected),
+        ))
+        .unwrap();
+        bank.process(instruction::finalize_consensus(
+            &PROGRAM_ID,
+            &state,
+            rejected,
+        ))
+        .unwrap();
+        assert_eq!(
+            bank.state(&state).validated_sequences[&rejected].consensus_status,
+            ConsensusStatus::Rejected
+        );
+        assert_eq!(
+            bank.process(instruction::finalize_consensus(
+                &PROGRAM_ID,
+                &state,
+                rejected
+            )),
+            failure(ConsensusError::SequenceSettled)
+        );
+
+        bank.process(instruction::submit_validation(
+            &PROGRAM_ID,
+            &state,
+            reject(1, unsettled),
+        ))
+        .unwrap();
+        bank.set_unix_timestamp(NOW + 3601);
+        bank.process(instruction::finalize_consensus(
+            &PROGRAM_ID,
+            &state,
+            unsettled,
+        ))
+        .unwrap();
+        assert_eq!(
+            bank.state(&state).validated_sequences[&unsettled].consensus_status,
+            ConsensusStatus::Expired
+        );
+        assert_eq!(
+            bank.process(instruction::submit_validation(
+                &PROGRAM_ID,
+                &state,
+                reject(2, unsettled)
+            )),
+            failure(ConsensusError::SequenceSettled)
+        );
+
+        // The rejected sequence stays on record; the expired one is closed.
+        assert_eq!(
+            bank.process(instruction::expire_sequences(
+                &PROGRAM_ID,
+                &state,
+                &[(rejected, payer)]
+            )),
+            failure(ConsensusError::SequenceSettled)
+        );
+        bank.process(instruction::expire_sequences(
+            &PROGRAM_ID,
+            &state,
+            &[(unsettled, payer)],
+        ))
+        .unwrap();
+        let registry = bank.state(&state);
+        assert_eq!(
+            registry.validated_sequences.keys().collect::<Vec<_>>(),
+            vec![&rejected]
+        );
+        assert_eq!(
+            bank.account(&find_sequence_address(&PROGRAM_ID, &state, &unsettled).0)
+                .lamports(),
+            0
+        );
+    }
+}
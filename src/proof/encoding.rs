@@ -0,0 +1,837 @@
+use crate::model::{InsertSizeStats, QualityMetrics};
+use crate::proof::{InstrumentRecord, RejectReason, SequenceProof, ValidationProof, Verdict};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+
+/// Newest version byte leading a canonical proof encoding. Bump when a
+/// layout below changes; decoders reject versions they do not know. Each
+/// proof is written with the oldest version able to hold it, so bytes signed
+/// under an earlier version re-encode identically, and decoders refuse any
+/// other version so each proof has exactly one encoding.
+pub const PROOF_ENCODING_VERSION: u8 = 4;
+const INSTRUMENT_VERSION: u8 = 2; // First version carrying `ValidationProof.instrument`
+const INSERT_SIZE_VERSION: u8 = 3; // First version carrying `QualityMetrics.insert_size`
+const VERDICT_VERSION: u8 = 4; // First version carrying `ValidationProof.verdict`
+const VALIDATION_PROOF_KIND: u8 = 1;
+const SEQUENCE_PROOF_KIND: u8 = 2;
+const MAX_CANONICAL_FIELD_BYTES: u32 = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanonicalError {
+    Truncated,
+    TrailingBytes,
+    UnsupportedVersion(u8),
+    UnexpectedKind(u8),
+    FieldTooLarge(u32),
+    InvalidHex,
+    InvalidPubkey,
+    InvalidUtf8,
+    InvalidFlag(u8),
+    InvalidRejectReason(u8),
+}
+
+impl fmt::Display for CanonicalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanonicalError::Truncated => write!(f, "proof encoding is truncated"),
+            CanonicalError::TrailingBytes => write!(f, "proof encoding has trailing bytes"),
+            CanonicalError::UnsupportedVersion(v) => {
+                write!(f, "unsupported proof encoding version {}", v)
+            }
+            CanonicalError::UnexpectedKind(k) => write!(f, "unexpected proof kind {}", k),
+            CanonicalError::FieldTooLarge(len) => {
+                write!(f, "proof field of {} bytes exceeds the limit", len)
+            }
+            CanonicalError::InvalidHex => write!(f, "invalid hex string"),
+            CanonicalError::InvalidPubkey => write!(f, "invalid validator public key"),
+            CanonicalError::InvalidUtf8 => write!(f, "proof text field is not UTF-8"),
+            CanonicalError::InvalidFlag(b) => write!(f, "invalid presence flag {}", b),
+            CanonicalError::InvalidRejectReason(code) => {
+                write!(f, "unknown reject reason code {}", code)
+            }
+        }
+    }
+}
+
+impl From<CanonicalError> for io::Error {
+    fn from(e: CanonicalError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+    }
+}
+
+/// Little-endian writer for the canonical layouts. Variable-length byte
+/// fields carry a u32 length prefix; floats are written as their IEEE-754
+/// bit pattern.
+struct CanonicalWriter {
+    bytes: Vec<u8>,
+}
+
+impl CanonicalWriter {
+    fn new(version: u8, kind: u8) -> Self {
+        Self {
+            bytes: vec![version, kind],
+        }
+    }
+
+    fn put_fixed(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn put_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_i64(&mut self, value: i64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_f32(&mut self, value: f32) {
+        self.put_u32(value.to_bits());
+    }
+
+    fn put_var(&mut self, bytes: &[u8]) {
+        self.put_u32(bytes.len() as u32);
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    fn put_instrument(&mut self, instrument: &InstrumentRecord) {
+        self.put_var(instrument.manufacturer.as_bytes());
+        self.put_var(instrument.model.as_bytes());
+        self.put_var(instrument.serial_number.as_bytes());
+        self.put_i64(instrument.last_calibration);
+    }
+}
+
+struct CanonicalReader<R> {
+    reader: R,
+    version: u8,
+}
+
+impl<R: Read> CanonicalReader<R> {
+    fn new(reader: R, kind: u8) -> Result<Self, CanonicalError> {
+        let mut reader = Self { reader, version: 0 };
+        reader.version = reader.take_u8()?;
+        if !(1..=PROOF_ENCODING_VERSION).contains(&reader.version) {
+            return Err(CanonicalError::UnsupportedVersion(reader.version));
+        }
+        let found = reader.take_u8()?;
+        if found != kind {
+            return Err(CanonicalError::UnexpectedKind(found));
+        }
+        Ok(reader)
+    }
+
+    fn take_fixed<const N: usize>(&mut self) -> Result<[u8; N], CanonicalError> {
+        let mut bytes = [0u8; N];
+        self.reader
+            .read_exact(&mut bytes)
+            .map_err(|_| CanonicalError::Truncated)?;
+        Ok(bytes)
+    }
+
+    fn take_u8(&mut self) -> Result<u8, CanonicalError> {
+        Ok(self.take_fixed::<1>()?[0])
+    }
+
+    fn take_u32(&mut self) -> Result<u32, CanonicalError> {
+        Ok(u32::from_le_bytes(self.take_fixed()?))
+    }
+
+    fn take_i64(&mut self) -> Result<i64, CanonicalError> {
+        Ok(i64::from_le_bytes(self.take_fixed()?))
+    }
+
+    fn take_f32(&mut self) -> Result<f32, CanonicalError> {
+        Ok(f32::from_bits(self.take_u32()?))
+    }
+
+    fn take_var(&mut self) -> Result<Vec<u8>, CanonicalError> {
+        let len = self.take_u32()?;
+        if len > MAX_CANONICAL_FIELD_BYTES {
+            return Err(CanonicalError::FieldTooLarge(len));
+        }
+        let mut bytes = vec![0u8; len as usize];
+        self.reader
+            .read_exact(&mut bytes)
+            .map_err(|_| CanonicalError::Truncated)?;
+        Ok(bytes)
+    }
+
+    fn take_string(&mut self) -> Result<String, CanonicalError> {
+        String::from_utf8(self.take_var()?).map_err(|_| CanonicalError::InvalidUtf8)
+    }
+
+    fn take_flag(&mut self) -> Result<bool, CanonicalError> {
+        match self.take_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            other => Err(CanonicalError::InvalidFlag(other)),
+        }
+    }
+
+    fn take_instrument(&mut self) -> Result<InstrumentRecord, CanonicalError> {
+        Ok(InstrumentRecord {
+            manufacturer: self.take_string()?,
+            model: self.take_string()?,
+            serial_number: self.take_string()?,
+            last_calibration: self.take_i64()?,
+        })
+    }
+}
+
+/// Oldest layout able to hold a validation proof with these fields.
+fn validation_proof_version(has_instrument: bool, verdict: Verdict) -> u8 {
+    if !verdict.is_accept() {
+        VERDICT_VERSION
+    } else if has_instrument {
+        INSTRUMENT_VERSION
+    } else {
+        1
+    }
+}
+
+fn decode_exact<T>(
+    mut bytes: &[u8],
+    decode: impl FnOnce(&mut &[u8]) -> Result<T, CanonicalError>,
+) -> Result<T, CanonicalError> {
+    let value = decode(&mut bytes)?;
+    if !bytes.is_empty() {
+        return Err(CanonicalError::TrailingBytes);
+    }
+    Ok(value)
+}
+
+impl ValidationProof {
+    /// Canonical v1 layout:
+    /// version (u8) | kind = 1 (u8) | validator ([u8; 32]) | sequence_hash ([u8; 32]) |
+    /// merkle_root ([u8; 32]) | zk_proof (u32 LE len + bytes) |
+    /// signature (u32 LE len + bytes) | timestamp (i64 LE).
+    ///
+    /// v2 appends the instrument: manufacturer, model and serial_number
+    /// (each u32 LE len + UTF-8) | last_calibration (i64 LE). Proofs without
+    /// an instrument are written as v1. v3 is never written.
+    ///
+    /// v4 makes the instrument optional and adds the verdict: instrument
+    /// flag (u8, 0 or 1) followed by the v2 record when set | reject reason
+    /// code (u8). Only rejections are written as v4.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        self.encode(&self.signature)
+    }
+
+    /// The canonical encoding with an empty signature field, i.e. exactly
+    /// the bytes a validator signs.
+    pub fn unsigned_canonical_bytes(&self) -> Vec<u8> {
+        self.encode(&[])
+    }
+
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, CanonicalError> {
+        decode_exact(bytes, |reader| Self::decode(reader))
+    }
+
+    fn encode(&self, signature: &[u8]) -> Vec<u8> {
+        let version = validation_proof_version(self.instrument.is_some(), self.verdict);
+        let mut writer = CanonicalWriter::new(version, VALIDATION_PROOF_KIND);
+        writer.put_fixed(self.validator.as_ref());
+        writer.put_fixed(&self.sequence_hash);
+        writer.put_fixed(&self.merkle_root);
+        writer.put_var(&self.zk_proof);
+        writer.put_var(signature);
+        writer.put_i64(self.timestamp);
+        if version >= VERDICT_VERSION {
+            writer.put_fixed(&[self.instrument.is_some() as u8]);
+        }
+        if let Some(instrument) = &self.instrument {
+            writer.put_instrument(instrument);
+        }
+        if let Verdict::Reject(reason) = self.verdict {
+            writer.put_fixed(&[reason.code()]);
+        }
+        writer.bytes
+    }
+
+    fn decode<R: Read>(reader: R) -> Result<Self, CanonicalError> {
+        let mut reader = CanonicalReader::new(reader, VALIDATION_PROOF_KIND)?;
+        let validator = Pubkey::new_from_array(reader.take_fixed()?);
+        let sequence_hash = reader.take_fixed()?;
+        let merkle_root = reader.take_fixed()?;
+        let zk_proof = reader.take_var()?;
+        let signature = reader.take_var()?;
+        let timestamp = reader.take_i64()?;
+        let has_instrument = if reader.version >= VERDICT_VERSION {
+            reader.take_flag()?
+        } else {
+            reader.version >= INSTRUMENT_VERSION
+        };
+        let instrument = if has_instrument {
+            Some(reader.take_instrument()?)
+        } else {
+            None
+        };
+        let verdict = if reader.version >= VERDICT_VERSION {
+            // An acceptance would have been written as v1 or v2.
+            let code = reader.take_u8()?;
+            Verdict::Reject(
+                RejectReason::from_code(code).ok_or(CanonicalError::InvalidRejectReason(code))?,
+            )
+        } else {
+            Verdict::Accept
+        };
+        if reader.version != validation_proof_version(instrument.is_some(), verdict) {
+            return Err(CanonicalError::UnsupportedVersion(reader.version));
+        }
+        Ok(Self {
+            validator,
+            sequence_hash,
+            merkle_root,
+            zk_proof,
+            signature,
+            timestamp,
+            instrument,
+            verdict,
+        })
+    }
+}
+
+impl SequenceProof {
+    /// Canonical v1 layout:
+    /// version (u8) | kind = 2 (u8) | merkle_root ([u8; 32]) |
+    /// coverage_depth (u32 LE) | quality_score (f32 LE) | error_rate (f32 LE) |
+    /// interval count (u32 LE) + (low, high) f32 LE pairs |
+    /// zk_proof (u32 LE len + bytes) | timestamp (i64 LE).
+    ///
+    /// Unchanged in v2. v3 appends the insert sizes: proper_pairs (u32 LE) |
+    /// median (u32 LE) | mean (f32 LE) | std_dev (f32 LE). Proofs without
+    /// them are written as v1. v2 and v4 are never written.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let metrics = &self.quality_metrics;
+        let version = if metrics.insert_size.is_some() {
+            INSERT_SIZE_VERSION
+        } else {
+            1
+        };
+        let mut writer = CanonicalWriter::new(version, SEQUENCE_PROOF_KIND);
+        writer.put_fixed(&self.merkle_root);
+        writer.put_u32(metrics.coverage_depth);
+        writer.put_f32(metrics.quality_score);
+        writer.put_f32(metrics.error_rate);
+        writer.put_u32(metrics.confidence_intervals.len() as u32);
+        for &(low, high) in &metrics.confidence_intervals {
+            writer.put_f32(low);
+            writer.put_f32(high);
+        }
+        writer.put_var(&self.zk_proof);
+        writer.put_i64(self.timestamp);
+        if let Some(insert_size) = &metrics.insert_size {
+            writer.put_u32(insert_size.proper_pairs);
+            writer.put_u32(insert_size.median);
+            writer.put_f32(insert_size.mean);
+            writer.put_f32(insert_size.std_dev);
+        }
+        writer.bytes
+    }
+
+    pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, CanonicalError> {
+        decode_exact(bytes, |reader| Self::decode(reader))
+    }
+
+    fn decode<R: Read>(reader: R) -> Result<Self, CanonicalError> {
+        let mut reader = CanonicalReader::new(reader, SEQUENCE_PROOF_KIND)?;
+        let merkle_root = reader.take_fixed()?;
+        let coverage_depth = reader.take_u32()?;
+        let quality_score = reader.take_f32()?;
+        let error_rate = reader.take_f32()?;
+        let intervals = reader.take_u32()?;
+        if intervals > MAX_CANONICAL_FIELD_BYTES / 8 {
+            return Err(CanonicalError::FieldTooLarge(intervals));
+        }
+        let confidence_intervals = (0..intervals)
+            .map(|_| Ok((reader.take_f32()?, reader.take_f32()?)))
+            .collect::<Result<Vec<_>, CanonicalError>>()?;
+
+        let zk_proof = reader.take_var()?;
+        let timestamp = reader.take_i64()?;
+        let insert_size = if reader.version >= INSERT_SIZE_VERSION {
+            Some(InsertSizeStats {
+                proper_pairs: reader.take_u32()?,
+                median: reader.take_u32()?,
+                mean: reader.take_f32()?,
+                std_dev: reader.take_f32()?,
+            })
+        } else {
+            None
+        };
+        let version = if insert_size.is_some() {
+            INSERT_SIZE_VERSION
+        } else {
+            1
+        };
+        if reader.version != version {
+            return Err(CanonicalError::UnsupportedVersion(reader.version));
+        }
+
+        Ok(Self {
+            merkle_root,
+            quality_metrics: QualityMetrics {
+                coverage_depth,
+                quality_score,
+                error_rate,
+                confidence_intervals,
+                insert_size,
+            },
+            zk_proof,
+            timestamp,
+        })
+    }
+}
+
+// Borsh goes through the canonical layout so on-chain accounts store exactly
+// the bytes that are signed and hashed.
+impl BorshSerialize for ValidationProof {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_canonical_bytes())
+    }
+}
+
+impl BorshDeserialize for ValidationProof {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self::decode(reader)?)
+    }
+}
+
+impl BorshSerialize for SequenceProof {
+    fn serialize<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.to_canonical_bytes())
+    }
+}
+
+impl BorshDeserialize for SequenceProof {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self::decode(reader)?)
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(text: &str) -> Result<Vec<u8>, CanonicalError> {
+    if !text.len().is_multiple_of(2) || !text.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(CanonicalError::InvalidHex);
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).map_err(|_| CanonicalError::InvalidHex))
+        .collect()
+}
+
+fn from_hex32(text: &str) -> Result<[u8; 32], CanonicalError> {
+    from_hex(text)?
+        .try_into()
+        .map_err(|_| CanonicalError::InvalidHex)
+}
+
+/// JSON mirror of the canonical layout for the HTTP clients: same fields in
+/// the same order, byte fields as lower-case hex and the validator in base58.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct ValidationProofJson {
+    version: u8,
+    validator: String,
+    sequence_hash: String,
+    merkle_root: String,
+    zk_proof: String,
+    signature: String,
+    timestamp: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    instrument: Option<InstrumentRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reject_reason: Option<RejectReason>,
+}
+
+impl From<ValidationProof> for ValidationProofJson {
+    fn from(proof: ValidationProof) -> Self {
+        Self {
+            version: validation_proof_version(proof.instrument.is_some(), proof.verdict),
+            validator: proof.validator.to_string(),
+            sequence_hash: to_hex(&proof.sequence_hash),
+            merkle_root: to_hex(&proof.merkle_root),
+            zk_proof: to_hex(&proof.zk_proof),
+            signature: to_hex(&proof.signature),
+            timestamp: proof.timestamp,
+            instrument: proof.instrument,
+            reject_reason: match proof.verdict {
+                Verdict::Accept => None,
+                Verdict::Reject(reason) => Some(reason),
+            },
+        }
+    }
+}
+
+impl TryFrom<ValidationProofJson> for ValidationProof {
+    type Error = CanonicalError;
+
+    fn try_from(json: ValidationProofJson) -> Result<Self, Self::Error> {
+        // The version must match the layout the fields imply, as in the
+        // canonical encoding.
+        let verdict = json.reject_reason.map_or(Verdict::Accept, Verdict::Reject);
+        if json.version != validation_proof_version(json.instrument.is_some(), verdict) {
+            return Err(CanonicalError::UnsupportedVersion(json.version));
+        }
+        Ok(Self {
+            validator: Pubkey::from_str(&json.validator)
+                .map_err(|_| CanonicalError::InvalidPubkey)?,
+            sequence_hash: from_hex32(&json.sequence_hash)?,
+            merkle_root: from_hex32(&json.merkle_root)?,
+            zk_proof: from_hex(&json.zk_proof)?,
+            signature: from_hex(&json.signature)?,
+            timestamp: json.timestamp,
+            instrument: json.instrument,
+            verdict,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SequenceProofJson {
+    version: u8,
+    merkle_root: String,
+    quality_metrics: QualityMetrics,
+    zk_proof: String,
+    timestamp: i64,
+}
+
+impl From<SequenceProof> for SequenceProofJson {
+    fn from(proof: SequenceProof) -> Self {
+        Self {
+            version: if proof.quality_metrics.insert_size.is_some() {
+                INSERT_SIZE_VERSION
+            } else {
+                1
+            },
+            merkle_root: to_hex(&proof.merkle_root),
+            quality_metrics: proof.quality_metrics,
+            zk_proof: to_hex(&proof.zk_proof),
+            timestamp: proof.timestamp,
+        }
+    }
+}
+
+impl TryFrom<SequenceProofJson> for SequenceProof {
+    type Error = CanonicalError;
+
+    fn try_from(json: SequenceProofJson) -> Result<Self, Self::Error> {
+        let version = if json.quality_metrics.insert_size.is_some() {
+            INSERT_SIZE_VERSION
+        } else {
+            1
+        };
+        if json.version != version {
+            return Err(CanonicalError::UnsupportedVersion(json.version));
+        }
+        Ok(Self {
+            merkle_root: from_hex32(&json.merkle_root)?,
+            quality_metrics: json.quality_metrics,
+            zk_proof: from_hex(&json.zk_proof)?,
+            timestamp: json.timestamp,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::SigningKey;
+
+    const VALIDATION_PROOF_GOLDEN: &[u8] =
+        include_bytes!("../../tests/golden/validation_proof_v1.bin");
+    const VALIDATION_PROOF_GOLDEN_JSON: &str =
+        include_str!("../../tests/golden/validation_proof_v1.json");
+    const VALIDATION_PROOF_V2_GOLDEN: &[u8] =
+        include_bytes!("../../tests/golden/validation_proof_v2.bin");
+    const VALIDATION_PROOF_V2_GOLDEN_JSON: &str =
+        include_str!("../../tests/golden/validation_proof_v2.json");
+    const VALIDATION_PROOF_V4_GOLDEN: &[u8] =
+        include_bytes!("../../tests/golden/validation_proof_v4.bin");
+    const VALIDATION_PROOF_V4_GOLDEN_JSON: &str =
+        include_str!("../../tests/golden/validation_proof_v4.json");
+    const SEQUENCE_PROOF_GOLDEN: &[u8] = include_bytes!("../../tests/golden/sequence_proof_v1.bin");
+    const SEQUENCE_PROOF_GOLDEN_JSON: &str =
+        include_str!("../../tests/golden/sequence_proof_v1.json");
+    const SEQUENCE_PROOF_V3_GOLDEN: &[u8] =
+        include_bytes!("../../tests/golden/sequence_proof_v3.bin");
+    const SEQUENCE_PROOF_V3_GOLDEN_JSON: &str =
+        include_str!("../../tests/golden/sequence_proof_v3.json");
+
+    fn golden_validation_proof() -> ValidationProof {
+        ValidationProof::new_signed(
+            &SigningKey::from_bytes(&[7; 32]),
+            [0x11; 32],
+            [0x22; 32],
+            vec![1, 2, 3, 4, 5],
+            1_700_000_100,
+        )
+    }
+
+    fn golden_instrument_proof() -> ValidationProof {
+        ValidationProof::new_signed_with_instrument(
+            &SigningKey::from_bytes(&[7; 32]),
+            [0x11; 32],
+            [0x22; 32],
+            vec![1, 2, 3, 4, 5],
+            1_700_000_100,
+            InstrumentRecord {
+                manufacturer: "Illumina".to_string(),
+                model: "NovaSeq 6000".to_string(),
+                serial_number: "A00123".to_string(),
+                last_calibration: 1_699_000_000,
+            },
+        )
+    }
+
+    fn golden_sequence_proof() -> SequenceProof {
+        SequenceProof {
+            merkle_root: [0x22; 32],
+            quality_metrics: QualityMetrics {
+                coverage_depth: 35,
+                quality_score: 32.5,
+                error_rate: 0.0009765625,
+                confidence_intervals: vec![(31.5, 33.5)],
+                insert_size: None,
+            },
+            zk_proof: vec![9, 8, 7],
+            timestamp: 1_700_000_000,
+        }
+    }
+
+    #[test]
+    fn test_validation_proof_golden_encoding() {
+        let proof = golden_validation_proof();
+        assert_eq!(proof.to_canonical_bytes(), VALIDATION_PROOF_GOLDEN);
+        assert_eq!(borsh::to_vec(&proof).unwrap(), VALIDATION_PROOF_GOLDEN);
+        assert_eq!(
+            serde_json::to_string_pretty(&proof).unwrap(),
+            VALIDATION_PROOF_GOLDEN_JSON.trim_end()
+        );
+
+        // A node that only has the golden bytes must accept the signature.
+        let decoded = ValidationProof::from_canonical_bytes(VALIDATION_PROOF_GOLDEN).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify_signature().is_ok());
+        let from_json: ValidationProof =
+            serde_json::from_str(VALIDATION_PROOF_GOLDEN_JSON).unwrap();
+        assert_eq!(from_json, proof);
+        assert_eq!(
+            ValidationProof::try_from_slice(VALIDATION_PROOF_GOLDEN).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn test_validation_proof_v2_golden_encoding() {
+        let proof = golden_instrument_proof();
+        assert_eq!(proof.to_canonical_bytes(), VALIDATION_PROOF_V2_GOLDEN);
+        assert_eq!(proof.to_canonical_bytes()[0], 2);
+        assert_eq!(
+            serde_json::to_string_pretty(&proof).unwrap(),
+            VALIDATION_PROOF_V2_GOLDEN_JSON.trim_end()
+        );
+
+        let decoded = ValidationProof::from_canonical_bytes(VALIDATION_PROOF_V2_GOLDEN).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify_signature().is_ok());
+        let from_json: ValidationProof =
+            serde_json::from_str(VALIDATION_PROOF_V2_GOLDEN_JSON).unwrap();
+        assert_eq!(from_json, proof);
+
+        // The instrument is signed: dropping it or editing the calibration
+        // invalidates the proof.
+        let mut stripped = proof.clone();
+        stripped.instrument = None;
+        assert!(stripped.verify_signature().is_err());
+        let mut recalibrated = proof;
+        recalibrated.instrument.as_mut().unwrap().last_calibration += 1;
+        assert!(recalibrated.verify_signature().is_err());
+
+        let json = VALIDATION_PROOF_V2_GOLDEN_JSON.replace("\"version\": 2", "\"version\": 1");
+        assert!(serde_json::from_str::<ValidationProof>(&json).is_err());
+    }
+
+    #[test]
+    fn test_validation_proof_v4_golden_encoding() {
+        let proof = ValidationProof::new_signed_rejection(
+            &SigningKey::from_bytes(&[7; 32]),
+            [0x11; 32],
+            [0x22; 32],
+            vec![],
+            1_700_000_100,
+            RejectReason::MerkleRootMismatch,
+        );
+        assert_eq!(proof.to_canonical_bytes(), VALIDATION_PROOF_V4_GOLDEN);
+        assert_eq!(proof.to_canonical_bytes()[0], 4);
+        assert_eq!(
+            serde_json::to_string_pretty(&proof).unwrap(),
+            VALIDATION_PROOF_V4_GOLDEN_JSON.trim_end()
+        );
+
+        let decoded = ValidationProof::from_canonical_bytes(VALIDATION_PROOF_V4_GOLDEN).unwrap();
+        assert_eq!(decoded, proof);
+        assert!(decoded.verify_signature().is_ok());
+        let from_json: ValidationProof =
+            serde_json::from_str(VALIDATION_PROOF_V4_GOLDEN_JSON).unwrap();
+        assert_eq!(from_json, proof);
+
+        // The verdict is signed: a rejection cannot be turned into an acceptance.
+        let mut flipped = proof.clone();
+        flipped.verdict = Verdict::Accept;
+        assert!(flipped.verify_signature().is_err());
+
+        // No instrument flag, then the reason code.
+        let len = VALIDATION_PROOF_V4_GOLDEN.len();
+        assert_eq!(
+            VALIDATION_PROOF_V4_GOLDEN[len - 2..],
+            [0, RejectReason::MerkleRootMismatch.code()]
+        );
+        let mut unknown_reason = VALIDATION_PROOF_V4_GOLDEN.to_vec();
+        unknown_reason[len - 1] = 0;
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&unknown_reason),
+            Err(CanonicalError::InvalidRejectReason(0))
+        );
+        let mut bad_flag = VALIDATION_PROOF_V4_GOLDEN.to_vec();
+        bad_flag[len - 2] = 2;
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&bad_flag),
+            Err(CanonicalError::InvalidFlag(2))
+        );
+
+        let mut with_instrument = golden_instrument_proof();
+        with_instrument.verdict = Verdict::Reject(RejectReason::InstrumentOutOfCalibration);
+        let bytes = with_instrument.to_canonical_bytes();
+        assert_eq!(bytes[0], 4);
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&bytes).unwrap(),
+            with_instrument
+        );
+
+        let json = VALIDATION_PROOF_V4_GOLDEN_JSON.replace("\"version\": 4", "\"version\": 1");
+        assert!(serde_json::from_str::<ValidationProof>(&json).is_err());
+    }
+
+    #[test]
+    fn test_sequence_proof_golden_encoding() {
+        let proof = golden_sequence_proof();
+        assert_eq!(proof.to_canonical_bytes(), SEQUENCE_PROOF_GOLDEN);
+        assert_eq!(borsh::to_vec(&proof).unwrap(), SEQUENCE_PROOF_GOLDEN);
+        assert_eq!(
+            serde_json::to_string_pretty(&proof).unwrap(),
+            SEQUENCE_PROOF_GOLDEN_JSON.trim_end()
+        );
+        assert_eq!(
+            SequenceProof::from_canonical_bytes(SEQUENCE_PROOF_GOLDEN).unwrap(),
+            proof
+        );
+        let from_json: SequenceProof = serde_json::from_str(SEQUENCE_PROOF_GOLDEN_JSON).unwrap();
+        assert_eq!(from_json, proof);
+    }
+
+    #[test]
+    fn test_sequence_proof_v3_golden_encoding() {
+        let mut proof = golden_sequence_proof();
+        proof.quality_metrics.insert_size = Some(InsertSizeStats {
+            proper_pairs: 1200,
+            median: 350,
+            mean: 352.5,
+            std_dev: 41.25,
+        });
+        assert_eq!(proof.to_canonical_bytes(), SEQUENCE_PROOF_V3_GOLDEN);
+        assert_eq!(proof.to_canonical_bytes()[0], 3);
+        assert_eq!(
+            serde_json::to_string_pretty(&proof).unwrap(),
+            SEQUENCE_PROOF_V3_GOLDEN_JSON.trim_end()
+        );
+        assert_eq!(
+            SequenceProof::from_canonical_bytes(SEQUENCE_PROOF_V3_GOLDEN).unwrap(),
+            proof
+        );
+        let from_json: SequenceProof = serde_json::from_str(SEQUENCE_PROOF_V3_GOLDEN_JSON).unwrap();
+        assert_eq!(from_json, proof);
+
+        // The v1 prefix is shared; the insert sizes are the last 16 bytes.
+        assert_eq!(
+            SEQUENCE_PROOF_V3_GOLDEN[1..SEQUENCE_PROOF_V3_GOLDEN.len() - 16],
+            SEQUENCE_PROOF_GOLDEN[1..]
+        );
+        let json = SEQUENCE_PROOF_V3_GOLDEN_JSON.replace("\"version\": 3", "\"version\": 1");
+        assert!(serde_json::from_str::<SequenceProof>(&json).is_err());
+    }
+
+    #[test]
+    fn test_canonical_decoding_rejects_malformed_input() {
+        let bytes = golden_validation_proof().to_canonical_bytes();
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&bytes[..bytes.len() - 1]),
+            Err(CanonicalError::Truncated)
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&trailing),
+            Err(CanonicalError::TrailingBytes)
+        );
+        let mut future = bytes.clone();
+        future[0] = PROOF_ENCODING_VERSION + 1;
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&future),
+            Err(CanonicalError::UnsupportedVersion(
+                PROOF_ENCODING_VERSION + 1
+            ))
+        );
+        // A v2 header promises an instrument record that is not there.
+        let mut upgraded = bytes.clone();
+        upgraded[0] = 2;
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&upgraded),
+            Err(CanonicalError::Truncated)
+        );
+        // Versions a proof would never be written with are refused, even
+        // where the layout matches.
+        let relabel = |golden: &[u8], version: u8| {
+            let mut bytes = golden.to_vec();
+            bytes[0] = version;
+            bytes
+        };
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&relabel(VALIDATION_PROOF_V2_GOLDEN, 3)),
+            Err(CanonicalError::UnsupportedVersion(3))
+        );
+        assert_eq!(
+            SequenceProof::from_canonical_bytes(&relabel(SEQUENCE_PROOF_GOLDEN, 2)),
+            Err(CanonicalError::UnsupportedVersion(2))
+        );
+        assert_eq!(
+            SequenceProof::from_canonical_bytes(&relabel(SEQUENCE_PROOF_V3_GOLDEN, 4)),
+            Err(CanonicalError::UnsupportedVersion(4))
+        );
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(SEQUENCE_PROOF_GOLDEN),
+            Err(CanonicalError::UnexpectedKind(SEQUENCE_PROOF_KIND))
+        );
+        let mut oversized = bytes;
+        oversized[98..102].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            ValidationProof::from_canonical_bytes(&oversized),
+            Err(CanonicalError::FieldTooLarge(u32::MAX))
+        );
+
+        let json = VALIDATION_PROOF_GOLDEN_JSON.replace("\"version\": 1", "\"version\": 2");
+        assert!(serde_json::from_str::<ValidationProof>(&json).is_err());
+        let json =
+            VALIDATION_PROOF_GOLDEN_JSON.replace("\"timestamp\"", "\"extra\": 0,\n  \"timestamp\"");
+        assert!(serde_json::from_str::<ValidationProof>(&json).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use crate::model::{
+    iupac_code, AmbiguityRun, EncodingError, Nucleotide, NucleotideSequence, SequenceMetadata,
+};
+use solana_program::hash::Hasher;
+
+/// Version of the sequence hash construction. Bump when the layout changes so
+/// stored `sequence_hash` values can be told apart.
+pub const SEQUENCE_HASH_VERSION: u8 = 1;
+const SEQUENCE_DOMAIN: &[u8] = b"biomod/sequence";
+const AMBIGUITY_DOMAIN: &[u8] = b"biomod/ambiguity";
+const METADATA_DOMAIN: &[u8] = b"biomod/metadata";
+const HASH_BLOCK_BYTES: usize = 64; // 256 bases per compression call
+
+/// Streaming Merkle-Damgård hasher over the packed 2-bit nucleotide stream.
+///
+/// Each 64-byte block is folded into the chaining value with SHA-256 keyed by
+/// the domain tag, version and block index. The final block carries MD
+/// strengthening (0x80, zero fill, base count), and the ambiguity mask is
+/// hashed separately and bound in at finalization. Input chunking does not
+/// affect the result, and lower-case (soft-masked) bases hash like upper-case.
+pub struct SequenceHasher {
+    chaining_value: [u8; 32],
+    block: Vec<u8>,
+    partial_byte: u8,
+    bases: u64,
+    blocks: u64,
+    ambiguity: Hasher,
+    open_run: Option<AmbiguityRun>,
+}
+
+impl SequenceHasher {
+    pub fn new() -> Self {
+        let mut iv = Hasher::default();
+        iv.hashv(&[SEQUENCE_DOMAIN, &[SEQUENCE_HASH_VERSION]]);
+        let mut ambiguity = Hasher::default();
+        ambiguity.hashv(&[AMBIGUITY_DOMAIN, &[SEQUENCE_HASH_VERSION]]);
+
+        Self {
+            chaining_value: iv.result().to_bytes(),
+            block: Vec::with_capacity(HASH_BLOCK_BYTES),
+            partial_byte: 0,
+            bases: 0,
+            blocks: 0,
+            ambiguity,
+            open_run: None,
+        }
+    }
+
+    pub fn bases(&self) -> u64 {
+        self.bases
+    }
+
+    pub fn update_base(&mut self, base: Nucleotide) {
+        self.close_run_before(self.bases);
+        self.push_bits(base as u8);
+    }
+
+    /// Feeds IUPAC text. Newlines and other non-nucleotide bytes are rejected,
+    /// so callers strip FASTA framing first.
+    pub fn update_ascii(&mut self, data: &[u8]) -> Result<(), EncodingError> {
+        for &byte in data {
+            if let Some(base) = Nucleotide::from_ascii(byte) {
+                self.update_base(base);
+                continue;
+            }
+            let code = iupac_code(byte).ok_or(EncodingError::InvalidBase {
+                position: self.bases,
+                byte,
+            })?;
+            self.update_ambiguous(code, 1);
+        }
+        Ok(())
+    }
+
+    pub fn update_sequence(&mut self, sequence: &NucleotideSequence) {
+        let offset = self.bases;
+        let mut index = 0;
+        if self.bases.is_multiple_of(4) {
+            // Byte aligned: the packed stream can be copied as is.
+            let whole_bytes = (sequence.len() / 4) as usize;
+            for &byte in &sequence.packed()[..whole_bytes] {
+                self.push_byte(byte);
+            }
+            index = whole_bytes as u64 * 4;
+        }
+        for position in index..sequence.len() {
+            let bits = sequence.packed()[(position / 4) as usize] >> (6 - 2 * (position % 4) as u8);
+            self.push_bits(bits & 0b11);
+        }
+        for run in sequence.ambiguity_mask() {
+            self.record_run(AmbiguityRun {
+                start: run.start + offset,
+                ..*run
+            });
+        }
+    }
+
+    pub fn finalize(mut self) -> [u8; 32] {
+        self.close_run_before(u64::MAX);
+
+        let bases = self.bases;
+        if !bases.is_multiple_of(4) {
+            let byte = self.partial_byte << (2 * (4 - bases % 4));
+            self.block.push(byte);
+        }
+        self.block.push(0x80);
+        if self.block.len() > HASH_BLOCK_BYTES - 8 {
+            self.block.resize(HASH_BLOCK_BYTES, 0);
+            self.compress();
+        }
+        self.block.resize(HASH_BLOCK_BYTES - 8, 0);
+        self.block.extend_from_slice(&bases.to_be_bytes());
+        self.compress();
+
+        let mut hasher = Hasher::default();
+        hasher.hashv(&[
+            SEQUENCE_DOMAIN,
+            &[SEQUENCE_HASH_VERSION],
+            &self.chaining_value,
+            self.ambiguity.result().as_ref(),
+        ]);
+        hasher.result().to_bytes()
+    }
+
+    fn update_ambiguous(&mut self, code: u8, count: u64) {
+        self.record_run(AmbiguityRun {
+            start: self.bases,
+            len: count,
+            code,
+        });
+        for _ in 0..count {
+            self.push_bits(Nucleotide::A as u8);
+        }
+    }
+
+    fn record_run(&mut self, run: AmbiguityRun) {
+        match self.open_run.as_mut() {
+            Some(open) if open.code == run.code && open.end() == run.start => open.len += run.len,
+            _ => {
+                self.close_run_before(u64::MAX);
+                self.open_run = Some(run);
+            }
+        }
+    }
+
+    fn close_run_before(&mut self, position: u64) {
+        if let Some(run) = self.open_run.filter(|run| run.end() <= position) {
+            self.ambiguity.hashv(&[
+                &run.start.to_le_bytes(),
+                &run.len.to_le_bytes(),
+                &[run.code],
+            ]);
+            self.open_run = None;
+        }
+    }
+
+    fn push_bits(&mut self, bits: u8) {
+        self.partial_byte = (self.partial_byte << 2) | bits;
+        self.bases += 1;
+        if self.bases.is_multiple_of(4) {
+            let byte = self.partial_byte;
+            self.partial_byte = 0;
+            self.bases -= 4;
+            self.push_byte(byte);
+        }
+    }
+
+    fn push_byte(&mut self, byte: u8) {
+        self.block.push(byte);
+        self.bases += 4;
+        if self.block.len() == HASH_BLOCK_BYTES {
+            self.compress();
+        }
+    }
+
+    fn compress(&mut self) {
+        let mut hasher = Hasher::default();
+        hasher.hashv(&[
+            SEQUENCE_DOMAIN,
+            &[SEQUENCE_HASH_VERSION],
+            &self.blocks.to_le_bytes(),
+            &self.chaining_value,
+            &self.block,
+        ]);
+        self.chaining_value = hasher.result().to_bytes();
+        self.blocks += 1;
+        self.block.clear();
+    }
+}
+
+impl Default for SequenceHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NucleotideSequence {
+    pub fn sequence_hash(&self) -> [u8; 32] {
+        let mut hasher = SequenceHasher::new();
+        hasher.update_sequence(self);
+        hasher.finalize()
+    }
+}
+
+/// Hash of the descriptive metadata, domain separated from the sequence hash
+/// so a metadata digest can never collide with a sequence digest.
+pub fn hash_metadata(metadata: &SequenceMetadata) -> [u8; 32] {
+    let encoded = borsh::to_vec(metadata).expect("metadata serialization is infallible");
+    let mut hasher = Hasher::default();
+    hasher.hashv(&[METADATA_DOMAIN, &[SEQUENCE_HASH_VERSION], &encoded]);
+    hasher.result().to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn test_sequence_hash_known_answers() {
+        let empty = SequenceHasher::new().finalize();
+        let short = NucleotideSequence::from_ascii(b"ACGT")
+            .unwrap()
+            .sequence_hash();
+        let gapped = NucleotideSequence::from_ascii(b"ACGTNNNNRYACGTA")
+            .unwrap()
+            .sequence_hash();
+        let long = NucleotideSequence::from_ascii(&b"GATTACA".repeat(100))
+            .unwrap()
+            .sequence_hash();
+        assert_eq!(
+            hex(&empty),
+            "3061d1951d58cdbf8bdaa46b7bd745e51c51a2906c3300d5f5c62b37dffed737"
+        );
+        assert_eq!(
+            hex(&short),
+            "51e77faec9b0ee63ab60dc7b2f9e3df9dc730907c13f4ff462ac79562a51bb83"
+        );
+        assert_eq!(
+            hex(&gapped),
+            "a55109d1285bdd8f54759db65a8a9013ece56dd271b31cbde3a61057f436eeb9"
+        );
+        assert_eq!(
+            hex(&long),
+            "7f5d2c540364874ba33a2ba302383fd3dbe9713cb80b69d0425b3a45c8c63112"
+        );
+    }
+
+    #[test]
+    fn test_sequence_hash_is_streaming() {
+        let raw = b"acgtNNNNRYACGTAGATTACAGATTACA".repeat(37);
+        let whole = NucleotideSequence::from_ascii(&raw)
+            .unwrap()
+            .sequence_hash();
+        let mut hasher = SequenceHasher::new();
+        for chunk in raw.chunks(7) {
+            hasher.update_ascii(chunk).unwrap();
+        }
+        assert_eq!(hasher.finalize(), whole);
+        let mut hasher = SequenceHasher::new();
+        hasher.update_ascii(&raw[..3]).unwrap();
+        hasher.update_sequence(&NucleotideSequence::from_ascii(&raw[3..500]).unwrap());
+        hasher.update_sequence(&NucleotideSequence::from_ascii(&raw[500..]).unwrap());
+        assert_eq!(hasher.finalize(), whole);
+        let mut hasher = SequenceHasher::new();
+        hasher.update_ascii(&raw[..4]).unwrap();
+        hasher.update_sequence(&NucleotideSequence::from_ascii(&raw[4..]).unwrap());
+        assert_eq!(hasher.finalize(), whole);
+
+        let hash_of = |raw: &[u8]| NucleotideSequence::from_ascii(raw).unwrap().sequence_hash();
+        assert_ne!(hash_of(b"ACGTA"), hash_of(b"ACGTN"));
+        assert_ne!(hash_of(b"ACGT"), hash_of(b"ACGTA"));
+    }
+}
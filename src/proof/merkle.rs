@@ -0,0 +1,376 @@
+use crate::model::NucleotideSequence;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::hash::Hasher;
+use std::ops::Range;
+
+pub const DEFAULT_CHUNK_BASES: u64 = 1024;
+const MERKLE_LEAF_DOMAIN: &[u8] = b"biomod/merkle-leaf";
+const MERKLE_NODE_DOMAIN: &[u8] = b"biomod/merkle-node";
+const MERKLE_ROOT_DOMAIN: &[u8] = b"biomod/merkle-root";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MerkleError {
+    InvalidChunkSize,
+    LeafCountMismatch,
+    EmptyRange,
+    RangeOutOfBounds { start: u64, end: u64, len: u64 },
+    MalformedProof,
+    RootMismatch,
+}
+
+/// Merkle tree over fixed-size chunks of a sequence. Leaves are the
+/// `sequence_hash` of each chunk, an unpaired node is promoted to the next
+/// level unchanged, and the root binds the chunk size and sequence length so
+/// a verifier can rebuild the layout from the proof alone.
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    chunk_bases: u64,
+    sequence_len: u64,
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+/// Inclusion proof for `range`. Carries the chunk-aligned bases covering the
+/// range plus the sibling hashes needed to climb to the root, left sibling
+/// before right at each level.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RangeProof {
+    pub chunk_bases: u64,
+    pub sequence_len: u64,
+    pub range: Range<u64>,
+    pub covering_bases: NucleotideSequence,
+    pub siblings: Vec<[u8; 32]>,
+}
+
+fn leaf_hash(index: u64, chunk: &NucleotideSequence) -> [u8; 32] {
+    let mut hasher = Hasher::default();
+    hasher.hashv(&[
+        MERKLE_LEAF_DOMAIN,
+        &index.to_le_bytes(),
+        &chunk.sequence_hash(),
+    ]);
+    hasher.result().to_bytes()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Hasher::default();
+    hasher.hashv(&[MERKLE_NODE_DOMAIN, left, right]);
+    hasher.result().to_bytes()
+}
+
+fn root_hash(chunk_bases: u64, sequence_len: u64, top: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Hasher::default();
+    hasher.hashv(&[
+        MERKLE_ROOT_DOMAIN,
+        &chunk_bases.to_le_bytes(),
+        &sequence_len.to_le_bytes(),
+        top,
+    ]);
+    hasher.result().to_bytes()
+}
+
+fn chunk_count(sequence_len: u64, chunk_bases: u64) -> u64 {
+    sequence_len.div_ceil(chunk_bases)
+}
+
+impl MerkleTree {
+    pub fn from_sequence(sequence: &NucleotideSequence) -> Self {
+        Self::with_chunk_bases(sequence, DEFAULT_CHUNK_BASES)
+            .expect("default chunk size is non-zero")
+    }
+
+    pub fn with_chunk_bases(
+        sequence: &NucleotideSequence,
+        chunk_bases: u64,
+    ) -> Result<Self, MerkleError> {
+        if chunk_bases == 0 {
+            return Err(MerkleError::InvalidChunkSize);
+        }
+
+        let leaves = (0..chunk_count(sequence.len(), chunk_bases))
+            .map(|index| {
+                let start = index * chunk_bases;
+                let end = (start + chunk_bases).min(sequence.len());
+                let chunk = sequence
+                    .slice(start..end)
+                    .expect("chunk lies within the sequence");
+                leaf_hash(index, &chunk)
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_leaves(chunk_bases, sequence.len(), leaves)
+    }
+
+    /// Rebuilds a tree from precomputed leaf hashes, e.g. ones supplied by a
+    /// validator that holds the sequence.
+    pub fn from_leaves(
+        chunk_bases: u64,
+        sequence_len: u64,
+        leaves: Vec<[u8; 32]>,
+    ) -> Result<Self, MerkleError> {
+        if chunk_bases == 0 {
+            return Err(MerkleError::InvalidChunkSize);
+        }
+        if leaves.len() as u64 != chunk_count(sequence_len, chunk_bases) {
+            return Err(MerkleError::LeafCountMismatch);
+        }
+
+        let mut levels = vec![leaves];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node_hash(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Ok(Self {
+            chunk_bases,
+            sequence_len,
+            levels,
+        })
+    }
+
+    pub fn chunk_bases(&self) -> u64 {
+        self.chunk_bases
+    }
+
+    pub fn sequence_len(&self) -> u64 {
+        self.sequence_len
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn leaves(&self) -> &[[u8; 32]] {
+        &self.levels[0]
+    }
+
+    pub fn root(&self) -> [u8; 32] {
+        let top = self
+            .levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or([0u8; 32]);
+        root_hash(self.chunk_bases, self.sequence_len, &top)
+    }
+
+    /// Builds an inclusion proof for `range` of `sequence`, which must be the
+    /// sequence this tree was built from.
+    pub fn prove_range(
+        &self,
+        sequence: &NucleotideSequence,
+        range: Range<u64>,
+    ) -> Result<RangeProof, MerkleError> {
+        if sequence.len() != self.sequence_len {
+            return Err(MerkleError::RootMismatch);
+        }
+        if range.start >= range.end {
+            return Err(MerkleError::EmptyRange);
+        }
+        if range.end > self.sequence_len {
+            return Err(MerkleError::RangeOutOfBounds {
+                start: range.start,
+                end: range.end,
+                len: self.sequence_len,
+            });
+        }
+
+        let mut low = (range.start / self.chunk_bases) as usize;
+        let mut high = ((range.end - 1) / self.chunk_bases) as usize;
+        let covering_start = low as u64 * self.chunk_bases;
+        let covering_end = ((high as u64 + 1) * self.chunk_bases).min(self.sequence_len);
+
+        let mut siblings = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            if low % 2 == 1 {
+                siblings.push(level[low - 1]);
+            }
+            if high.is_multiple_of(2) && high + 1 < level.len() {
+                siblings.push(level[high + 1]);
+            }
+            low /= 2;
+            high /= 2;
+        }
+
+        Ok(RangeProof {
+            chunk_bases: self.chunk_bases,
+            sequence_len: self.sequence_len,
+            range,
+            covering_bases: sequence
+                .slice(covering_start..covering_end)
+                .map_err(|_| MerkleError::MalformedProof)?,
+            siblings,
+        })
+    }
+}
+
+impl RangeProof {
+    /// Bases of the proven range, taken from the covering chunks.
+    pub fn bases(&self) -> Result<NucleotideSequence, MerkleError> {
+        let offset = (self.range.start / self.chunk_bases) * self.chunk_bases;
+        self.covering_bases
+            .slice(self.range.start - offset..self.range.end - offset)
+            .map_err(|_| MerkleError::MalformedProof)
+    }
+}
+
+/// Checks `proof` against `merkle_root` without access to the rest of the
+/// sequence and returns the verified bases of the requested range.
+pub fn verify_range(
+    merkle_root: &[u8; 32],
+    proof: &RangeProof,
+) -> Result<NucleotideSequence, MerkleError> {
+    let chunk_bases = proof.chunk_bases;
+    if chunk_bases == 0 {
+        return Err(MerkleError::InvalidChunkSize);
+    }
+    if proof.range.start >= proof.range.end {
+        return Err(MerkleError::EmptyRange);
+    }
+    if proof.range.end > proof.sequence_len {
+        return Err(MerkleError::RangeOutOfBounds {
+            start: proof.range.start,
+            end: proof.range.end,
+            len: proof.sequence_len,
+        });
+    }
+
+    let first_chunk = proof.range.start / chunk_bases;
+    let last_chunk = (proof.range.end - 1) / chunk_bases;
+    // Chunk offsets come from the proof, so none of this arithmetic is
+    // trusted not to overflow.
+    let offset = |index: u64| {
+        index
+            .checked_mul(chunk_bases)
+            .ok_or(MerkleError::MalformedProof)
+    };
+    let covering_start = offset(first_chunk)?;
+    let covering_end = offset(last_chunk + 1)?.min(proof.sequence_len);
+    if proof.covering_bases.len() != covering_end - covering_start {
+        return Err(MerkleError::MalformedProof);
+    }
+
+    let mut nodes = (first_chunk..=last_chunk)
+        .map(|index| {
+            let start = offset(index)? - covering_start;
+            let end = start
+                .saturating_add(chunk_bases)
+                .min(proof.covering_bases.len());
+            let chunk = proof
+                .covering_bases
+                .slice(start..end)
+                .map_err(|_| MerkleError::MalformedProof)?;
+            Ok(leaf_hash(index, &chunk))
+        })
+        .collect::<Result<Vec<_>, MerkleError>>()?;
+
+    let mut siblings = proof.siblings.iter();
+    let mut low = first_chunk;
+    let mut high = last_chunk;
+    let mut width = chunk_count(proof.sequence_len, chunk_bases);
+    while width > 1 {
+        if low % 2 == 1 {
+            nodes.insert(0, *siblings.next().ok_or(MerkleError::MalformedProof)?);
+            low -= 1;
+        }
+        if high.is_multiple_of(2) && high + 1 < width {
+            nodes.push(*siblings.next().ok_or(MerkleError::MalformedProof)?);
+            high += 1;
+        }
+        nodes = nodes
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => node_hash(left, right),
+                [single] => *single,
+                _ => unreachable!(),
+            })
+            .collect();
+        low /= 2;
+        high /= 2;
+        width = width.div_ceil(2);
+    }
+    if siblings.next().is_some() || nodes.len() != 1 {
+        return Err(MerkleError::MalformedProof);
+    }
+
+    if root_hash(chunk_bases, proof.sequence_len, &nodes[0]) != *merkle_root {
+        return Err(MerkleError::RootMismatch);
+    }
+    proof.bases()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_range_proofs() {
+        let sequence = NucleotideSequence::from_ascii(&b"GATTACANNCCGGTTA".repeat(25)).unwrap();
+        let tree = MerkleTree::with_chunk_bases(&sequence, 16).unwrap();
+        assert_eq!(tree.leaf_count(), 25);
+        assert_eq!(
+            tree.root(),
+            MerkleTree::with_chunk_bases(&sequence, 16).unwrap().root()
+        );
+        assert_ne!(
+            tree.root(),
+            MerkleTree::with_chunk_bases(&sequence, 32).unwrap().root()
+        );
+
+        for range in [0..1, 5..40, 100..101, 17..399, 0..400, 384..400, 390..395] {
+            let proof = tree.prove_range(&sequence, range.clone()).unwrap();
+            let bases = verify_range(&tree.root(), &proof).unwrap();
+            assert_eq!(bases, sequence.slice(range).unwrap());
+        }
+        assert!(tree.prove_range(&sequence, 10..401).is_err());
+    }
+
+    #[test]
+    fn test_merkle_rejects_tampered_range() {
+        let sequence = NucleotideSequence::from_ascii(&b"ACGTTGCA".repeat(40)).unwrap();
+        let tree = MerkleTree::with_chunk_bases(&sequence, 10).unwrap();
+        let proof = tree.prove_range(&sequence, 55..75).unwrap();
+
+        let mut tampered = proof.clone();
+        let mut raw = tampered.covering_bases.to_ascii();
+        raw[3] = if raw[3] == b'A' { b'C' } else { b'A' };
+        tampered.covering_bases = NucleotideSequence::from_ascii(&raw).unwrap();
+        assert_eq!(
+            verify_range(&tree.root(), &tampered),
+            Err(MerkleError::RootMismatch)
+        );
+
+        let mut truncated = proof.clone();
+        truncated.siblings.pop();
+        assert_eq!(
+            verify_range(&tree.root(), &truncated),
+            Err(MerkleError::MalformedProof)
+        );
+
+        let mut shifted = proof;
+        shifted.range = 45..65;
+        assert!(verify_range(&tree.root(), &shifted).is_err());
+
+        // Chunk sizes that overflow the offsets are refused, not panicked on.
+        let overflowing = RangeProof {
+            chunk_bases: u64::MAX / 2,
+            sequence_len: u64::MAX,
+            range: u64::MAX - 2..u64::MAX - 1,
+            covering_bases: NucleotideSequence::new(),
+            siblings: Vec::new(),
+        };
+        assert_eq!(
+            verify_range(&tree.root(), &overflowing),
+            Err(MerkleError::MalformedProof)
+        );
+    }
+}
@@ -0,0 +1,681 @@
+pub mod encoding;
+pub mod hash;
+pub mod merkle;
+pub mod signing;
+pub mod zk;
+
+use crate::model::{unix_timestamp, NucleotideSequence, QualityMetrics, SequencerModel};
+use borsh::BorshDeserialize;
+use encoding::{SequenceProofJson, ValidationProofJson};
+use serde::{Deserialize, Serialize};
+use solana_program::pubkey::Pubkey;
+use std::collections::HashMap;
+
+pub use encoding::{CanonicalError, PROOF_ENCODING_VERSION};
+pub use hash::{hash_metadata, SequenceHasher, SEQUENCE_HASH_VERSION};
+pub use merkle::{verify_range, MerkleError, MerkleTree, RangeProof, DEFAULT_CHUNK_BASES};
+pub use signing::SignatureError;
+pub use zk::{
+    HashChainBackend, ZkBackend, ZkParameters, ZkPayload, ZkProofBuilder, ZkStatement,
+    ZkThresholds, ZkWitness, HASH_CHAIN_BACKEND_ID,
+};
+
+/// A validator's signed attestation about one sequence. Encoded with the
+/// canonical layout in `encoding` for signing, hashing and storage.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(into = "ValidationProofJson", try_from = "ValidationProofJson")]
+pub struct ValidationProof {
+    pub validator: Pubkey,
+    pub sequence_hash: [u8; 32],
+    pub merkle_root: [u8; 32],
+    pub zk_proof: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub timestamp: i64,
+    pub instrument: Option<InstrumentRecord>, // Sequencer the validation ran on; `None` in v1 proofs
+    pub verdict: Verdict,
+}
+
+/// Whether the validator vouches for the sequence. Covered by the signature;
+/// proofs before v4 are always acceptances.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verdict {
+    #[default]
+    Accept,
+    Reject(RejectReason),
+}
+
+impl Verdict {
+    pub fn is_accept(self) -> bool {
+        self == Verdict::Accept
+    }
+}
+
+/// Why a validator rejected a sequence, carried as a one-byte code.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RejectReason {
+    QualityBelowThreshold,
+    MerkleRootMismatch, // The validator's own reads give a different root
+    Contaminated,
+    InstrumentOutOfCalibration,
+    Other,
+}
+
+impl RejectReason {
+    pub fn code(self) -> u8 {
+        match self {
+            RejectReason::QualityBelowThreshold => 1,
+            RejectReason::MerkleRootMismatch => 2,
+            RejectReason::Contaminated => 3,
+            RejectReason::InstrumentOutOfCalibration => 4,
+            RejectReason::Other => 255,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        match code {
+            1 => Some(RejectReason::QualityBelowThreshold),
+            2 => Some(RejectReason::MerkleRootMismatch),
+            3 => Some(RejectReason::Contaminated),
+            4 => Some(RejectReason::InstrumentOutOfCalibration),
+            255 => Some(RejectReason::Other),
+            _ => None,
+        }
+    }
+}
+
+/// The sequencer behind a validation, copied from the validator's declared
+/// `SequencerModel` when the proof is signed so auditors can trace a result
+/// to a machine and the calibration it had at the time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct InstrumentRecord {
+    pub manufacturer: String,
+    pub model: String,
+    pub serial_number: String,
+    pub last_calibration: i64,
+}
+
+impl From<&SequencerModel> for InstrumentRecord {
+    fn from(sequencer: &SequencerModel) -> Self {
+        Self {
+            manufacturer: sequencer.manufacturer.clone(),
+            model: sequencer.model.clone(),
+            serial_number: sequencer.serial_number.clone(),
+            last_calibration: sequencer.last_calibration,
+        }
+    }
+}
+
+impl InstrumentRecord {
+    /// Whether this record names `sequencer`.
+    pub fn identifies(&self, sequencer: &SequencerModel) -> bool {
+        self.manufacturer == sequencer.manufacturer
+            && self.model == sequencer.model
+            && self.serial_number == sequencer.serial_number
+    }
+}
+
+/// Checks proofs against trusted merkle roots. By default zk proofs go
+/// through `HashChainBackend`, which is not zero-knowledge: see
+/// `zero_knowledge`.
+pub struct ProofValidator {
+    pub zk_params: ZkParameters,
+    pub registered_roots: HashMap<[u8; 32], [u8; 32]>, // Root to its pinned zk commitment
+    pub zk_backend: Box<dyn ZkBackend>,
+    pub required_thresholds: ZkThresholds,
+}
+
+impl Default for ProofValidator {
+    fn default() -> Self {
+        Self::new(
+            Box::new(HashChainBackend),
+            ZkParameters::default(),
+            ZkThresholds::default(),
+        )
+    }
+}
+
+impl ProofValidator {
+    pub fn new(
+        zk_backend: Box<dyn ZkBackend>,
+        zk_params: ZkParameters,
+        required_thresholds: ZkThresholds,
+    ) -> Self {
+        Self {
+            zk_params,
+            registered_roots: HashMap::new(),
+            zk_backend,
+            required_thresholds,
+        }
+    }
+
+    /// Whether the zk backend's proofs reveal nothing beyond their statement.
+    pub fn zero_knowledge(&self) -> bool {
+        self.zk_backend.zero_knowledge()
+    }
+
+    /// Trusts `root` and the zk commitment its proofs must carry, as taken
+    /// from the sequencer's own proof with `zk_commitment`.
+    pub fn register_root(&mut self, root: [u8; 32], zk_commitment: [u8; 32]) {
+        self.registered_roots.insert(root, zk_commitment);
+    }
+
+    /// Accepts the proof only if its root is one this validator has registered.
+    pub fn validate_proof(&self, proof: &SequenceProof) -> Result<(), ProofError> {
+        if !self.registered_roots.contains_key(&proof.merkle_root) {
+            return Err(ProofError::UnknownMerkleRoot);
+        }
+        self.verify_zk_proof(proof)
+    }
+
+    /// Accepts the proof only if `leaves` rebuild to its root and the root
+    /// is registered.
+    pub fn validate_proof_with_leaves(
+        &self,
+        proof: &SequenceProof,
+        chunk_bases: u64,
+        sequence_len: u64,
+        leaves: Vec<[u8; 32]>,
+    ) -> Result<(), ProofError> {
+        self.verify_merkle_root(&proof.merkle_root, chunk_bases, sequence_len, leaves)?;
+        self.verify_zk_proof(proof)
+    }
+
+    /// Checks that `range_proof` climbs to the proof's root and returns the
+    /// verified bases.
+    pub fn validate_range(
+        &self,
+        proof: &SequenceProof,
+        range_proof: &RangeProof,
+    ) -> Result<NucleotideSequence, ProofError> {
+        verify_range(&proof.merkle_root, range_proof).map_err(ProofError::from)
+    }
+
+    fn verify_merkle_root(
+        &self,
+        root: &[u8; 32],
+        chunk_bases: u64,
+        sequence_len: u64,
+        leaves: Vec<[u8; 32]>,
+    ) -> Result<(), ProofError> {
+        let tree = MerkleTree::from_leaves(chunk_bases, sequence_len, leaves)?;
+        if tree.root() != *root {
+            return Err(ProofError::InvalidMerkleRoot);
+        }
+        Ok(())
+    }
+
+    fn verify_zk_proof(&self, proof: &SequenceProof) -> Result<(), ProofError> {
+        self.verify_zk(&proof.merkle_root, &proof.zk_proof)
+    }
+
+    /// Checks a zk proof envelope against a registered `merkle_root`, its
+    /// pinned commitment and the thresholds this validator requires.
+    pub fn verify_zk(&self, merkle_root: &[u8; 32], zk_proof: &[u8]) -> Result<(), ProofError> {
+        let commitment = self
+            .registered_roots
+            .get(merkle_root)
+            .ok_or(ProofError::UnknownMerkleRoot)?;
+        self.verify_zk_commitment(merkle_root, zk_proof, commitment)
+    }
+
+    /// As `verify_zk`, with the commitment pinned elsewhere, such as in
+    /// `SequenceData.zk_commitment`.
+    pub fn verify_zk_commitment(
+        &self,
+        merkle_root: &[u8; 32],
+        zk_proof: &[u8],
+        commitment: &[u8; 32],
+    ) -> Result<(), ProofError> {
+        let payload = self.decode_zk(merkle_root, zk_proof)?;
+        if !payload
+            .statement
+            .thresholds
+            .covers(&self.required_thresholds)
+        {
+            return Err(ProofError::StatementBelowRequirement);
+        }
+        if self.zk_backend.commitment(&payload.proof)? != *commitment {
+            return Err(ProofError::ZkCommitmentMismatch);
+        }
+        self.zk_backend
+            .verify(&self.zk_params, &payload.statement, &payload.proof)
+    }
+
+    /// Checks a validator's acceptance of `merkle_root` for a sequence whose
+    /// sequencer pinned `commitment` for `pinned_root`. Proofs of the pinned
+    /// root must carry that commitment. A proof of another root is a dissent:
+    /// it is checked against its own chain only, so the conflict reaches
+    /// consensus instead of being dropped. An all-zero commitment is not
+    /// pinned yet and fails every acceptance.
+    pub fn verify_acceptance(
+        &self,
+        merkle_root: &[u8; 32],
+        zk_proof: &[u8],
+        pinned_root: &[u8; 32],
+        commitment: &[u8; 32],
+    ) -> Result<(), ProofError> {
+        if *commitment == [0; 32] {
+            return Err(ProofError::ZkCommitmentMismatch);
+        }
+        if merkle_root == pinned_root {
+            return self.verify_zk_commitment(merkle_root, zk_proof, commitment);
+        }
+        let payload = self.decode_zk(merkle_root, zk_proof)?;
+        if !payload
+            .statement
+            .thresholds
+            .covers(&self.required_thresholds)
+        {
+            return Err(ProofError::StatementBelowRequirement);
+        }
+        self.zk_backend
+            .verify(&self.zk_params, &payload.statement, &payload.proof)
+    }
+
+    /// As `verify_acceptance`, short of the backend's own verification,
+    /// which for `HashChainBackend` replays a chain as long as each
+    /// threshold claimed. The cost no longer depends on the proof, which is
+    /// what the program needs to stay within its compute budget. Only the
+    /// pinned commitment then backs the proof, so this refuses to run with
+    /// thresholds required.
+    pub fn verify_acceptance_commitment(
+        &self,
+        merkle_root: &[u8; 32],
+        zk_proof: &[u8],
+        pinned_root: &[u8; 32],
+        commitment: &[u8; 32],
+    ) -> Result<(), ProofError> {
+        if self.required_thresholds != ZkThresholds::default() {
+            return Err(ProofError::StatementBelowRequirement);
+        }
+        if *commitment == [0; 32] {
+            return Err(ProofError::ZkCommitmentMismatch);
+        }
+        let payload = self.decode_zk(merkle_root, zk_proof)?;
+        if merkle_root == pinned_root && self.zk_backend.commitment(&payload.proof)? != *commitment
+        {
+            return Err(ProofError::ZkCommitmentMismatch);
+        }
+        Ok(())
+    }
+
+    /// The commitment a sequencer pins for its own zk proof when the
+    /// sequence is registered.
+    pub fn zk_commitment(
+        &self,
+        merkle_root: &[u8; 32],
+        zk_proof: &[u8],
+    ) -> Result<[u8; 32], ProofError> {
+        let payload = self.decode_zk(merkle_root, zk_proof)?;
+        self.zk_backend.commitment(&payload.proof)
+    }
+
+    fn decode_zk(&self, merkle_root: &[u8; 32], zk_proof: &[u8]) -> Result<ZkPayload, ProofError> {
+        let envelope = ZkProofEnvelope::decode(zk_proof)?;
+        if envelope.merkle_root != *merkle_root {
+            return Err(ProofError::ZkRootMismatch);
+        }
+        if envelope.backend != self.zk_backend.id() || envelope.backend != self.zk_params.backend {
+            return Err(ProofError::UnsupportedZkBackend(envelope.backend));
+        }
+
+        let payload = ZkPayload::try_from_slice(&envelope.payload)
+            .map_err(|_| ProofError::MalformedZkPayload)?;
+        if payload.statement.merkle_root != *merkle_root {
+            return Err(ProofError::ZkRootMismatch);
+        }
+        Ok(payload)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(into = "SequenceProofJson", try_from = "SequenceProofJson")]
+pub struct SequenceProof {
+    pub merkle_root: [u8; 32],
+    pub quality_metrics: QualityMetrics,
+    pub zk_proof: Vec<u8>,
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofError {
+    InvalidMerkleRoot,
+    UnknownMerkleRoot,
+    MalformedMerkleProof,
+    LeafCountMismatch,
+    InvalidZkProof,
+    MalformedZkPayload,
+    UnsupportedZkBackend(u8),
+    IncompleteStatement,
+    StatementNotSatisfied,
+    StatementBelowRequirement,
+    ThresholdOutOfRange,
+    ZkRootMismatch,
+    ZkCommitmentMismatch,
+    TruncatedProof,
+    TrailingProofBytes,
+    UnsupportedProofVersion(u8),
+    QualityBelowThreshold,
+}
+
+impl From<MerkleError> for ProofError {
+    fn from(e: MerkleError) -> Self {
+        match e {
+            MerkleError::RootMismatch => ProofError::InvalidMerkleRoot,
+            MerkleError::LeafCountMismatch => ProofError::LeafCountMismatch,
+            _ => ProofError::MalformedMerkleProof,
+        }
+    }
+}
+
+pub const ZK_PROOF_VERSION: u8 = 1;
+
+/// Wire layout of `SequenceProof.zk_proof`:
+/// version (u8) | backend (u8) | merkle_root ([u8; 32]) | payload_len (u32 LE) | payload.
+/// The root is repeated so a proof cannot be lifted onto another sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZkProofEnvelope {
+    pub backend: u8,
+    pub merkle_root: [u8; 32],
+    pub payload: Vec<u8>,
+}
+
+impl ZkProofEnvelope {
+    const HEADER_LEN: usize = 1 + 1 + 32 + 4;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::HEADER_LEN + self.payload.len());
+        bytes.push(ZK_PROOF_VERSION);
+        bytes.push(self.backend);
+        bytes.extend_from_slice(&self.merkle_root);
+        bytes.extend_from_slice(&(self.payload.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self, ProofError> {
+        if bytes.len() < Self::HEADER_LEN {
+            return Err(ProofError::TruncatedProof);
+        }
+        if bytes[0] != ZK_PROOF_VERSION {
+            return Err(ProofError::UnsupportedProofVersion(bytes[0]));
+        }
+        let backend = bytes[1];
+        let merkle_root: [u8; 32] = bytes[2..34].try_into().unwrap();
+        let payload_len = u32::from_le_bytes(bytes[34..38].try_into().unwrap()) as usize;
+        let payload = &bytes[Self::HEADER_LEN..];
+        if payload.len() < payload_len {
+            return Err(ProofError::TruncatedProof);
+        }
+        if payload.len() > payload_len {
+            return Err(ProofError::TrailingProofBytes);
+        }
+
+        Ok(Self {
+            backend,
+            merkle_root,
+            payload: payload.to_vec(),
+        })
+    }
+}
+
+const MIN_PROOF_COVERAGE: u32 = 30;
+const MIN_PROOF_QUALITY: f32 = 30.0;
+const MAX_PROOF_ERROR_RATE: f32 = 0.001;
+
+pub struct SequenceProofBuilder {
+    sequence: NucleotideSequence,
+    quality_metrics: QualityMetrics,
+    merkle_tree: MerkleTree,
+    min_length: u64,
+}
+
+impl SequenceProofBuilder {
+    pub fn new(sequence: NucleotideSequence, quality_metrics: QualityMetrics) -> Self {
+        let merkle_tree = MerkleTree::from_sequence(&sequence);
+
+        Self {
+            sequence,
+            quality_metrics,
+            merkle_tree,
+            min_length: 0,
+        }
+    }
+
+    /// Length the zk proof will claim the sequence reaches.
+    pub fn with_min_length(mut self, min_length: u64) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    pub fn merkle_tree(&self) -> &MerkleTree {
+        &self.merkle_tree
+    }
+
+    pub fn generate_proof(&self) -> Result<SequenceProof, ProofError> {
+        if !self.meets_minimum_requirements() {
+            return Err(ProofError::QualityBelowThreshold);
+        }
+
+        let zk_proof = self.create_zk_proof()?;
+
+        let proof = SequenceProof {
+            merkle_root: self.merkle_tree.root(),
+            quality_metrics: self.quality_metrics.clone(),
+            zk_proof,
+            timestamp: unix_timestamp(),
+        };
+
+        self.validate_proof(&proof)?;
+
+        Ok(proof)
+    }
+
+    fn thresholds(&self) -> ZkThresholds {
+        ZkThresholds {
+            min_length: self.min_length,
+            min_coverage: MIN_PROOF_COVERAGE,
+            min_quality: (MIN_PROOF_QUALITY * 10.0) as u32,
+        }
+    }
+
+    fn create_zk_proof(&self) -> Result<Vec<u8>, ProofError> {
+        let mut proof_builder = ZkProofBuilder::new();
+
+        proof_builder
+            .require(self.thresholds())
+            .add_length_proof(self.sequence.len())
+            .add_quality_proof(&self.quality_metrics)
+            .add_merkle_proof(&self.merkle_tree)
+            .add_coverage_proof(self.quality_metrics.coverage_depth);
+
+        proof_builder.build()
+    }
+
+    /// Round-trips the fresh proof through a validator holding the leaves,
+    /// so a builder bug surfaces here rather than on another node.
+    fn validate_proof(&self, proof: &SequenceProof) -> Result<(), ProofError> {
+        let mut validator = ProofValidator::new(
+            Box::new(HashChainBackend),
+            ZkParameters::default(),
+            self.thresholds(),
+        );
+        validator.register_root(
+            proof.merkle_root,
+            validator.zk_commitment(&proof.merkle_root, &proof.zk_proof)?,
+        );
+        validator.validate_proof_with_leaves(
+            proof,
+            self.merkle_tree.chunk_bases(),
+            self.merkle_tree.sequence_len(),
+            self.merkle_tree.leaves().to_vec(),
+        )
+    }
+
+    fn meets_minimum_requirements(&self) -> bool {
+        self.quality_metrics.coverage_depth >= MIN_PROOF_COVERAGE
+            && self.quality_metrics.quality_score >= MIN_PROOF_QUALITY
+            && self.quality_metrics.error_rate <= MAX_PROOF_ERROR_RATE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_validator_and_proof() -> (ProofValidator, SequenceProof, MerkleTree) {
+        let sequence = NucleotideSequence::from_ascii(&b"ACGTTGCAAGGT".repeat(20)).unwrap();
+        let tree = MerkleTree::with_chunk_bases(&sequence, 32).unwrap();
+        let quality_metrics = QualityMetrics {
+            coverage_depth: 35,
+            quality_score: 32.5,
+            error_rate: 0.0009,
+            confidence_intervals: vec![],
+            insert_size: None,
+        };
+        let thresholds = ZkThresholds {
+            min_length: 200,
+            min_coverage: 30,
+            min_quality: 300,
+        };
+        let mut zk_builder = ZkProofBuilder::new();
+        zk_builder
+            .require(thresholds)
+            .add_length_proof(sequence.len())
+            .add_quality_proof(&quality_metrics)
+            .add_merkle_proof(&tree)
+            .add_coverage_proof(quality_metrics.coverage_depth);
+        let proof = SequenceProof {
+            merkle_root: tree.root(),
+            quality_metrics,
+            zk_proof: zk_builder.build().unwrap(),
+            timestamp: 1_700_000_000,
+        };
+        let validator = ProofValidator::new(
+            Box::new(HashChainBackend),
+            ZkParameters::default(),
+            thresholds,
+        );
+        (validator, proof, tree)
+    }
+
+    #[test]
+    fn test_proof_validator_rejects_tampered_root() {
+        let (mut validator, proof, tree) = sample_validator_and_proof();
+        assert_eq!(
+            validator.validate_proof(&proof),
+            Err(ProofError::UnknownMerkleRoot)
+        );
+        let commitment = validator
+            .zk_commitment(&proof.merkle_root, &proof.zk_proof)
+            .unwrap();
+        validator.register_root(tree.root(), commitment);
+        assert!(validator.validate_proof(&proof).is_ok());
+
+        let leaves = tree.leaves().to_vec();
+        assert!(validator
+            .validate_proof_with_leaves(&proof, 32, 240, leaves.clone())
+            .is_ok());
+        assert_eq!(
+            validator.validate_proof_with_leaves(&proof, 32, 240, leaves[1..].to_vec()),
+            Err(ProofError::LeafCountMismatch)
+        );
+
+        let mut tampered = proof.clone();
+        tampered.merkle_root[0] ^= 1;
+        assert_eq!(
+            validator.validate_proof(&tampered),
+            Err(ProofError::UnknownMerkleRoot)
+        );
+        assert_eq!(
+            validator.validate_proof_with_leaves(&tampered, 32, 240, leaves),
+            Err(ProofError::InvalidMerkleRoot)
+        );
+    }
+
+    #[test]
+    fn test_proof_validator_rejects_malformed_zk_proof() {
+        let (mut validator, proof, tree) = sample_validator_and_proof();
+        let commitment = validator
+            .zk_commitment(&proof.merkle_root, &proof.zk_proof)
+            .unwrap();
+        validator.register_root(tree.root(), commitment);
+
+        let mut truncated = proof.clone();
+        truncated.zk_proof.truncate(truncated.zk_proof.len() - 1);
+        assert_eq!(
+            validator.validate_proof(&truncated),
+            Err(ProofError::TruncatedProof)
+        );
+        truncated.zk_proof.truncate(10);
+        assert_eq!(
+            validator.validate_proof(&truncated),
+            Err(ProofError::TruncatedProof)
+        );
+
+        let mut trailing = proof.clone();
+        trailing.zk_proof.push(0);
+        assert_eq!(
+            validator.validate_proof(&trailing),
+            Err(ProofError::TrailingProofBytes)
+        );
+
+        let mut future = proof.clone();
+        future.zk_proof[0] = 9;
+        assert_eq!(
+            validator.validate_proof(&future),
+            Err(ProofError::UnsupportedProofVersion(9))
+        );
+
+        let mut lifted = proof.clone();
+        lifted.zk_proof[2] ^= 1;
+        assert_eq!(
+            validator.validate_proof(&lifted),
+            Err(ProofError::ZkRootMismatch)
+        );
+
+        let mut forged = proof.clone();
+        let last = forged.zk_proof.len() - 1;
+        forged.zk_proof[last] ^= 1;
+        assert_eq!(
+            validator.validate_proof(&forged),
+            Err(ProofError::InvalidZkProof)
+        );
+
+        // A well-formed proof of inflated metrics carries its own
+        // commitments, not the pinned ones.
+        let mut inflated = ZkProofBuilder::new();
+        inflated
+            .require(validator.required_thresholds)
+            .add_length_proof(10_000)
+            .add_quality_proof(&QualityMetrics {
+                quality_score: 40.0,
+                ..proof.quality_metrics.clone()
+            })
+            .add_merkle_proof(&tree)
+            .add_coverage_proof(90);
+        let overclaimed = SequenceProof {
+            zk_proof: inflated.build().unwrap(),
+            ..proof.clone()
+        };
+        assert_eq!(
+            validator.validate_proof(&overclaimed),
+            Err(ProofError::ZkCommitmentMismatch)
+        );
+
+        let mut garbled = proof;
+        garbled.zk_proof.truncate(38);
+        garbled.zk_proof[34..38].copy_from_slice(&0u32.to_le_bytes());
+        assert_eq!(
+            validator.validate_proof(&garbled),
+            Err(ProofError::MalformedZkPayload)
+        );
+
+        validator.required_thresholds.min_coverage = 40;
+        let (_, proof, _) = sample_validator_and_proof();
+        assert_eq!(
+            validator.validate_proof(&proof),
+            Err(ProofError::StatementBelowRequirement)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::proof::{InstrumentRecord, RejectReason, ValidationProof, Verdict};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use solana_program::hash::Hasher;
+use solana_program::pubkey::Pubkey;
+
+const VALIDATION_SIGNING_DOMAIN: &[u8] = b"biomod/validation-proof";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureError {
+    Missing,
+    Malformed,
+    InvalidKey,
+    Invalid,
+}
+
+impl ValidationProof {
+    /// Builds a proof signed by `signing_key`; the validator is the key's
+    /// public half, so the signer cannot claim another validator's identity.
+    pub fn new_signed(
+        signing_key: &SigningKey,
+        sequence_hash: [u8; 32],
+        merkle_root: [u8; 32],
+        zk_proof: Vec<u8>,
+        timestamp: i64,
+    ) -> Self {
+        Self::sign(
+            signing_key,
+            sequence_hash,
+            merkle_root,
+            zk_proof,
+            timestamp,
+            None,
+            Verdict::Accept,
+        )
+    }
+
+    /// Like `new_signed`, with the instrument covered by the signature.
+    pub fn new_signed_with_instrument(
+        signing_key: &SigningKey,
+        sequence_hash: [u8; 32],
+        merkle_root: [u8; 32],
+        zk_proof: Vec<u8>,
+        timestamp: i64,
+        instrument: InstrumentRecord,
+    ) -> Self {
+        Self::sign(
+            signing_key,
+            sequence_hash,
+            merkle_root,
+            zk_proof,
+            timestamp,
+            Some(instrument),
+            Verdict::Accept,
+        )
+    }
+
+    /// A signed vote against the sequence. `merkle_root` is the root the
+    /// validator computed, and the zk proof may be empty.
+    pub fn new_signed_rejection(
+        signing_key: &SigningKey,
+        sequence_hash: [u8; 32],
+        merkle_root: [u8; 32],
+        zk_proof: Vec<u8>,
+        timestamp: i64,
+        reason: RejectReason,
+    ) -> Self {
+        Self::sign(
+            signing_key,
+            sequence_hash,
+            merkle_root,
+            zk_proof,
+            timestamp,
+            None,
+            Verdict::Reject(reason),
+        )
+    }
+
+    fn sign(
+        signing_key: &SigningKey,
+        sequence_hash: [u8; 32],
+        merkle_root: [u8; 32],
+        zk_proof: Vec<u8>,
+        timestamp: i64,
+        instrument: Option<InstrumentRecord>,
+        verdict: Verdict,
+    ) -> Self {
+        let mut proof = Self {
+            validator: Pubkey::new_from_array(signing_key.verifying_key().to_bytes()),
+            sequence_hash,
+            merkle_root,
+            zk_proof,
+            signature: vec![],
+            timestamp,
+            instrument,
+            verdict,
+        };
+        proof.signature = signing_key
+            .sign(&proof.signing_message())
+            .to_bytes()
+            .to_vec();
+        proof
+    }
+
+    /// Bytes covered by the signature: the domain tag followed by the
+    /// canonical encoding with an empty signature field.
+    pub fn signing_message(&self) -> Vec<u8> {
+        [VALIDATION_SIGNING_DOMAIN, &self.unsigned_canonical_bytes()].concat()
+    }
+
+    /// Identifies the signed content independently of the signature bytes,
+    /// so a re-signed copy of the same proof is still caught as a replay.
+    pub fn signing_digest(&self) -> [u8; 32] {
+        let mut hasher = Hasher::default();
+        hasher.hash(&self.signing_message());
+        hasher.result().to_bytes()
+    }
+
+    pub fn verify_signature(&self) -> Result<(), SignatureError> {
+        if self.signature.is_empty() {
+            return Err(SignatureError::Missing);
+        }
+        let signature =
+            Signature::from_slice(&self.signature).map_err(|_| SignatureError::Malformed)?;
+        let key = VerifyingKey::from_bytes(&self.validator.to_bytes())
+            .map_err(|_| SignatureError::InvalidKey)?;
+        key.verify_strict(&self.signing_message(), &signature)
+            .map_err(|_| SignatureError::Invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_signed_proof(seed: u8) -> ValidationProof {
+        ValidationProof::new_signed(
+            &SigningKey::from_bytes(&[seed; 32]),
+            [1; 32],
+            [2; 32],
+            vec![3; 48],
+            1_700_000_100,
+        )
+    }
+
+    #[test]
+    fn test_validation_proof_signatures() {
+        let proof = sample_signed_proof(7);
+        assert!(proof.verify_signature().is_ok());
+
+        let mut wrong_key = proof.clone();
+        wrong_key.validator = sample_signed_proof(8).validator;
+        assert_eq!(wrong_key.verify_signature(), Err(SignatureError::Invalid));
+
+        let mut tampered = proof.clone();
+        tampered.zk_proof[0] ^= 1;
+        assert_eq!(tampered.verify_signature(), Err(SignatureError::Invalid));
+
+        let mut malformed = proof.clone();
+        malformed.signature.truncate(63);
+        assert_eq!(malformed.verify_signature(), Err(SignatureError::Malformed));
+        malformed.signature.clear();
+        assert_eq!(malformed.verify_signature(), Err(SignatureError::Missing));
+    }
+}
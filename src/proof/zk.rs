@@ -0,0 +1,494 @@
+use crate::model::QualityMetrics;
+use crate::proof::merkle::MerkleTree;
+use crate::proof::{ProofError, ZkProofEnvelope};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+use solana_program::hash::{hashv, Hasher};
+
+pub const HASH_CHAIN_BACKEND_ID: u8 = 1;
+const ZK_SETUP_DOMAIN: &[u8] = b"biomod/zk-setup";
+const ZK_SEED_DOMAIN: &[u8] = b"biomod/zk-seed";
+const ZK_CHAIN_DOMAIN: &[u8] = b"biomod/zk-chain";
+const ZK_BLINDING_DOMAIN: &[u8] = b"biomod/zk-blinding";
+const ZK_COMMITMENT_DOMAIN: &[u8] = b"biomod/zk-commitment";
+const ZK_CLAIM_LABELS: [&[u8]; 3] = [b"length", b"coverage", b"quality"];
+
+/// SHA-256 over `vals`, one `sol_sha256` syscall on-chain.
+fn sha256(vals: &[&[u8]]) -> [u8; 32] {
+    #[cfg(test)]
+    crate::program::bank::charge_sha256(vals);
+    hashv(vals).to_bytes()
+}
+
+/// Public parameters produced by `ZkBackend::setup` and shared by prover and
+/// verifier.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ZkParameters {
+    pub backend: u8,
+    pub domain: [u8; 32],
+    pub length_unit: u64, // Bases per step of the length claim
+    pub max_chain: u64,   // Largest provable threshold, in steps
+}
+
+impl Default for ZkParameters {
+    fn default() -> Self {
+        HashChainBackend.setup(b"biomod/default")
+    }
+}
+
+/// Lower bounds a proof claims on the values its commitments were made from.
+/// `min_quality` is in tenths of a Phred point.
+#[derive(
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+)]
+pub struct ZkThresholds {
+    pub min_length: u64,
+    pub min_coverage: u32,
+    pub min_quality: u32,
+}
+
+impl ZkThresholds {
+    pub fn covers(&self, required: &ZkThresholds) -> bool {
+        self.min_length >= required.min_length
+            && self.min_coverage >= required.min_coverage
+            && self.min_quality >= required.min_quality
+    }
+}
+
+#[derive(
+    BorshSerialize, BorshDeserialize, Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq,
+)]
+pub struct ZkStatement {
+    pub merkle_root: [u8; 32],
+    pub thresholds: ZkThresholds,
+}
+
+/// Private inputs. Never leaves the prover.
+#[derive(Debug, Clone, Default)]
+pub struct ZkWitness {
+    pub length: u64,
+    pub coverage_depth: u32,
+    pub quality: u32,
+    pub blinding: [u8; 32],
+}
+
+/// Envelope payload: the public statement followed by backend proof bytes.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ZkPayload {
+    pub statement: ZkStatement,
+    pub proof: Vec<u8>,
+}
+
+pub trait ZkBackend {
+    fn id(&self) -> u8;
+    fn setup(&self, seed: &[u8]) -> ZkParameters;
+    fn prove(
+        &self,
+        params: &ZkParameters,
+        statement: &ZkStatement,
+        witness: &ZkWitness,
+    ) -> Result<Vec<u8>, ProofError>;
+    /// Checks `proof` against the value commitments it carries. That says
+    /// nothing on its own: the caller must also match `commitment` against
+    /// one pinned before the proof was made.
+    fn verify(
+        &self,
+        params: &ZkParameters,
+        statement: &ZkStatement,
+        proof: &[u8],
+    ) -> Result<(), ProofError>;
+    /// Digest of the value commitments in `proof`. It does not depend on
+    /// the statement's thresholds, so it can be pinned at registration.
+    fn commitment(&self, proof: &[u8]) -> Result<[u8; 32], ProofError>;
+    /// Whether proofs reveal nothing beyond their statement. False unless a
+    /// backend overrides it, which only a real zero-knowledge backend may.
+    fn zero_knowledge(&self) -> bool {
+        false
+    }
+}
+
+/// Pure-Rust default backend built on hash-chain threshold proofs.
+///
+/// For each claim the prover commits to `C = H^v(seed)` and opens
+/// `O = H^(v - t)(seed)`; the verifier checks `H^t(O) == C`. Both come from
+/// the proof, so this only shows that `C` lies at least `t` steps down some
+/// chain: anyone can pick `O` and claim any threshold. The claim holds only
+/// if `C` was pinned beforehand by a party trusted to have measured the
+/// sequence, which is why `ProofValidator` matches `commitment` against the
+/// one registered with the root. Seeds are keyed by the Merkle root and a
+/// blinding value derived from the leaves, which keeps `v` from a verifier
+/// without the leaves, but this is not a zero-knowledge proof; plug in a
+/// range-proof backend where that matters.
+pub struct HashChainBackend;
+
+impl HashChainBackend {
+    fn seed(
+        params: &ZkParameters,
+        statement: &ZkStatement,
+        label: &[u8],
+        blinding: &[u8; 32],
+    ) -> [u8; 32] {
+        sha256(&[
+            ZK_SEED_DOMAIN,
+            &params.domain,
+            &statement.merkle_root,
+            label,
+            blinding,
+        ])
+    }
+
+    fn step(
+        params: &ZkParameters,
+        statement: &ZkStatement,
+        label: &[u8],
+        link: &[u8; 32],
+    ) -> [u8; 32] {
+        sha256(&[
+            ZK_CHAIN_DOMAIN,
+            &params.domain,
+            &statement.merkle_root,
+            label,
+            link,
+        ])
+    }
+
+    /// (value, threshold) pairs in steps, in `ZK_CLAIM_LABELS` order.
+    fn claims(
+        params: &ZkParameters,
+        statement: &ZkStatement,
+        witness: &ZkWitness,
+    ) -> [(u64, u64); 3] {
+        let thresholds = &statement.thresholds;
+        [
+            (
+                witness.length / params.length_unit,
+                thresholds.min_length.div_ceil(params.length_unit),
+            ),
+            (
+                witness.coverage_depth as u64,
+                thresholds.min_coverage as u64,
+            ),
+            (witness.quality as u64, thresholds.min_quality as u64),
+        ]
+    }
+}
+
+impl ZkBackend for HashChainBackend {
+    fn id(&self) -> u8 {
+        HASH_CHAIN_BACKEND_ID
+    }
+
+    fn setup(&self, seed: &[u8]) -> ZkParameters {
+        ZkParameters {
+            backend: HASH_CHAIN_BACKEND_ID,
+            domain: sha256(&[ZK_SETUP_DOMAIN, seed]),
+            length_unit: 100,
+            max_chain: 1 << 16,
+        }
+    }
+
+    fn prove(
+        &self,
+        params: &ZkParameters,
+        statement: &ZkStatement,
+        witness: &ZkWitness,
+    ) -> Result<Vec<u8>, ProofError> {
+        let mut proof = Vec::with_capacity(ZK_CLAIM_LABELS.len() * 64);
+        for (label, (value, threshold)) in ZK_CLAIM_LABELS
+            .iter()
+            .zip(Self::claims(params, statement, witness))
+        {
+            if threshold > params.max_chain {
+                return Err(ProofError::ThresholdOutOfRange);
+            }
+            let value = value.min(params.max_chain);
+            if value < threshold {
+                return Err(ProofError::StatementNotSatisfied);
+            }
+
+            let mut link = Self::seed(params, statement, label, &witness.blinding);
+            let mut opening = link;
+            for step in 0..value {
+                if step == value - threshold {
+                    opening = link;
+                }
+                link = Self::step(params, statement, label, &link);
+            }
+            if threshold == 0 {
+                opening = link;
+            }
+            proof.extend_from_slice(&link);
+            proof.extend_from_slice(&opening);
+        }
+        Ok(proof)
+    }
+
+    fn verify(
+        &self,
+        params: &ZkParameters,
+        statement: &ZkStatement,
+        proof: &[u8],
+    ) -> Result<(), ProofError> {
+        if proof.len() != ZK_CLAIM_LABELS.len() * 64 {
+            return Err(ProofError::MalformedZkPayload);
+        }
+        let witness = ZkWitness::default();
+        for ((label, (_, threshold)), claim) in ZK_CLAIM_LABELS
+            .iter()
+            .zip(Self::claims(params, statement, &witness))
+            .zip(proof.chunks(64))
+        {
+            if threshold > params.max_chain {
+                return Err(ProofError::ThresholdOutOfRange);
+            }
+            let commitment: [u8; 32] = claim[..32].try_into().unwrap();
+            let mut link: [u8; 32] = claim[32..].try_into().unwrap();
+            for _ in 0..threshold {
+                link = Self::step(params, statement, label, &link);
+            }
+            if link != commitment {
+                return Err(ProofError::InvalidZkProof);
+            }
+        }
+        Ok(())
+    }
+
+    fn commitment(&self, proof: &[u8]) -> Result<[u8; 32], ProofError> {
+        if proof.len() != ZK_CLAIM_LABELS.len() * 64 {
+            return Err(ProofError::MalformedZkPayload);
+        }
+        let mut vals = vec![ZK_COMMITMENT_DOMAIN];
+        vals.extend(proof.chunks(64).map(|claim| &claim[..32]));
+        Ok(sha256(&vals))
+    }
+}
+
+/// Builds the zk proofs validators attach to acceptances. The default backend
+/// is `HashChainBackend`, whose proofs are not zero-knowledge; check
+/// `zero_knowledge` before handing one to a party that must not learn the
+/// sequence's metrics.
+pub struct ZkProofBuilder {
+    backend: Box<dyn ZkBackend>,
+    params: ZkParameters,
+    statement: ZkStatement,
+    witness: ZkWitness,
+    has_root: bool,
+}
+
+impl ZkProofBuilder {
+    pub fn new() -> Self {
+        Self::with_backend(Box::new(HashChainBackend), ZkParameters::default())
+    }
+
+    pub fn with_backend(backend: Box<dyn ZkBackend>, params: ZkParameters) -> Self {
+        Self {
+            backend,
+            params,
+            statement: ZkStatement::default(),
+            witness: ZkWitness::default(),
+            has_root: false,
+        }
+    }
+
+    pub fn zero_knowledge(&self) -> bool {
+        self.backend.zero_knowledge()
+    }
+
+    pub fn require(&mut self, thresholds: ZkThresholds) -> &mut Self {
+        self.statement.thresholds = thresholds;
+        self
+    }
+
+    pub fn add_length_proof(&mut self, length: u64) -> &mut Self {
+        self.witness.length = length;
+        self
+    }
+
+    pub fn add_quality_proof(&mut self, metrics: &QualityMetrics) -> &mut Self {
+        self.witness.quality = (metrics.quality_score.max(0.0) * 10.0) as u32;
+        self
+    }
+
+    /// Binds the proof to the tree's root. The blinding value is derived from
+    /// the leaves, which only the sequence holder knows.
+    pub fn add_merkle_proof(&mut self, tree: &MerkleTree) -> &mut Self {
+        let mut hasher = Hasher::default();
+        hasher.hash(ZK_BLINDING_DOMAIN);
+        for leaf in tree.leaves() {
+            hasher.hash(leaf);
+        }
+        self.statement.merkle_root = tree.root();
+        self.witness.blinding = hasher.result().to_bytes();
+        self.has_root = true;
+        self
+    }
+
+    pub fn add_coverage_proof(&mut self, coverage_depth: u32) -> &mut Self {
+        self.witness.coverage_depth = coverage_depth;
+        self
+    }
+
+    /// Proves the statement and wraps it in a `ZkProofEnvelope`.
+    pub fn build(&self) -> Result<Vec<u8>, ProofError> {
+        if !self.has_root {
+            return Err(ProofError::IncompleteStatement);
+        }
+        if self.params.backend != self.backend.id() {
+            return Err(ProofError::UnsupportedZkBackend(self.params.backend));
+        }
+
+        let proof = self
+            .backend
+            .prove(&self.params, &self.statement, &self.witness)?;
+        let payload = ZkPayload {
+            statement: self.statement.clone(),
+            proof,
+        };
+
+        Ok(ZkProofEnvelope {
+            backend: self.backend.id(),
+            merkle_root: self.statement.merkle_root,
+            payload: borsh::to_vec(&payload).map_err(|_| ProofError::MalformedZkPayload)?,
+        }
+        .encode())
+    }
+}
+
+impl Default for ZkProofBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::NucleotideSequence;
+
+    fn sample_metrics() -> QualityMetrics {
+        QualityMetrics {
+            coverage_depth: 42,
+            quality_score: 33.7,
+            error_rate: 0.0004,
+            confidence_intervals: vec![],
+            insert_size: None,
+        }
+    }
+
+    #[test]
+    fn test_zk_proof_verifies_thresholds_against_commitments() {
+        let sequence = NucleotideSequence::from_ascii(&b"GATTACA".repeat(300)).unwrap();
+        let tree = MerkleTree::from_sequence(&sequence);
+        let required = ZkThresholds {
+            min_length: 2_000,
+            min_coverage: 30,
+            min_quality: 300,
+        };
+
+        let mut builder = ZkProofBuilder::new();
+        builder
+            .require(required)
+            .add_length_proof(sequence.len())
+            .add_quality_proof(&sample_metrics())
+            .add_merkle_proof(&tree)
+            .add_coverage_proof(42);
+        let bytes = builder.build().unwrap();
+
+        let envelope = ZkProofEnvelope::decode(&bytes).unwrap();
+        let payload = ZkPayload::try_from_slice(&envelope.payload).unwrap();
+        assert_eq!(payload.statement.thresholds, required);
+        assert_eq!(payload.statement.merkle_root, tree.root());
+        let params = ZkParameters::default();
+        assert!(HashChainBackend
+            .verify(&params, &payload.statement, &payload.proof)
+            .is_ok());
+
+        let mut inflated = payload.statement.clone();
+        inflated.thresholds.min_coverage = 43;
+        assert_eq!(
+            HashChainBackend.verify(&params, &inflated, &payload.proof),
+            Err(ProofError::InvalidZkProof)
+        );
+        let mut moved = payload.statement.clone();
+        moved.merkle_root[0] ^= 1;
+        assert!(HashChainBackend
+            .verify(&params, &moved, &payload.proof)
+            .is_err());
+
+        // The commitment does not depend on the thresholds claimed.
+        let mut lenient = builder;
+        lenient.require(ZkThresholds::default());
+        let lenient = ZkProofEnvelope::decode(&lenient.build().unwrap()).unwrap();
+        let lenient = ZkPayload::try_from_slice(&lenient.payload).unwrap();
+        let commitment = HashChainBackend.commitment(&payload.proof).unwrap();
+        assert_eq!(HashChainBackend.commitment(&lenient.proof), Ok(commitment));
+    }
+
+    #[test]
+    fn test_zk_forgery_changes_commitment() {
+        // A prover without the data picks its own openings for an inflated
+        // statement. The chains check out, but the commitments cannot match
+        // the ones pinned for the honest proof.
+        let sequence = NucleotideSequence::from_ascii(&b"ACGT".repeat(100)).unwrap();
+        let mut builder = ZkProofBuilder::new();
+        builder
+            .add_length_proof(sequence.len())
+            .add_merkle_proof(&MerkleTree::from_sequence(&sequence));
+        let honest = ZkProofEnvelope::decode(&builder.build().unwrap()).unwrap();
+        let honest = ZkPayload::try_from_slice(&honest.payload).unwrap();
+
+        let params = ZkParameters::default();
+        let statement = ZkStatement {
+            thresholds: ZkThresholds {
+                min_length: 1_000_000,
+                min_coverage: 100,
+                min_quality: 400,
+            },
+            ..honest.statement.clone()
+        };
+        let witness = ZkWitness {
+            length: 1_000_000,
+            coverage_depth: 100,
+            quality: 400,
+            blinding: [7; 32],
+        };
+        let forged = HashChainBackend
+            .prove(&params, &statement, &witness)
+            .unwrap();
+        assert!(HashChainBackend
+            .verify(&params, &statement, &forged)
+            .is_ok());
+        assert_ne!(
+            HashChainBackend.commitment(&forged),
+            HashChainBackend.commitment(&honest.proof)
+        );
+    }
+
+    #[test]
+    fn test_zk_proof_refuses_unsatisfied_statement() {
+        let sequence = NucleotideSequence::from_ascii(&b"ACGT".repeat(100)).unwrap();
+        let mut builder = ZkProofBuilder::new();
+        builder
+            .require(ZkThresholds {
+                min_length: 1_000,
+                ..Default::default()
+            })
+            .add_length_proof(sequence.len())
+            .add_merkle_proof(&MerkleTree::from_sequence(&sequence));
+        assert_eq!(builder.build(), Err(ProofError::StatementNotSatisfied));
+        assert_eq!(
+            ZkProofBuilder::new().build(),
+            Err(ProofError::IncompleteStatement)
+        );
+        assert!(!builder.zero_knowledge());
+    }
+}
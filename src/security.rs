@@ -0,0 +1,390 @@
+use crate::model::{ValidatorHardware, ValidatorInfo};
+use crate::proof::ValidationProof;
+use borsh::{BorshDeserialize, BorshSerialize};
+
+const MIN_CPU_CORES: u32 = 64;
+const MIN_RAM_GB: u32 = 256;
+const MIN_STORAGE_TB: u32 = 4;
+const MIN_NETWORK_BANDWIDTH: u32 = 1; // Gbps
+pub const DEFAULT_MAX_CALIBRATION_AGE: i64 = 90 * 24 * 60 * 60; // Seconds
+pub const FULL_WEIGHT: u8 = 100;
+
+#[derive(Debug, PartialEq)]
+pub enum SecurityError {
+    InsufficientStake,
+    LowReputation,
+    InsufficientHardware,
+    InsufficientCores,
+    InsufficientRAM,
+    InsufficientStorage,
+    InsufficientBandwidth,
+    MissingInstrument,
+    UnknownInstrument,
+    CalibrationMismatch,
+    StaleCalibration,
+    ValidatorMismatch,
+    InvalidSignature,
+}
+
+pub fn verify_hardware_requirements(validator: &ValidatorHardware) -> Result<(), SecurityError> {
+    if validator.cpu_cores < MIN_CPU_CORES {
+        return Err(SecurityError::InsufficientCores);
+    }
+    if validator.ram_gb < MIN_RAM_GB {
+        return Err(SecurityError::InsufficientRAM);
+    }
+    if validator.storage_tb < MIN_STORAGE_TB {
+        return Err(SecurityError::InsufficientStorage);
+    }
+    if validator.network_bandwidth < MIN_NETWORK_BANDWIDTH {
+        return Err(SecurityError::InsufficientBandwidth);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StakeRequirements {
+    pub minimum_stake: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ReputationSystem {
+    pub reputation_threshold: u32,
+}
+
+impl ReputationSystem {
+    pub fn verify_reputation(&self, validator: &ValidatorInfo) -> bool {
+        validator.reputation_score >= self.reputation_threshold
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SlashingConditions {
+    pub slash_percentage: u8,        // Share of stake forfeited per offence
+    pub max_failed_validations: u64, // Failures tolerated before slashing
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StaleCalibrationAction {
+    Reject,
+    /// Keep the validation at this percentage of a fresh one's weight.
+    DownWeight(u8),
+}
+
+/// How old an instrument's calibration may be when a validation is counted.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CalibrationPolicy {
+    pub max_age: i64, // Seconds between last calibration and the counting clock
+    pub stale_action: StaleCalibrationAction,
+}
+
+impl CalibrationPolicy {
+    /// Checks the instrument recorded in `proof` against the sequencers
+    /// `validator` declares and returns the weight, in percent, the
+    /// validation carries at `now`. The recorded calibration may not
+    /// postdate the declared one, the proof or `now`. Age is measured at
+    /// `now`, the caller's round or cluster clock, since the proof's own
+    /// timestamp is the validator's to choose. The proof is assumed to be
+    /// `validator`'s and signed.
+    pub fn weight(
+        &self,
+        validator: &ValidatorInfo,
+        proof: &ValidationProof,
+        now: i64,
+    ) -> Result<u8, SecurityError> {
+        let instrument = proof
+            .instrument
+            .as_ref()
+            .ok_or(SecurityError::MissingInstrument)?;
+        let sequencer = validator
+            .hardware_specs
+            .sequencer_models
+            .iter()
+            .find(|sequencer| instrument.identifies(sequencer))
+            .ok_or(SecurityError::UnknownInstrument)?;
+        let calibrated = instrument.last_calibration;
+        if calibrated > sequencer.last_calibration
+            || calibrated > proof.timestamp
+            || calibrated > now
+        {
+            return Err(SecurityError::CalibrationMismatch);
+        }
+
+        match now.checked_sub(calibrated) {
+            Some(age) if age <= self.max_age => Ok(FULL_WEIGHT),
+            _ => match self.stale_action {
+                StaleCalibrationAction::Reject => Err(SecurityError::StaleCalibration),
+                StaleCalibrationAction::DownWeight(weight) => Ok(weight.min(FULL_WEIGHT)),
+            },
+        }
+    }
+
+    /// The weight a vote counts with at `now`: acceptances by `weight`, or
+    /// nothing if they fail it, and rejections in full.
+    pub fn vote_weight(&self, validator: &ValidatorInfo, proof: &ValidationProof, now: i64) -> u8 {
+        if !proof.verdict.is_accept() {
+            return FULL_WEIGHT;
+        }
+        self.weight(validator, proof, now).unwrap_or(0)
+    }
+}
+
+impl Default for CalibrationPolicy {
+    fn default() -> Self {
+        Self {
+            max_age: DEFAULT_MAX_CALIBRATION_AGE,
+            stale_action: StaleCalibrationAction::Reject,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SecurityManager {
+    stake_requirements: StakeRequirements,
+    reputation_system: ReputationSystem,
+    slashing_conditions: SlashingConditions,
+    calibration_policy: CalibrationPolicy,
+}
+
+impl SecurityManager {
+    pub fn new(
+        stake_requirements: StakeRequirements,
+        reputation_system: ReputationSystem,
+        slashing_conditions: SlashingConditions,
+    ) -> Self {
+        Self {
+            stake_requirements,
+            reputation_system,
+            slashing_conditions,
+            calibration_policy: CalibrationPolicy::default(),
+        }
+    }
+
+    pub fn with_calibration_policy(mut self, calibration_policy: CalibrationPolicy) -> Self {
+        self.calibration_policy = calibration_policy;
+        self
+    }
+
+    pub fn slashing_conditions(&self) -> &SlashingConditions {
+        &self.slashing_conditions
+    }
+
+    pub fn verify_validator(&self, validator: &ValidatorInfo) -> Result<(), SecurityError> {
+        if !self.verify_stake(validator) {
+            return Err(SecurityError::InsufficientStake);
+        }
+
+        if !self.verify_reputation(validator) {
+            return Err(SecurityError::LowReputation);
+        }
+
+        if !self.verify_hardware(validator) {
+            return Err(SecurityError::InsufficientHardware);
+        }
+
+        Ok(())
+    }
+
+    /// `CalibrationPolicy::weight` under this manager's policy, for a proof
+    /// not yet known to be `validator`'s own.
+    pub fn verify_calibration(
+        &self,
+        validator: &ValidatorInfo,
+        proof: &ValidationProof,
+        now: i64,
+    ) -> Result<u8, SecurityError> {
+        if proof.validator != validator.pubkey {
+            return Err(SecurityError::ValidatorMismatch);
+        }
+        proof
+            .verify_signature()
+            .map_err(|_| SecurityError::InvalidSignature)?;
+        self.calibration_policy.weight(validator, proof, now)
+    }
+
+    fn verify_stake(&self, validator: &ValidatorInfo) -> bool {
+        validator.stake >= self.stake_requirements.minimum_stake
+    }
+
+    fn verify_reputation(&self, validator: &ValidatorInfo) -> bool {
+        self.reputation_system.verify_reputation(validator)
+    }
+
+    fn verify_hardware(&self, validator: &ValidatorInfo) -> bool {
+        verify_hardware_requirements(&validator.hardware_specs).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SequencerModel;
+    use crate::proof::InstrumentRecord;
+    use ed25519_dalek::SigningKey;
+    use solana_program::pubkey::Pubkey;
+
+    const DAY: i64 = 24 * 60 * 60;
+
+    fn sample_validator() -> ValidatorInfo {
+        ValidatorInfo {
+            stake: 1_000,
+            reputation_score: 80,
+            hardware_specs: ValidatorHardware {
+                cpu_cores: 64,
+                ram_gb: 256,
+                storage_tb: 4,
+                network_bandwidth: 10,
+                sequencer_models: vec![],
+            },
+            ..ValidatorInfo::new(Pubkey::new_from_array([1; 32]))
+        }
+    }
+
+    #[test]
+    fn test_verify_validator() {
+        let manager = SecurityManager::new(
+            StakeRequirements { minimum_stake: 500 },
+            ReputationSystem {
+                reputation_threshold: 50,
+            },
+            SlashingConditions::default(),
+        );
+        let validator = sample_validator();
+        assert!(manager.verify_validator(&validator).is_ok());
+
+        let poor = ValidatorInfo {
+            stake: 100,
+            ..validator.clone()
+        };
+        assert_eq!(
+            manager.verify_validator(&poor),
+            Err(SecurityError::InsufficientStake)
+        );
+
+        let untrusted = ValidatorInfo {
+            reputation_score: 10,
+            ..validator.clone()
+        };
+        assert_eq!(
+            manager.verify_validator(&untrusted),
+            Err(SecurityError::LowReputation)
+        );
+
+        let mut underpowered = validator;
+        underpowered.hardware_specs.ram_gb = 64;
+        assert_eq!(
+            verify_hardware_requirements(&underpowered.hardware_specs),
+            Err(SecurityError::InsufficientRAM)
+        );
+        assert_eq!(
+            manager.verify_validator(&underpowered),
+            Err(SecurityError::InsufficientHardware)
+        );
+    }
+
+    #[test]
+    fn test_verify_calibration() {
+        let signing_key = SigningKey::from_bytes(&[1; 32]);
+        let novaseq = SequencerModel {
+            manufacturer: "Illumina".to_string(),
+            model: "NovaSeq 6000".to_string(),
+            serial_number: "A00123".to_string(),
+            throughput: 6000,
+            error_rate: 0.001,
+            last_calibration: 1_700_000_000,
+        };
+        let mut validator = sample_validator();
+        validator.pubkey = Pubkey::new_from_array(signing_key.verifying_key().to_bytes());
+        validator
+            .hardware_specs
+            .sequencer_models
+            .push(novaseq.clone());
+        let proof_at = |timestamp: i64, instrument: Option<InstrumentRecord>| match instrument {
+            Some(instrument) => ValidationProof::new_signed_with_instrument(
+                &signing_key,
+                [1; 32],
+                [2; 32],
+                vec![],
+                timestamp,
+                instrument,
+            ),
+            None => ValidationProof::new_signed(&signing_key, [1; 32], [2; 32], vec![], timestamp),
+        };
+        let record = InstrumentRecord::from(&novaseq);
+
+        let manager = SecurityManager::default();
+        let fresh = proof_at(1_700_000_000 + 30 * DAY, Some(record.clone()));
+        assert_eq!(
+            manager.verify_calibration(&validator, &fresh, 1_700_000_000 + 30 * DAY),
+            Ok(FULL_WEIGHT)
+        );
+        // Age is measured at the caller's clock, not the proof's timestamp.
+        let later = 1_700_000_000 + 91 * DAY;
+        assert_eq!(
+            manager.verify_calibration(&validator, &fresh, later),
+            Err(SecurityError::StaleCalibration)
+        );
+
+        let lenient = SecurityManager::default().with_calibration_policy(CalibrationPolicy {
+            max_age: 90 * DAY,
+            stale_action: StaleCalibrationAction::DownWeight(25),
+        });
+        assert_eq!(
+            lenient.verify_calibration(&validator, &fresh, later),
+            Ok(25)
+        );
+        // An age beyond `i64` is stale, not an overflow.
+        let ancient = InstrumentRecord {
+            last_calibration: i64::MIN,
+            ..record.clone()
+        };
+        assert_eq!(
+            lenient.verify_calibration(&validator, &proof_at(later, Some(ancient)), later),
+            Ok(25)
+        );
+
+        assert_eq!(
+            manager.verify_calibration(&validator, &proof_at(1_700_000_100, None), later),
+            Err(SecurityError::MissingInstrument)
+        );
+        let other_machine = InstrumentRecord {
+            serial_number: "A00999".to_string(),
+            ..record.clone()
+        };
+        assert_eq!(
+            manager.verify_calibration(
+                &validator,
+                &proof_at(1_700_000_100, Some(other_machine)),
+                later
+            ),
+            Err(SecurityError::UnknownInstrument)
+        );
+        // Claiming a calibration the validator never declared.
+        let postdated = InstrumentRecord {
+            last_calibration: 1_700_000_000 + DAY,
+            ..record
+        };
+        assert_eq!(
+            manager.verify_calibration(
+                &validator,
+                &proof_at(1_700_000_000 + 2 * DAY, Some(postdated)),
+                later
+            ),
+            Err(SecurityError::CalibrationMismatch)
+        );
+
+        // The proof must be the validator's own, as signed.
+        let mut borrowed = fresh.clone();
+        borrowed.validator = Pubkey::new_from_array([9; 32]);
+        assert_eq!(
+            manager.verify_calibration(&validator, &borrowed, later),
+            Err(SecurityError::ValidatorMismatch)
+        );
+        let mut edited = fresh;
+        edited.instrument.as_mut().unwrap().last_calibration -= 1;
+        assert_eq!(
+            manager.verify_calibration(&validator, &edited, later),
+            Err(SecurityError::InvalidSignature)
+        );
+    }
+}